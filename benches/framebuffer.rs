@@ -0,0 +1,96 @@
+//! Host benchmarks of the display buffer, run with `cargo bench`
+
+use core::convert::Infallible;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use epd_spectra::{packing, Display2in66, DisplayBuffer, DisplayRotation, TriColor};
+
+const COLORS: [TriColor; 3] = [TriColor::White, TriColor::Black, TriColor::Red];
+
+fn rotated(rotation: DisplayRotation) -> Display2in66 {
+    let mut display = Display2in66::new();
+    display.set_rotation(rotation);
+    display
+}
+
+fn draw_iter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("draw_iter");
+    for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90] {
+        let mut display = rotated(rotation);
+        let pixels: Vec<_> = display
+            .bounding_box()
+            .points()
+            .enumerate()
+            .map(|(i, p)| Pixel(p, COLORS[i % 3]))
+            .collect();
+        group.bench_function(BenchmarkId::from_parameter(format!("{rotation:?}")), |b| {
+            b.iter(|| display.draw_iter(black_box(pixels.iter().copied())));
+        });
+    }
+}
+
+fn fill_solid(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fill_solid");
+    for (name, area) in [
+        (
+            "aligned",
+            Rectangle::new(Point::new(8, 10), Size::new(128, 200)),
+        ),
+        (
+            "unaligned",
+            Rectangle::new(Point::new(3, 10), Size::new(133, 200)),
+        ),
+    ] {
+        for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90] {
+            let mut display = rotated(rotation);
+            group.bench_function(BenchmarkId::new(name, format!("{rotation:?}")), |b| {
+                b.iter(|| display.fill_solid(black_box(&area), TriColor::Red));
+            });
+        }
+    }
+}
+
+fn clear(c: &mut Criterion) {
+    let mut display = Display2in66::new();
+    c.bench_function("clear", |b| {
+        b.iter(|| display.clear(black_box(TriColor::Black)));
+    });
+}
+
+fn planes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("planes");
+    let mut display = Display2in66::new();
+    display.set_inverted(true);
+    group.bench_function("write", |b| {
+        b.iter(|| {
+            let mut sum = 0_u32;
+            let mut write = |chunk: &[u8]| {
+                sum = chunk.iter().fold(sum, |sum, &byte| sum + u32::from(byte));
+                Ok::<_, Infallible>(())
+            };
+            let _ = display.write_black(&mut write);
+            let _ = display.write_red(&mut write);
+            sum
+        });
+    });
+
+    let (mut black, mut red) = (
+        vec![0; Display2in66::PLANE_SIZE],
+        vec![0; Display2in66::PLANE_SIZE],
+    );
+    group.bench_function("set_pixel", |b| {
+        b.iter(|| {
+            for y in 0..Display2in66::HEIGHT as usize {
+                for x in 0..Display2in66::WIDTH as usize {
+                    let color = COLORS[(x + y) % 3];
+                    packing::set_pixel(&mut black, &mut red, Display2in66::WIDTH, (x, y), color);
+                }
+            }
+        });
+    });
+}
+
+criterion_group!(benches, draw_iter, fill_solid, clear, planes);
+criterion_main!(benches);
@@ -0,0 +1,105 @@
+//! "Hello World" example for an ESP32-C3 board (e.g. ESP32-C3-DevKitM-1) with a 2.66 inch
+//! e-paper display using [esp-hal](https://github.com/esp-rs/esp-hal).
+//!
+//! Connections:
+//!
+//! | ESP32-C3 | EPD   |
+//! |----------|-------|
+//! | GPIO 6   | SCK   |
+//! | GPIO 7   | MOSI  |
+//! | GPIO 10  | CS    |
+//! | GPIO 3   | BUSY  |
+//! | GPIO 4   | DC    |
+//! | GPIO 5   | RESET |
+//!
+//! If you have another display size, simply replace `Display2in66` with your display.
+//! The example is a crate on its own. Install [espflash](https://github.com/esp-rs/espflash)
+//! and run in this folder: `cargo run --release`
+//!
+//! The blocking SPI driver of esp-hal splits writes of any length into FIFO sized transfers,
+//! so no chunk size is needed. If the SPI is used with DMA (`SpiDmaBus`), a single transfer
+//! cannot exceed the DMA buffer: pass its size as `spi_chunk_size` to `Epd::new`.
+//! On the ESP32-S3 replace the feature `esp32c3` of the dependencies with `esp32s3`, the target
+//! with `xtensa-esp32s3-none-elf` and use pins available on the board.
+
+#![no_std]
+#![no_main]
+
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_10X20, MonoTextStyle},
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+    text::Text,
+};
+use embedded_hal_bus::spi::ExclusiveDevice;
+use epd_spectra::{Display2in66, Epd, TriColor};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Input, InputConfig, Level, Output, OutputConfig},
+    main,
+    spi::{
+        master::{Config, Spi},
+        Mode,
+    },
+    time::Rate,
+};
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    let mut delay = Delay::new();
+
+    // Configure GPIO pins
+    let busy = Input::new(peripherals.GPIO3, InputConfig::default());
+    let dc = Output::new(peripherals.GPIO4, Level::High, OutputConfig::default());
+    let reset = Output::new(peripherals.GPIO5, Level::High, OutputConfig::default());
+
+    // Configure SPI
+    let cs = Output::new(peripherals.GPIO10, Level::High, OutputConfig::default());
+    let spi = Spi::new(
+        peripherals.SPI2,
+        Config::default()
+            .with_frequency(Rate::from_mhz(4))
+            .with_mode(Mode::_0),
+    )
+    .unwrap()
+    .with_sck(peripherals.GPIO6)
+    .with_mosi(peripherals.GPIO7);
+    let mut spi_device = ExclusiveDevice::new(spi, cs, Delay::new()).unwrap();
+
+    // create EPD driver
+    let epd = Epd::new(&mut spi_device, busy, dc, reset, &mut delay, 0);
+    let mut epd = epd.init(&mut spi_device, &mut delay).unwrap();
+
+    let mut display = Display2in66::default();
+    Text::new(
+        "Hello",
+        Point::new(10, 20),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Black),
+    )
+    .draw(&mut display)
+    .unwrap();
+
+    Text::new(
+        "ESP32",
+        Point::new(30, 60),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Red),
+    )
+    .draw(&mut display)
+    .unwrap();
+
+    Rectangle::new(Point::new(20, 100), Size::new(100, 60))
+        .into_styled(PrimitiveStyle::with_stroke(TriColor::Black, 3))
+        .draw(&mut display)
+        .unwrap();
+
+    epd.update(&display, &mut spi_device, &mut delay).unwrap();
+    let _inactive_epd = epd.power_off(&mut spi_device, &mut delay).unwrap();
+
+    loop {
+        delay.delay_millis(1000);
+    }
+}
@@ -0,0 +1,104 @@
+//! "Hello World" example for a Raspberry Pi (or any other Linux board) with a 2.66 inch
+//! e-paper display using [linux-embedded-hal](https://github.com/rust-embedded/linux-embedded-hal),
+//! i.e. the spidev and GPIO character device interfaces of the kernel. In contrast to the
+//! `raspberry` example no root privileges are needed if the user is in the `spi` and `gpio` groups.
+//!
+//! Connections:
+//!
+//! | Raspberry      | EPD   |
+//! |----------------|-------|
+//! | GPIO 11 (SCLK) | SCK   |
+//! | GPIO 10 (MOSI) | MOSI  |
+//! | GPIO 8  (CE0)  | CS    |
+//! | GPIO 24        | BUSY  |
+//! | GPIO 25        | DC    |
+//! | GPIO 17        | RESET |
+//!
+//! If you have another display size, simply replace `Display2in66` with your display.
+//! You have to enable SPI (e.g. with raspi-config):
+//! `cargo run --example linux --features="std"`
+
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_10X20, MonoTextStyle},
+    prelude::*,
+    primitives::{Circle, PrimitiveStyle},
+    text::Text,
+};
+use epd_spectra::{Display2in66, Epd, TriColor};
+use linux_embedded_hal::{
+    gpio_cdev::{Chip, LineRequestFlags},
+    spidev::{SpiModeFlags, SpidevOptions},
+    CdevPin, Delay, SpidevDevice,
+};
+use std::error::Error;
+
+/// Default buffer size of the spidev driver, longer transfers fail
+const SPIDEV_BUFFER_SIZE: usize = 4096;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // setup a display buffer
+    let mut display = Display2in66::default();
+
+    Text::new(
+        "Hello",
+        Point::new(10, 20),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Black),
+    )
+    .draw(&mut display)?;
+
+    Text::new(
+        "Linux",
+        Point::new(30, 60),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Red),
+    )
+    .draw(&mut display)?;
+
+    Circle::new(Point::new(30, 120), 80)
+        .into_styled(PrimitiveStyle::with_stroke(TriColor::Black, 4))
+        .draw(&mut display)?;
+
+    // setup all peripherals needed for EPD driver
+    let mut spi_device = SpidevDevice::open("/dev/spidev0.0")?;
+    spi_device.configure(
+        &SpidevOptions::new()
+            .max_speed_hz(4_000_000)
+            .mode(SpiModeFlags::SPI_MODE_0)
+            .build(),
+    )?;
+
+    let mut chip = Chip::new("/dev/gpiochip0")?;
+    let busy = pin(&mut chip, 24, LineRequestFlags::INPUT, "epd-busy")?;
+    let dc = pin(&mut chip, 25, LineRequestFlags::OUTPUT, "epd-dc")?;
+    let rst = pin(&mut chip, 17, LineRequestFlags::OUTPUT, "epd-rst")?;
+    let mut delay = Delay {};
+
+    // frames are larger than the spidev buffer, so they are written in chunks
+    let mut epd = Epd::new(
+        &mut spi_device,
+        busy,
+        dc,
+        rst,
+        &mut delay,
+        SPIDEV_BUFFER_SIZE,
+    );
+    // spidev limits the size of a whole transaction, not of each write
+    epd.set_writes_per_transaction(1);
+    let mut epd = epd.init(&mut spi_device, &mut delay)?;
+
+    // show the display
+    epd.update(&display, &mut spi_device, &mut delay)?;
+    let _inactive_epd = epd.power_off(&mut spi_device, &mut delay)?;
+
+    Ok(())
+}
+
+/// Requests the GPIO line `offset` of `chip`, outputs are initially high
+fn pin(
+    chip: &mut Chip,
+    offset: u32,
+    flags: LineRequestFlags,
+    name: &str,
+) -> Result<CdevPin, Box<dyn Error>> {
+    let handle = chip.get_line(offset)?.request(flags, 1, name)?;
+    Ok(CdevPin::new(handle)?)
+}
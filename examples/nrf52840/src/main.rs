@@ -0,0 +1,102 @@
+//! "Hello World" example for the nRF52840 (e.g. nRF52840-DK) with a 2.66 inch e-paper display
+//! using [nrf-hal](https://github.com/nrf-rs/nrf-hal).
+//!
+//! Connections:
+//!
+//! | nRF52840 | EPD   |
+//! |----------|-------|
+//! | P0.13    | SCK   |
+//! | P0.14    | MOSI  |
+//! | P0.15    | CS    |
+//! | P0.16    | BUSY  |
+//! | P0.17    | DC    |
+//! | P0.18    | RESET |
+//!
+//! If you have another display size, simply replace `Display2in66` with your display.
+//! The example is a crate on its own. Connect a debug probe and run in this folder:
+//! `cargo run --release`
+//!
+//! The SPIM peripheral sends data with `EasyDMA`, which can only read from RAM and is limited
+//! in the length of a transfer: 255 bytes on the nRF52832 and 65535 bytes on the nRF52840.
+//! Frames are therefore written in chunks of `SPI_CHUNK_SIZE` bytes. The display buffer is
+//! in RAM and the driver copies its register data to RAM before sending it.
+
+#![no_std]
+#![no_main]
+
+use cortex_m_rt::entry;
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_10X20, MonoTextStyle},
+    prelude::*,
+    primitives::{Circle, PrimitiveStyle},
+    text::Text,
+};
+use embedded_hal_bus::spi::ExclusiveDevice;
+use epd_spectra::{Display2in66, Epd, TriColor};
+use nrf52840_hal::{
+    gpio::{p0, Level},
+    pac,
+    spim::{self, Spim},
+    Delay,
+};
+use panic_halt as _;
+
+/// Maximum length of a single SPI transfer, it works with all chips of the nRF52 series
+const SPI_CHUNK_SIZE: usize = 255;
+
+#[allow(clippy::similar_names)]
+#[entry]
+fn main() -> ! {
+    let p = pac::Peripherals::take().unwrap();
+    let core = pac::CorePeripherals::take().unwrap();
+    let port0 = p0::Parts::new(p.P0);
+    let mut delay = Delay::new(core.SYST);
+
+    // Configure GPIO pins
+    let busy = port0.p0_16.into_floating_input();
+    let dc = port0.p0_17.into_push_pull_output(Level::High);
+    let reset = port0.p0_18.into_push_pull_output(Level::High);
+
+    // Configure SPI
+    let pins = spim::Pins {
+        sck: Some(port0.p0_13.into_push_pull_output(Level::Low).degrade()),
+        mosi: Some(port0.p0_14.into_push_pull_output(Level::Low).degrade()),
+        miso: None,
+    };
+    let spi = Spim::new(p.SPIM0, pins, spim::Frequency::M4, spim::MODE_0, 0);
+    let cs = port0.p0_15.into_push_pull_output(Level::High);
+    let mut spi_device = ExclusiveDevice::new_no_delay(spi, cs).unwrap();
+
+    // create EPD driver
+    let epd = Epd::new(&mut spi_device, busy, dc, reset, &mut delay, SPI_CHUNK_SIZE);
+    let mut epd = epd.init(&mut spi_device, &mut delay).unwrap();
+
+    let mut display = Display2in66::default();
+    Text::new(
+        "Hello",
+        Point::new(10, 20),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Black),
+    )
+    .draw(&mut display)
+    .unwrap();
+
+    Text::new(
+        "nRF52",
+        Point::new(30, 60),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Red),
+    )
+    .draw(&mut display)
+    .unwrap();
+
+    Circle::new(Point::new(30, 120), 80)
+        .into_styled(PrimitiveStyle::with_fill(TriColor::Red))
+        .draw(&mut display)
+        .unwrap();
+
+    epd.update(&display, &mut spi_device, &mut delay).unwrap();
+    let _inactive_epd = epd.power_off(&mut spi_device, &mut delay).unwrap();
+
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
@@ -0,0 +1,136 @@
+//! Example for the STM32 Nucleo F401RE microcontroller board with two e-paper displays of
+//! different sizes (2.66 and 2.13 inch) on a shared SPI bus, each with its own chip select.
+//! The SPI bus is shared with `RefCellDevice` of `embedded-hal-bus`. The displays are updated
+//! together with `EpdDisplays::flush_all`, so they refresh at the same time, and afterwards
+//! one of them on its own with `EpdDisplay::flush`.
+//!
+//! Connections:
+//!
+//! | Nucleo | EPD 2.66" | EPD 2.13" |
+//! |--------|-----------|-----------|
+//! | PB3    | SCK       | SCK       |
+//! | PB5    | MOSI      | MOSI      |
+//! | PA6    | CS        |           |
+//! | PA7    | BUSY      |           |
+//! | PB6    | DC        |           |
+//! | PA9    | RESET     |           |
+//! | PB10   |           | CS        |
+//! | PA10   |           | BUSY      |
+//! | PC7    |           | DC        |
+//! | PA8    |           | RESET     |
+//!
+//! To run this example clone this repository and run:
+//! `cargo run --example nucleo-f401re-multi --target thumbv7em-none-eabihf`
+
+#![no_main]
+#![no_std]
+#![cfg(target_os = "none")]
+
+use core::cell::RefCell;
+
+use cortex_m::peripheral::Peripherals;
+use cortex_m_rt::entry;
+use defmt_rtt as _;
+use panic_probe as _;
+
+use nucleo_f401re::{
+    hal::{
+        prelude::*,
+        spi::{self, Spi},
+    },
+    pac, Led,
+};
+
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_10X20, MonoTextStyle},
+    prelude::*,
+    text::Text,
+};
+use embedded_hal_bus::spi::RefCellDevice;
+use epd_spectra::{Epd, EpdDisplay, EpdDisplays, Panel, TriColor};
+
+#[allow(clippy::similar_names)]
+#[entry]
+fn main() -> ! {
+    let device = pac::Peripherals::take().unwrap();
+    let cp = Peripherals::take().unwrap();
+
+    let gpioa = device.GPIOA.split();
+    let gpiob = device.GPIOB.split();
+    let gpioc = device.GPIOC.split();
+
+    // (Re-)configure PA5 (LD2 - User Led) as output
+    let mut led = Led::new(gpioa.pa5);
+    led.set(false);
+
+    // Constrain clock registers
+    let rcc = device.RCC.constrain();
+    let clocks = rcc.cfgr.sysclk(84.MHz()).freeze();
+
+    let mut delay = cp.SYST.delay(&clocks);
+
+    // Configure the shared SPI bus
+    let sck = gpiob.pb3.into_alternate();
+    let miso = spi::NoMiso::new();
+    let mosi = gpiob.pb5.into_alternate();
+    let spi_bus = RefCell::new(Spi::new(
+        device.SPI1,
+        (sck, miso, mosi),
+        epd_spectra::SPI_MODE,
+        4.MHz(),
+        &clocks,
+    ));
+
+    // 2.66 inch display
+    let mut spi_device = RefCellDevice::new_no_delay(&spi_bus, gpioa.pa6.into_push_pull_output());
+    let epd = Epd::new(
+        &mut spi_device,
+        gpioa.pa7.into_floating_input(),
+        gpiob.pb6.into_push_pull_output(),
+        gpioa.pa9.into_push_pull_output(),
+        &mut delay,
+        0,
+    );
+    let panel_2in66: Panel<296, 152, 5624, _, _, _, _, _> = Panel::new(epd, spi_device);
+
+    // 2.13 inch display
+    let mut spi_device = RefCellDevice::new_no_delay(&spi_bus, gpiob.pb10.into_push_pull_output());
+    let epd = Epd::new(
+        &mut spi_device,
+        gpioa.pa10.into_floating_input(),
+        gpioc.pc7.into_push_pull_output(),
+        gpioa.pa8.into_push_pull_output(),
+        &mut delay,
+        0,
+    );
+    let panel_2in13: Panel<212, 104, 2756, _, _, _, _, _> = Panel::new(epd, spi_device);
+
+    let mut displays = (EpdDisplay::new(panel_2in66), EpdDisplay::new(panel_2in13));
+    let style = MonoTextStyle::new(&FONT_10X20, TriColor::Black);
+    Text::new("Display 1", Point::new(10, 20), style)
+        .draw(&mut displays.0)
+        .unwrap();
+    Text::new("Display 2", Point::new(10, 20), style)
+        .draw(&mut displays.1)
+        .unwrap();
+
+    // both displays refresh at the same time
+    displays.flush_all(&mut delay).unwrap();
+
+    // only the first display is updated
+    Text::new(
+        "Updated",
+        Point::new(10, 60),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Red),
+    )
+    .draw(&mut displays.0)
+    .unwrap();
+    displays.0.flush(&mut delay).unwrap();
+
+    displays.power_off_all(&mut delay).unwrap();
+
+    loop {
+        led.toggle();
+        delay.delay_ms(1000);
+    }
+}
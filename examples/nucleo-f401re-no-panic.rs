@@ -0,0 +1,147 @@
+//! Checks that the driver cannot panic: the panic handler calls a function which does not
+//! exist, so linking fails if any panic remains in the optimized binary. The pins, the SPI
+//! device and the delay only access memory, so the example runs on any board.
+//! CI builds it without the defmt linker script, which keeps the panic hook of defmt:
+//! `RUSTFLAGS="-C link-arg=-Tlink.x" cargo build --release --example nucleo-f401re-no-panic
+//! --target thumbv7em-none-eabihf`
+
+#![no_main]
+#![no_std]
+#![cfg(target_os = "none")]
+
+use core::{convert::Infallible, panic::PanicInfo, ptr};
+
+use cortex_m_rt::entry;
+use embedded_graphics::{
+    mono_font::{ascii::FONT_6X10, MonoTextStyle},
+    prelude::*,
+    primitives::{Circle, PrimitiveStyle},
+    text::Text,
+};
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{self, InputPin, OutputPin},
+    spi::{self, Operation, SpiDevice},
+};
+use epd_spectra::{Display2in66, Epd, EpdPanel, Panel, TriColor};
+// only linked for the interrupt vectors
+use nucleo_f401re as _;
+
+/// Register standing in for the peripherals, read and written volatile
+static mut REGISTER: u8 = 0;
+
+fn read() -> u8 {
+    // SAFETY: the example is single threaded
+    unsafe { ptr::read_volatile(ptr::addr_of!(REGISTER)) }
+}
+
+fn write(value: u8) {
+    // SAFETY: the example is single threaded
+    unsafe { ptr::write_volatile(ptr::addr_of_mut!(REGISTER), value) }
+}
+
+struct Pin;
+
+impl digital::ErrorType for Pin {
+    type Error = digital::ErrorKind;
+}
+
+impl InputPin for Pin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        match read() {
+            0xff => Err(digital::ErrorKind::Other),
+            value => Ok(value & 1 == 1),
+        }
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+impl OutputPin for Pin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        write(0);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        write(1);
+        Ok(())
+    }
+}
+
+struct Spi;
+
+impl spi::ErrorType for Spi {
+    type Error = spi::ErrorKind;
+}
+
+impl SpiDevice for Spi {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        for operation in operations {
+            match operation {
+                Operation::Write(data) => data.iter().copied().for_each(write),
+                Operation::Read(data) | Operation::TransferInPlace(data) => data.fill(read()),
+                Operation::Transfer(read_data, write_data) => {
+                    write_data.iter().copied().for_each(write);
+                    read_data.fill(read());
+                }
+                Operation::DelayNs(_) => {}
+            }
+        }
+        match read() {
+            0xfe => Err(spi::ErrorKind::Other),
+            _ => Ok(()),
+        }
+    }
+}
+
+struct Delay;
+
+impl DelayNs for Delay {
+    fn delay_ns(&mut self, ns: u32) {
+        cortex_m::asm::delay(ns / 16);
+    }
+}
+
+fn draw(display: &mut Display2in66) -> Result<(), Infallible> {
+    Circle::new(Point::new(20, 20), 100)
+        .into_styled(PrimitiveStyle::with_stroke(TriColor::Red, 3))
+        .draw(display)?;
+    let style = MonoTextStyle::new(&FONT_6X10, TriColor::Black);
+    Text::new("no panic", Point::new(30, 70), style).draw(display)?;
+    Ok(())
+}
+
+#[entry]
+fn main() -> ! {
+    let (mut spi, mut delay) = (Spi, Delay);
+    let mut display = Display2in66::default();
+    let _ = draw(&mut display);
+    loop {
+        let epd = Epd::new(&mut spi, Pin, Pin, Pin, &mut delay, 25);
+        let Ok(mut epd) = epd.init(&mut spi, &mut delay) else {
+            continue;
+        };
+        let _ = epd.update(&display, &mut spi, &mut delay);
+        let _ = epd.power_off(&mut spi, &mut delay);
+
+        // the panel changes the typestate of the driver behind a mutable reference
+        let epd = Epd::new(&mut spi, Pin, Pin, Pin, &mut delay, 25);
+        let mut panel: Panel<296, 152, 5624, _, _, _, _, _> = Panel::new(epd, Spi);
+        let _ = panel.update(&display, &mut delay);
+        let _ = panel.power_off(&mut delay);
+        let _ = panel.release(&mut delay);
+    }
+}
+
+#[panic_handler]
+fn panic(_: &PanicInfo) -> ! {
+    extern "Rust" {
+        /// Does not exist, so any reachable panic fails to link
+        fn the_driver_must_not_panic() -> !;
+    }
+    // SAFETY: never called, the example would not link otherwise
+    unsafe { the_driver_must_not_panic() }
+}
@@ -0,0 +1,176 @@
+//! [RTIC 2](https://rtic.rs) example for the STM32 Nucleo F401RE microcontroller board with
+//! a 2.66 inch e-paper display. The refresh is started with `start_update` and its end is
+//! detected with an interrupt on the rising edge of the busy pin (EXTI7). So the system stays
+//! responsive while the e-paper refreshes for several seconds: the interrupt spawns a task
+//! which powers off the e-paper, meanwhile other tasks can run and the core sleeps in `idle`.
+//!
+//! Connections are the same as in the `nucleo-f401re` example:
+//!
+//! | Nucleo | EPD   |
+//! |--------|-------|
+//! | PB3    | SCK   |
+//! | PB5    | MOSI  |
+//! | PA6    | CS    |
+//! | PA7    | BUSY  |
+//! | PB6    | DC    |
+//! | PA9    | RESET |
+//!
+//! To run this example clone this repository and run:
+//! `cargo run --example nucleo-f401re-rtic --target thumbv7em-none-eabihf`
+
+#![no_main]
+#![no_std]
+#![cfg(target_os = "none")]
+
+use defmt_rtt as _;
+use panic_probe as _;
+
+#[rtic::app(device = nucleo_f401re::pac, dispatchers = [USART1])]
+mod app {
+    use embedded_graphics::{
+        mono_font::{iso_8859_1::FONT_10X20, MonoTextStyle},
+        prelude::*,
+        text::Text,
+    };
+    use embedded_hal_bus::spi::{ExclusiveDevice, NoDelay};
+    use epd_spectra::{Active, Display2in66, Epd, TriColor};
+    use nucleo_f401re::{
+        hal::{
+            gpio::{Edge, ExtiPin, Input, Output, PA6, PA7, PA9, PB6},
+            pac::{EXTI, SPI1},
+            prelude::*,
+            spi::{self, Spi},
+            timer::SysDelay,
+        },
+        Led,
+    };
+
+    type SpiDevice = ExclusiveDevice<Spi<SPI1>, PA6<Output>, NoDelay>;
+    type Driver = Epd<Active, SpiDevice, PA7<Input>, PB6<Output>, PA9<Output>, SysDelay>;
+
+    #[shared]
+    struct Shared {
+        /// driver while the e-paper is active
+        epd: Option<Driver>,
+    }
+
+    #[local]
+    struct Local {
+        spi_device: SpiDevice,
+        delay: SysDelay,
+        exti: EXTI,
+        led: Led,
+    }
+
+    #[allow(clippy::similar_names)]
+    #[init]
+    fn init(cx: init::Context) -> (Shared, Local) {
+        let mut device = cx.device;
+
+        let gpioa = device.GPIOA.split();
+        let gpiob = device.GPIOB.split();
+        let mut syscfg = device.SYSCFG.constrain();
+
+        // (Re-)configure PA5 (LD2 - User Led) as output
+        let mut led = Led::new(gpioa.pa5);
+        led.set(false);
+
+        // Constrain clock registers
+        let rcc = device.RCC.constrain();
+        let clocks = rcc.cfgr.sysclk(84.MHz()).freeze();
+
+        let mut delay = cx.core.SYST.delay(&clocks);
+
+        // Configure GPIO pins
+        let busy = gpioa.pa7.into_floating_input();
+        let dc = gpiob.pb6.into_push_pull_output();
+        let reset = gpioa.pa9.into_push_pull_output();
+
+        // Configure SPI
+        let sck = gpiob.pb3.into_alternate();
+        let miso = spi::NoMiso::new();
+        let mosi = gpiob.pb5.into_alternate();
+        let cs = gpioa.pa6.into_push_pull_output();
+        let spi = Spi::new(
+            device.SPI1,
+            (sck, miso, mosi),
+            epd_spectra::SPI_MODE,
+            4.MHz(),
+            &clocks,
+        );
+        let mut spi_device = ExclusiveDevice::new_no_delay(spi, cs);
+
+        // create EPD driver
+        let epd = Epd::new(&mut spi_device, busy, dc, reset, &mut delay, 0);
+        let mut epd = epd.init(&mut spi_device, &mut delay).unwrap();
+
+        let mut display = Display2in66::default();
+        Text::new(
+            "Hello",
+            Point::new(10, 20),
+            MonoTextStyle::new(&FONT_10X20, TriColor::Black),
+        )
+        .draw(&mut display)
+        .unwrap();
+
+        Text::new(
+            "RTIC",
+            Point::new(30, 60),
+            MonoTextStyle::new(&FONT_10X20, TriColor::Red),
+        )
+        .draw(&mut display)
+        .unwrap();
+
+        epd.start_update(&display, &mut spi_device, &mut delay)
+            .unwrap();
+
+        // The busy pin is toggled while powering on, so the interrupt is enabled
+        // only after the refresh has been started.
+        let busy = epd.busy_pin_mut();
+        busy.make_interrupt_source(&mut syscfg);
+        busy.trigger_on_edge(&mut device.EXTI, Edge::Rising);
+        busy.clear_interrupt_pending_bit();
+        busy.enable_interrupt(&mut device.EXTI);
+        defmt::info!("refresh started");
+
+        (
+            Shared { epd: Some(epd) },
+            Local {
+                spi_device,
+                delay,
+                exti: device.EXTI,
+                led,
+            },
+        )
+    }
+
+    #[idle]
+    fn idle(_: idle::Context) -> ! {
+        loop {
+            // sleep until the next interrupt
+            cortex_m::asm::wfi();
+        }
+    }
+
+    /// Rising edge of the busy pin: the refresh is complete
+    #[task(binds = EXTI9_5, shared = [epd])]
+    fn busy(mut cx: busy::Context) {
+        cx.shared.epd.lock(|epd| {
+            if let Some(epd) = epd {
+                epd.busy_pin_mut().clear_interrupt_pending_bit();
+            }
+        });
+        refreshed::spawn().ok();
+    }
+
+    #[task(shared = [epd], local = [spi_device, delay, exti, led], priority = 1)]
+    async fn refreshed(mut cx: refreshed::Context) {
+        defmt::info!("refresh complete");
+        if let Some(mut epd) = cx.shared.epd.lock(Option::take) {
+            // the busy pin is toggled while powering off
+            epd.busy_pin_mut().disable_interrupt(cx.local.exti);
+            let _inactive_epd = epd.power_off(cx.local.spi_device, cx.local.delay).unwrap();
+        }
+        cx.local.led.set(true);
+    }
+}
@@ -0,0 +1,148 @@
+//! Low-power example for the STM32 Nucleo F401RE microcontroller board with a 2.66 inch
+//! e-paper display: the MCU is in STOP mode during the refresh of several seconds and is
+//! woken up by the rising edge of the busy pin. The refresh is started with `start_update`
+//! and `wait_while_busy` enters STOP mode as long as the e-paper is busy.
+//!
+//! The rising edge sets the pending bit of EXTI7, which wakes up the MCU from `WFE` as the
+//! interrupt is masked in the NVIC and `SEVONPEND` is set, so no interrupt handler is needed.
+//! After waking up from STOP mode the MCU runs with the HSI oscillator. So this example uses
+//! HSI (16 MHz) as system clock, otherwise the clocks have to be configured again after waking up.
+//!
+//! Connections are the same as in the `nucleo-f401re` example:
+//!
+//! | Nucleo | EPD   |
+//! |--------|-------|
+//! | PB3    | SCK   |
+//! | PB5    | MOSI  |
+//! | PA6    | CS    |
+//! | PA7    | BUSY  |
+//! | PB6    | DC    |
+//! | PA9    | RESET |
+//!
+//! To run this example clone this repository and run:
+//! `cargo run --example nucleo-f401re-stop --target thumbv7em-none-eabihf`
+//! Note that the debug connection may be lost while the MCU is in STOP mode.
+
+#![no_main]
+#![no_std]
+#![cfg(target_os = "none")]
+
+use cortex_m::peripheral::{Peripherals, NVIC};
+use cortex_m_rt::entry;
+use defmt_rtt as _;
+use panic_probe as _;
+
+use nucleo_f401re::{
+    hal::{
+        gpio::{Edge, ExtiPin},
+        pac::Interrupt,
+        prelude::*,
+        spi::{self, Spi},
+    },
+    pac, Led,
+};
+
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_10X20, MonoTextStyle},
+    prelude::*,
+    text::Text,
+};
+use epd_spectra::{Display2in66, Epd, TriColor};
+
+#[allow(clippy::similar_names)]
+#[entry]
+fn main() -> ! {
+    let mut device = pac::Peripherals::take().unwrap();
+    let mut cp = Peripherals::take().unwrap();
+
+    // enable the power interface to configure STOP mode
+    device.RCC.apb1enr.modify(|_, w| w.pwren().set_bit());
+
+    let gpioa = device.GPIOA.split();
+    let gpiob = device.GPIOB.split();
+    let mut syscfg = device.SYSCFG.constrain();
+
+    // (Re-)configure PA5 (LD2 - User Led) as output
+    let mut led = Led::new(gpioa.pa5);
+    led.set(false);
+
+    // Constrain clock registers, HSI is used as system clock
+    let rcc = device.RCC.constrain();
+    let clocks = rcc.cfgr.freeze();
+
+    let mut delay = cp.SYST.delay(&clocks);
+
+    // Configure GPIO pins
+    let mut busy = gpioa.pa7.into_floating_input();
+    let dc = gpiob.pb6.into_push_pull_output();
+    let reset = gpioa.pa9.into_push_pull_output();
+
+    // The rising edge of the busy pin sets the pending bit of EXTI7 which wakes up from WFE.
+    // The interrupt stays disabled in the NVIC.
+    busy.make_interrupt_source(&mut syscfg);
+    busy.trigger_on_edge(&mut device.EXTI, Edge::Rising);
+    busy.enable_interrupt(&mut device.EXTI);
+    cp.SCB.set_sevonpend();
+
+    // Configure SPI
+    let sck = gpiob.pb3.into_alternate();
+    let miso = spi::NoMiso::new();
+    let mosi = gpiob.pb5.into_alternate();
+    let cs = gpioa.pa6.into_push_pull_output();
+    let spi = Spi::new(
+        device.SPI1,
+        (sck, miso, mosi),
+        epd_spectra::SPI_MODE,
+        4.MHz(),
+        &clocks,
+    );
+    let mut spi_device = embedded_hal_bus::spi::ExclusiveDevice::new_no_delay(spi, cs);
+
+    // create EPD driver
+    let epd = Epd::new(&mut spi_device, busy, dc, reset, &mut delay, 0);
+    let mut epd = epd.init(&mut spi_device, &mut delay).unwrap();
+
+    let mut display = Display2in66::default();
+    Text::new(
+        "Hello",
+        Point::new(10, 20),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Black),
+    )
+    .draw(&mut display)
+    .unwrap();
+
+    Text::new(
+        "STOP",
+        Point::new(30, 60),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Red),
+    )
+    .draw(&mut display)
+    .unwrap();
+
+    epd.start_update(&display, &mut spi_device, &mut delay)
+        .unwrap();
+
+    // STOP mode with the low-power regulator
+    device
+        .PWR
+        .cr
+        .modify(|_, w| w.pdds().clear_bit().lpds().set_bit());
+    cp.SCB.set_sleepdeep();
+    epd.wait_while_busy(|busy| {
+        // an edge during powering on may be pending already
+        busy.clear_interrupt_pending_bit();
+        NVIC::unpend(Interrupt::EXTI9_5);
+        if busy.is_low() {
+            cortex_m::asm::wfe();
+        }
+    })
+    .unwrap();
+    cp.SCB.clear_sleepdeep();
+
+    let _inactive_epd = epd.power_off(&mut spi_device, &mut delay).unwrap();
+
+    loop {
+        led.toggle();
+        delay.delay_ms(1000);
+    }
+}
@@ -0,0 +1,18 @@
+/* This file is used for linking the RP2040 example (Raspberry Pi Pico with 2 MiB flash) */
+
+MEMORY
+{
+  BOOT2 : ORIGIN = 0x10000000, LENGTH = 0x100
+  FLASH : ORIGIN = 0x10000100, LENGTH = 2048K - 0x100
+  RAM : ORIGIN = 0x20000000, LENGTH = 256K
+}
+
+EXTERN(BOOT2_FIRMWARE)
+
+SECTIONS {
+  /* The second stage bootloader has to be placed at the start of the flash */
+  .boot2 ORIGIN(BOOT2) :
+  {
+    KEEP(*(.boot2));
+  } > BOOT2
+} INSERT BEFORE .text;
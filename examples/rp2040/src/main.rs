@@ -0,0 +1,129 @@
+//! "Hello World" example for the Raspberry Pi Pico (RP2040) with a 2.66 inch e-paper display
+//! using [rp2040-hal](https://github.com/rp-rs/rp-hal).
+//!
+//! Connections:
+//!
+//! | Pico    | EPD   |
+//! |---------|-------|
+//! | GPIO 18 | SCK   |
+//! | GPIO 19 | MOSI  |
+//! | GPIO 17 | CS    |
+//! | GPIO 20 | BUSY  |
+//! | GPIO 21 | DC    |
+//! | GPIO 22 | RESET |
+//!
+//! If you have another display size, simply replace `Display2in66` with your display.
+//! The example is a crate on its own. Install [elf2uf2-rs](https://github.com/JoNil/elf2uf2-rs),
+//! hold BOOTSEL while connecting the Pico and run in this folder: `cargo run --release`
+//!
+//! The driver writes the planes of the display buffer as contiguous slices with
+//! `SpiDevice::write` (with `spi_chunk_size` 0 a plane in one write). So a `SpiDevice`
+//! implementation which transfers the slices with DMA can be used instead of the blocking
+//! one of this example.
+
+#![no_std]
+#![no_main]
+
+use embedded_graphics::{
+    mono_font::{iso_8859_1::FONT_10X20, MonoTextStyle},
+    prelude::*,
+    primitives::{PrimitiveStyle, Triangle},
+    text::Text,
+};
+use embedded_hal_bus::spi::ExclusiveDevice;
+use epd_spectra::{Display2in66, Epd, TriColor, SPI_MODE};
+use panic_halt as _;
+use rp2040_hal::{
+    self as hal,
+    clocks::init_clocks_and_plls,
+    fugit::RateExtU32,
+    gpio::{FunctionSpi, Pins},
+    pac, Clock, Sio, Spi, Timer, Watchdog,
+};
+
+/// Second stage bootloader for the flash of the Pico
+#[link_section = ".boot2"]
+#[used]
+pub static BOOT2_FIRMWARE: [u8; 256] = rp2040_boot2::BOOT_LOADER_W25Q080;
+
+/// Frequency of the crystal of the Pico
+const XTAL_FREQ_HZ: u32 = 12_000_000;
+
+#[allow(clippy::similar_names)]
+#[hal::entry]
+fn main() -> ! {
+    let mut pac = pac::Peripherals::take().unwrap();
+    let mut watchdog = Watchdog::new(pac.WATCHDOG);
+    let clocks = init_clocks_and_plls(
+        XTAL_FREQ_HZ,
+        pac.XOSC,
+        pac.CLOCKS,
+        pac.PLL_SYS,
+        pac.PLL_USB,
+        &mut pac.RESETS,
+        &mut watchdog,
+    )
+    .unwrap();
+    let sio = Sio::new(pac.SIO);
+    let pins = Pins::new(
+        pac.IO_BANK0,
+        pac.PADS_BANK0,
+        sio.gpio_bank0,
+        &mut pac.RESETS,
+    );
+    let mut timer = Timer::new(pac.TIMER, &mut pac.RESETS, &clocks);
+
+    // Configure GPIO pins
+    let busy = pins.gpio20.into_floating_input();
+    let dc = pins.gpio21.into_push_pull_output();
+    let reset = pins.gpio22.into_push_pull_output();
+
+    // Configure SPI
+    let sck = pins.gpio18.into_function::<FunctionSpi>();
+    let mosi = pins.gpio19.into_function::<FunctionSpi>();
+    let cs = pins.gpio17.into_push_pull_output();
+    let spi = Spi::<_, _, _, 8>::new(pac.SPI0, (mosi, sck)).init(
+        &mut pac.RESETS,
+        clocks.peripheral_clock.freq(),
+        4.MHz(),
+        SPI_MODE,
+    );
+    let mut spi_device = ExclusiveDevice::new(spi, cs, timer).unwrap();
+
+    // create EPD driver
+    let epd = Epd::new(&mut spi_device, busy, dc, reset, &mut timer, 0);
+    let mut epd = epd.init(&mut spi_device, &mut timer).unwrap();
+
+    let mut display = Display2in66::default();
+    Text::new(
+        "Hello",
+        Point::new(10, 20),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Black),
+    )
+    .draw(&mut display)
+    .unwrap();
+
+    Text::new(
+        "Pico",
+        Point::new(30, 60),
+        MonoTextStyle::new(&FONT_10X20, TriColor::Red),
+    )
+    .draw(&mut display)
+    .unwrap();
+
+    Triangle::new(
+        Point::new(20, 180),
+        Point::new(70, 100),
+        Point::new(120, 180),
+    )
+    .into_styled(PrimitiveStyle::with_fill(TriColor::Red))
+    .draw(&mut display)
+    .unwrap();
+
+    epd.update(&display, &mut spi_device, &mut timer).unwrap();
+    let _inactive_epd = epd.power_off(&mut spi_device, &mut timer).unwrap();
+
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
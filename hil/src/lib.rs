@@ -0,0 +1,75 @@
+//! Setup of the STM32 Nucleo F401RE with a 2.66 inch e-paper display for the
+//! hardware-in-the-loop tests in `tests/`. The connections are the same as in the
+//! `nucleo-f401re` example:
+//!
+//! | Nucleo | EPD   |
+//! |--------|-------|
+//! | PB3    | SCK   |
+//! | PB5    | MOSI  |
+//! | PA6    | CS    |
+//! | PA7    | BUSY  |
+//! | PB6    | DC    |
+//! | PA9    | RESET |
+//!
+//! Run the tests with a connected board: `cargo test` in this directory.
+
+#![no_std]
+
+use cortex_m::peripheral::Peripherals;
+use embedded_hal_bus::spi::{ExclusiveDevice, NoDelay};
+use epd_spectra::{Epd, Inactive};
+use nucleo_f401re::hal::{
+    gpio::{Input, Output, PA6, PA7, PA9, PB6},
+    pac,
+    prelude::*,
+    spi::{self, Spi},
+    timer::SysDelay,
+};
+
+pub type SpiDevice = ExclusiveDevice<Spi<pac::SPI1>, PA6<Output>, NoDelay>;
+pub type Driver<STATE> = Epd<STATE, SpiDevice, PA7<Input>, PB6<Output>, PA9<Output>, SysDelay>;
+
+/// Driver of the e-paper with its SPI device and delay
+pub struct Board {
+    pub epd: Driver<Inactive>,
+    pub spi: SpiDevice,
+    pub delay: SysDelay,
+}
+
+/// Configures the clocks, the pins and SPI like the `nucleo-f401re` example
+///
+/// # Panics
+///
+/// Panics if the peripherals were taken before.
+#[allow(clippy::similar_names)]
+#[must_use]
+pub fn setup() -> Board {
+    let device = pac::Peripherals::take().unwrap();
+    let cp = Peripherals::take().unwrap();
+
+    let gpioa = device.GPIOA.split();
+    let gpiob = device.GPIOB.split();
+    let rcc = device.RCC.constrain();
+    let clocks = rcc.cfgr.sysclk(84.MHz()).freeze();
+    let mut delay = cp.SYST.delay(&clocks);
+
+    let busy = gpioa.pa7.into_floating_input();
+    let dc = gpiob.pb6.into_push_pull_output();
+    let reset = gpioa.pa9.into_push_pull_output();
+
+    let sck = gpiob.pb3.into_alternate();
+    let miso = spi::NoMiso::new();
+    let mosi = gpiob.pb5.into_alternate();
+    let cs = gpioa.pa6.into_push_pull_output();
+    let spi = Spi::new(
+        device.SPI1,
+        (sck, miso, mosi),
+        epd_spectra::SPI_MODE,
+        4.MHz(),
+        &clocks,
+    );
+    let mut spi = ExclusiveDevice::new_no_delay(spi, cs);
+
+    let epd = Epd::new(&mut spi, busy, dc, reset, &mut delay, 0);
+    Board { epd, spi, delay }
+}
@@ -0,0 +1,136 @@
+//! Init, update and power off on the reference setup, see the crate documentation for
+//! the connections. The e-paper shows the frames while the tests run.
+
+#![no_std]
+#![no_main]
+
+use defmt_rtt as _;
+use panic_probe as _;
+
+use embedded_graphics::{
+    mono_font::{ascii::FONT_10X20, MonoTextStyle},
+    prelude::*,
+    text::Text,
+};
+use epd_spectra::{Active, Display2in66, Error, Inactive, TriColor, OPERATING_TEMPERATURE};
+use epd_spectra_hil::{setup, Driver, SpiDevice};
+use nucleo_f401re::hal::timer::SysDelay;
+
+/// Upper bound of the busy time of the init, e.g. of the soft reset
+const MAX_INIT_MS: u64 = 100;
+/// Bounds of the busy time of a refresh of the 2.66 inch panel
+const REFRESH_MS: core::ops::RangeInclusive<u64> = 5_000..=30_000;
+
+struct State {
+    epd: Option<Driver<Inactive>>,
+    spi: SpiDevice,
+    delay: SysDelay,
+}
+
+impl State {
+    /// Initializes the e-paper, calls `f` and powers it off again
+    fn active(&mut self, f: impl FnOnce(&mut Driver<Active>, &mut SpiDevice, &mut SysDelay)) {
+        let epd = self.epd.take().unwrap();
+        let busy_ms = epd.busy_time_ms();
+        let mut epd = epd.init(&mut self.spi, &mut self.delay).unwrap();
+        let init_ms = epd.busy_time_ms() - busy_ms;
+        defmt::info!("init: busy for {} ms", init_ms);
+        defmt::assert!(init_ms <= MAX_INIT_MS);
+
+        f(&mut epd, &mut self.spi, &mut self.delay);
+        let epd = epd.power_off(&mut self.spi, &mut self.delay).unwrap();
+        self.epd = Some(epd);
+    }
+}
+
+fn frame(text: &str, color: TriColor) -> Display2in66 {
+    let mut display = Display2in66::new();
+    Text::new(
+        text,
+        Point::new(10, 30),
+        MonoTextStyle::new(&FONT_10X20, color),
+    )
+    .draw(&mut display)
+    .unwrap();
+    display
+}
+
+#[defmt_test::tests]
+mod tests {
+    use super::*;
+
+    #[init]
+    fn init() -> State {
+        let board = setup();
+        State {
+            epd: Some(board.epd),
+            spi: board.spi,
+            delay: board.delay,
+        }
+    }
+
+    #[test]
+    fn update_waits_for_the_refresh(state: &mut State) {
+        state.active(|epd, spi, delay| {
+            let (busy_ms, refreshes) = (epd.busy_time_ms(), epd.refresh_count());
+            epd.update(&frame("update", TriColor::Black), spi, delay)
+                .unwrap();
+            let refresh_ms = epd.busy_time_ms() - busy_ms;
+            defmt::info!("update: busy for {} ms", refresh_ms);
+            defmt::assert!(REFRESH_MS.contains(&refresh_ms));
+            defmt::assert_eq!(epd.refresh_count(), refreshes + 1);
+        });
+    }
+
+    #[test]
+    fn commands_are_rejected_during_a_started_refresh(state: &mut State) {
+        state.active(|epd, spi, delay| {
+            let display = frame("start", TriColor::Red);
+            epd.start_update(&display, spi, delay).unwrap();
+            defmt::assert!(epd.is_busy().unwrap());
+            let result = epd.update(&display, spi, delay);
+            defmt::assert!(matches!(result, Err(Error::Busy)));
+
+            let mut polls = 0_u32;
+            epd.wait_while_busy(|_| {
+                polls += 1;
+                cortex_m::asm::delay(84_000);
+            })
+            .unwrap();
+            defmt::info!("start_update: busy for about {} ms", polls);
+            defmt::assert!(!epd.is_busy().unwrap());
+        });
+    }
+
+    #[test]
+    fn planes_of_the_wrong_size_are_rejected(state: &mut State) {
+        state.active(|epd, spi, delay| {
+            let refreshes = epd.refresh_count();
+            let plane = [0; 5623];
+            let result = epd.update_raw(&plane, &plane, Size::new(152, 296), spi, delay);
+            defmt::assert!(matches!(result, Err(Error::FrameSize)));
+            defmt::assert_eq!(epd.refresh_count(), refreshes);
+        });
+    }
+
+    #[test]
+    fn update_if_changed_skips_the_same_frame(state: &mut State) {
+        state.active(|epd, spi, delay| {
+            let display = frame("same", TriColor::Black);
+            defmt::assert!(epd.update_if_changed(&display, spi, delay).unwrap());
+            let busy_ms = epd.busy_time_ms();
+            defmt::assert!(!epd.update_if_changed(&display, spi, delay).unwrap());
+            defmt::assert_eq!(epd.busy_time_ms(), busy_ms);
+        });
+    }
+
+    #[test]
+    fn temperature_outside_of_the_range_is_rejected(state: &mut State) {
+        state.active(|epd, spi, delay| {
+            epd.set_temperature(*OPERATING_TEMPERATURE.end() + 1);
+            let result = epd.update(&frame("hot", TriColor::Red), spi, delay);
+            defmt::assert!(matches!(result, Err(Error::Temperature(41))));
+            epd.set_temperature(25);
+        });
+    }
+}
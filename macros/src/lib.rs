@@ -0,0 +1,188 @@
+//! Procedural macros for the [epd-spectra](https://crates.io/crates/epd-spectra) driver.
+//!
+//! `include_epd_image!` converts PNG and BMP images at compile time into the raw formats
+//! of the driver, so no generated byte arrays have to be kept in the sources, like Ferris
+//! of the examples:
+//!
+//! ```ignore
+//! let ferris: ImageRaw<TriColor> = include_epd_image!("examples/ferris.bmp");
+//! let photo: PlanarImage = include_epd_image!("photo.png", dither = floyd_steinberg, format = planar);
+//! ```
+//!
+//! `include_vendor_image!` includes images exported as C arrays by the vendor tools:
+//!
+//! ```ignore
+//! let logo: PlanarImage = include_vendor_image!("image_266_296x152.c", width = 152);
+//! ```
+
+use std::path::PathBuf;
+
+use embedded_graphics::geometry::OriginDimensions;
+use epd_spectra::{
+    c_array::vendor_planes,
+    convert::{self, ConvertedImage, RawFormat},
+    DitherMethod, DitherPalette,
+};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Ident, LitInt, LitStr, Token,
+};
+
+/// Arguments of `include_epd_image!`
+struct Args {
+    path: LitStr,
+    dithering: Option<DitherMethod>,
+    palette: DitherPalette,
+    format: RawFormat,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Args {
+            path: input.parse()?,
+            dithering: None,
+            palette: DitherPalette::TriColor,
+            format: RawFormat::Interleaved,
+        };
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value: Ident = input.parse()?;
+            let value_str = value.to_string();
+            let invalid = |error: convert::ParseOptionError| {
+                syn::Error::new(value.span(), format!("{error} for `{key}`"))
+            };
+            match key.to_string().as_str() {
+                "dither" => {
+                    args.dithering = convert::parse_dithering(&value_str).map_err(invalid)?;
+                }
+                "palette" => args.palette = value_str.parse().map_err(invalid)?,
+                "format" => args.format = value_str.parse().map_err(invalid)?,
+                _ => return Err(syn::Error::new(key.span(), "unknown argument")),
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// Includes a PNG or BMP image converted into the raw format of the driver.
+///
+/// The path is relative to the directory of the `Cargo.toml` of the calling crate.
+/// Optional arguments:
+/// - `dither`: `none` (default, each pixel is mapped like `TriColor::from(Rgb888)`),
+///   `floyd_steinberg`, `bayer2`, `bayer4`, `bayer8` or `blue_noise`
+/// - `palette`: colors used for dithering, `tricolor` (default) or `monochrome`
+/// - `format`: `interleaved` (default) expands to an `ImageRaw<TriColor>`,
+///   `planar` to a [`PlanarImage`](epd_spectra::PlanarImage)
+///
+/// The expression can be used in constants. `embedded-graphics` has to be a dependency
+/// of the calling crate for the `interleaved` format.
+#[proc_macro]
+pub fn include_epd_image(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as Args);
+    let mut path = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+    path.push(args.path.value());
+
+    let image = match ConvertedImage::open(&path, args.dithering, args.palette) {
+        Ok(image) => image,
+        Err(error) => {
+            let message = format!("cannot load image {}: {error}", path.display());
+            return syn::Error::new(args.path.span(), message)
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    // the file is included to rebuild the crate when the image changes
+    let path = path.to_string_lossy();
+    let width = image.size().width;
+    let image = match args.format {
+        RawFormat::Interleaved => {
+            let data = image.to_interleaved();
+            quote! {
+                ::embedded_graphics::image::ImageRaw::<::epd_spectra::TriColor>::new(&[#(#data),*], #width)
+            }
+        }
+        RawFormat::Planar => {
+            let (black, red) = image.to_planes();
+            quote! {
+                ::epd_spectra::PlanarImage::new(&[#(#black),*], &[#(#red),*], #width)
+            }
+        }
+    };
+    quote! {
+        {
+            const _: &[u8] = include_bytes!(#path);
+            #image
+        }
+    }
+    .into()
+}
+
+/// Arguments of `include_vendor_image!`
+struct VendorArgs {
+    path: LitStr,
+    width: LitInt,
+}
+
+impl Parse for VendorArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let key: Ident = input.parse()?;
+        if key != "width" {
+            return Err(syn::Error::new(key.span(), "expected `width`"));
+        }
+        input.parse::<Token![=]>()?;
+        let width = input.parse()?;
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(VendorArgs { path, width })
+    }
+}
+
+/// Includes an image which was exported as C source by the tools of Pervasive Displays,
+/// see [`epd_spectra::c_array`]. The source has to contain one array with the black plane
+/// followed by the red plane or one array per plane, with the black plane first.
+///
+/// The path is relative to the directory of the `Cargo.toml` of the calling crate and `width`
+/// is the width of the image in pixels, usually `SIZE_H` of the display type.
+/// The macro expands to a [`PlanarImage`](epd_spectra::PlanarImage) and can be used in
+/// constants.
+#[proc_macro]
+pub fn include_vendor_image(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as VendorArgs);
+    let mut path = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+    path.push(args.path.value());
+
+    let planes = std::fs::read_to_string(&path)
+        .map_err(|error| error.to_string())
+        .and_then(|source| vendor_planes(&source).map_err(|error| error.to_string()));
+    let (black, red) = match planes {
+        Ok(planes) => planes,
+        Err(error) => {
+            let message = format!("cannot load image {}: {error}", path.display());
+            return syn::Error::new(args.path.span(), message)
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    // the file is included to rebuild the crate when the image changes
+    let path = path.to_string_lossy();
+    let width = args.width;
+    quote! {
+        {
+            const _: &[u8] = include_bytes!(#path);
+            ::epd_spectra::PlanarImage::new(&[#(#black),*], &[#(#red),*], #width)
+        }
+    }
+    .into()
+}
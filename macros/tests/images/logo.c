@@ -0,0 +1,12 @@
+// 10 x 2 pixels, black pixel in the top left and red pixel in the bottom right corner
+#include <stdint.h>
+
+const uint8_t logo_BW[4] = {
+    0x80, 0x00,
+    0x00, 0x00,
+};
+
+const uint8_t logo_R[4] = {
+    0x00, 0x00,
+    0x00, 0x40,
+};
@@ -0,0 +1,74 @@
+use embedded_graphics::{
+    image::{GetPixel, Image, ImageRaw},
+    prelude::*,
+    primitives::Rectangle,
+};
+use epd_spectra::{Display2in66, PlanarImage, TriColor};
+use epd_spectra_macros::{include_epd_image, include_vendor_image};
+
+const FERRIS: ImageRaw<TriColor> = include_epd_image!("../examples/ferris.bmp");
+const LOGO: PlanarImage = include_vendor_image!("tests/images/logo.c", width = 10);
+
+#[test]
+fn images_are_converted_like_the_former_script() {
+    assert_eq!(FERRIS.size(), Size::new(150, 100));
+    let mut display = Display2in66::default();
+    Image::new(&FERRIS, Point::zero())
+        .draw(&mut display)
+        .unwrap();
+
+    // pixel counts of the array which was generated with convert_bmp.py
+    let colors: Vec<TriColor> = Rectangle::new(Point::zero(), FERRIS.size())
+        .points()
+        .map(|p| display.pixel(p).unwrap())
+        .collect();
+    let count = |color| colors.iter().filter(|&&c| c == color).count();
+    assert_eq!(count(TriColor::White), 9333);
+    assert_eq!(count(TriColor::Black), 224);
+    assert_eq!(count(TriColor::Red), 5443);
+}
+
+#[test]
+fn planar_images_have_the_pixels_of_interleaved_ones() {
+    let planar: PlanarImage = include_epd_image!("../examples/ferris.bmp", format = planar);
+    assert_eq!(planar.size(), FERRIS.size());
+    let mut display = Display2in66::default();
+    Image::new(&FERRIS, Point::zero())
+        .draw(&mut display)
+        .unwrap();
+    for p in planar.bounding_box().points() {
+        assert_eq!(planar.pixel(p), display.pixel(p), "{p:?}");
+    }
+}
+
+#[test]
+fn dithering_keeps_the_size() {
+    let dithered: ImageRaw<TriColor> = include_epd_image!(
+        "../examples/ferris.bmp",
+        dither = bayer4,
+        palette = monochrome,
+    );
+    assert_eq!(dithered.size(), FERRIS.size());
+    let mut display = Display2in66::default();
+    Image::new(&dithered, Point::zero())
+        .draw(&mut display)
+        .unwrap();
+    let red = Rectangle::new(Point::zero(), FERRIS.size())
+        .points()
+        .filter(|&p| display.pixel(p) == Some(TriColor::Red))
+        .count();
+    assert_eq!(red, 0);
+}
+
+#[test]
+fn vendor_images_are_included_as_planes() {
+    assert_eq!(LOGO.size(), Size::new(10, 2));
+    assert_eq!(LOGO.pixel(Point::new(0, 0)), Some(TriColor::Black));
+    assert_eq!(LOGO.pixel(Point::new(9, 1)), Some(TriColor::Red));
+    let others = LOGO
+        .bounding_box()
+        .points()
+        .filter(|&p| LOGO.pixel(p) == Some(TriColor::White))
+        .count();
+    assert_eq!(others, 18);
+}
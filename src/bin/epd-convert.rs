@@ -0,0 +1,119 @@
+//! Converts PNG and BMP images into the raw formats of the driver (feature `cli`)
+//!
+//! `cargo install epd-spectra --features cli`, then e.g. `epd-convert --dither bayer4 ferris.png`
+
+use std::{fs, path::Path, process::ExitCode};
+
+use embedded_graphics::geometry::OriginDimensions;
+use epd_spectra::{
+    convert::{self, ConvertedImage, RawFormat},
+    DitherMethod, DitherPalette,
+};
+
+const USAGE: &str = "Usage: epd-convert [OPTIONS] <IMAGE>
+
+Options:
+  --dither <METHOD>   none (default), floyd_steinberg, bayer2, bayer4, bayer8 or blue_noise
+  --palette <COLORS>  tricolor (default) or monochrome
+  --format <FORMAT>   interleaved (default, for ImageRaw<TriColor>) or planar (black and red plane)
+  --binary <FILE>     write the raw data to FILE instead of printing Rust code;
+                      with planar format the black plane is followed by the red plane
+  -h, --help          print this help";
+
+struct Args {
+    image: String,
+    dithering: Option<DitherMethod>,
+    palette: DitherPalette,
+    format: RawFormat,
+    binary: Option<String>,
+}
+
+/// Parses the command line, returns `None` if the help is requested
+fn parse_args() -> Result<Option<Args>, String> {
+    let mut image = None;
+    let mut dithering = None;
+    let mut palette = DitherPalette::TriColor;
+    let mut format = RawFormat::Interleaved;
+    let mut binary = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {arg}"));
+        match arg.as_str() {
+            "--dither" => dithering = option(convert::parse_dithering(&value()?))?,
+            "--palette" => palette = option(value()?.parse())?,
+            "--format" => format = option(value()?.parse())?,
+            "--binary" => binary = Some(value()?),
+            "-h" | "--help" => return Ok(None),
+            _ if image.is_none() && !arg.starts_with('-') => image = Some(arg),
+            _ => return Err(format!("unexpected argument: {arg}\n\n{USAGE}")),
+        }
+    }
+
+    Ok(Some(Args {
+        image: image.ok_or(USAGE)?,
+        dithering,
+        palette,
+        format,
+        binary,
+    }))
+}
+
+/// Converts the error of an invalid option into its message
+fn option<T>(parsed: Result<T, convert::ParseOptionError>) -> Result<T, String> {
+    parsed.map_err(|error| error.to_string())
+}
+
+/// Returns the Rust code of a constant with the given bytes
+fn rust_array(name: &str, data: &[u8]) -> String {
+    let rows: Vec<String> = data
+        .chunks(24)
+        .map(|row| {
+            let bytes: Vec<String> = row.iter().map(u8::to_string).collect();
+            format!("    {},\n", bytes.join(", "))
+        })
+        .collect();
+    format!("const {name}: &[u8] = &[\n{}];\n", rows.concat())
+}
+
+fn run() -> Result<(), String> {
+    let Some(args) = parse_args()? else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+    let image = ConvertedImage::open(&args.image, args.dithering, args.palette)
+        .map_err(|error| format!("cannot load image {}: {error}", args.image))?;
+
+    let planes = match args.format {
+        RawFormat::Interleaved => vec![("IMG", image.to_interleaved())],
+        RawFormat::Planar => {
+            let (black, red) = image.to_planes();
+            vec![("BLACK", black), ("RED", red)]
+        }
+    };
+
+    if let Some(path) = args.binary {
+        let data: Vec<u8> = planes.into_iter().flat_map(|(_, data)| data).collect();
+        return fs::write(&path, data).map_err(|error| format!("cannot write {path}: {error}"));
+    }
+
+    let name = Path::new(&args.image)
+        .file_stem()
+        .map(|stem| convert::constant_name(&stem.to_string_lossy()))
+        .unwrap_or_default();
+    println!("const {name}_WIDTH: u32 = {};", image.size().width);
+    for (suffix, data) in planes {
+        print!("{}", rust_array(&format!("{name}_{suffix}"), &data));
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
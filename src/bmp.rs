@@ -0,0 +1,189 @@
+//! Streaming of BMP files, e.g. from an SD card with `embedded-sdmmc`, to the e-paper
+//! without a display buffer (see `Epd::update_from_bmp`)
+
+use embedded_graphics::geometry::{Point, Size};
+use embedded_sdmmc::{BlockDevice, File, TimeSource};
+
+use crate::{
+    dither::{add_offset, ErrorDiffusion},
+    graphics::CHUNK_SIZE,
+    packing, DitherMethod, DitherPalette, TriColor,
+};
+
+/// Errors reading a BMP file
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BmpError {
+    /// The file could not be read
+    Read,
+    /// The file is no uncompressed BMP with 24 or 32 bits per pixel
+    Format,
+}
+
+/// Source of a BMP file which can be read at arbitrary offsets
+pub trait BmpSource {
+    /// Fills `buffer` with the bytes of the file starting at `offset`
+    ///
+    /// # Errors
+    ///
+    /// Returns `BmpError::Read` if the file cannot be read and `BmpError::Format`
+    /// if it ends before `buffer` is filled.
+    fn read_at(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), BmpError>;
+}
+
+impl<D, T, const MAX_DIRS: usize, const MAX_FILES: usize, const MAX_VOLUMES: usize> BmpSource
+    for File<'_, D, T, MAX_DIRS, MAX_FILES, MAX_VOLUMES>
+where
+    D: BlockDevice,
+    T: TimeSource,
+{
+    fn read_at(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), BmpError> {
+        self.seek_from_start(offset).map_err(|_| BmpError::Read)?;
+        let mut len = 0;
+        while let Some(rest) = buffer.get_mut(len..).filter(|rest| !rest.is_empty()) {
+            match self.read(rest) {
+                Ok(0) => return Err(BmpError::Format),
+                Ok(n) => len += n,
+                Err(_) => return Err(BmpError::Read),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Number of pixels read from the file at once
+const PIXELS_PER_READ: u32 = 64;
+
+/// Header of an uncompressed BMP with 24 or 32 bits per pixel
+pub(crate) struct BmpHeader {
+    /// offset of the pixel data in the file
+    data_offset: u32,
+    width: u32,
+    height: u32,
+    /// size of a row in the file including the padding to 4 bytes
+    row_size: u32,
+    /// true if the first row in the file is the top row (the default is bottom-up)
+    top_down: bool,
+    bytes_per_pixel: u32,
+}
+
+impl BmpHeader {
+    /// Reads and checks the header of the file
+    pub(crate) fn read(source: &mut impl BmpSource) -> Result<Self, BmpError> {
+        let mut header = [0; 34];
+        source.read_at(0, &mut header)?;
+        let u16_at = |i: usize| {
+            let bytes = header.get(i..).and_then(<[u8]>::first_chunk);
+            bytes.copied().map_or(0, u16::from_le_bytes)
+        };
+        let u32_at = |i: usize| {
+            let bytes = header.get(i..).and_then(<[u8]>::first_chunk);
+            bytes.copied().map_or(0, u32::from_le_bytes)
+        };
+        let width = u32_at(18).cast_signed();
+        let height = u32_at(22).cast_signed();
+        let bytes_per_pixel = match (u16_at(28), u32_at(30)) {
+            (24, 0) => 3,
+            // with bit fields the default layout BGRA is assumed
+            (32, 0 | 3) => 4,
+            _ => return Err(BmpError::Format),
+        };
+        if &header[..2] != b"BM" || u32_at(14) < 40 || width <= 0 || height == 0 {
+            return Err(BmpError::Format);
+        }
+        let data_offset = u32_at(10);
+        // the offsets of all pixels have to fit in u32
+        let row_size = width
+            .unsigned_abs()
+            .checked_mul(bytes_per_pixel)
+            .and_then(|size| size.checked_next_multiple_of(4))
+            .ok_or(BmpError::Format)?;
+        row_size
+            .checked_mul(height.unsigned_abs())
+            .and_then(|size| size.checked_add(data_offset))
+            .ok_or(BmpError::Format)?;
+        Ok(Self {
+            data_offset,
+            width: width.unsigned_abs(),
+            height: height.unsigned_abs(),
+            row_size,
+            top_down: height < 0,
+            bytes_per_pixel,
+        })
+    }
+
+    /// Returns the offset of the pixel at (x, y) in the file, with y = 0 being the top row
+    fn offset(&self, x: u32, y: u32) -> u32 {
+        let row = if self.top_down {
+            y
+        } else {
+            self.height - 1 - y
+        };
+        self.data_offset + row * self.row_size + x * self.bytes_per_pixel
+    }
+
+    /// Writes the plane of the bits of the pixels which are dithered to `set` by calling `write`
+    /// with consecutive chunks of the plane, see `Display::black_plane` for the layout.
+    /// The image is placed at the top left corner of the panel with the unrotated `size`,
+    /// pixels outside the image are white. The errors of Floyd–Steinberg dithering are
+    /// diffused over `WIDTH` columns.
+    #[allow(clippy::cast_possible_wrap)]
+    pub(crate) fn write_plane<const WIDTH: usize, E: From<BmpError>>(
+        &self,
+        source: &mut impl BmpSource,
+        size: Size,
+        set: TriColor,
+        (palette, method): (DitherPalette, DitherMethod),
+        mut write: impl FnMut(&[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let mut diffusion = ErrorDiffusion::<WIDTH>::new();
+        let mut pixels = [0; PIXELS_PER_READ as usize * 4];
+        let mut chunk = [0; CHUNK_SIZE];
+        let mut len = 0;
+        for y in 0..size.height {
+            diffusion.next_row();
+            let mut byte = 0;
+            for x in 0..size.width {
+                let index = (x % PIXELS_PER_READ) as usize;
+                let color = if x < self.width && y < self.height {
+                    let bpp = self.bytes_per_pixel as usize;
+                    if index == 0 {
+                        let count = (self.width - x).min(PIXELS_PER_READ) as usize;
+                        let read = pixels.get_mut(..count * bpp).ok_or(BmpError::Format)?;
+                        source.read_at(self.offset(x, y), read)?;
+                    }
+                    let Some(&[b, g, r]) = pixels.get(index * bpp..index * bpp + 3) else {
+                        return Err(BmpError::Format.into());
+                    };
+                    let mut rgb = [r, g, b].map(i16::from);
+                    match method {
+                        DitherMethod::FloydSteinberg => diffusion.dither(x as usize, rgb, palette),
+                        DitherMethod::Ordered(matrix) => {
+                            add_offset(&mut rgb, matrix, Point::new(x as i32, y as i32));
+                            palette.nearest(rgb)
+                        }
+                    }
+                } else {
+                    TriColor::White
+                };
+                if color == set {
+                    byte |= packing::bit_mask(x as usize);
+                }
+                if x % 8 == 7 || x == size.width - 1 {
+                    if let Some(dst) = chunk.get_mut(len) {
+                        *dst = byte;
+                        len += 1;
+                    }
+                    byte = 0;
+                    if len == CHUNK_SIZE {
+                        write(&chunk)?;
+                        len = 0;
+                    }
+                }
+            }
+        }
+        match chunk.get(..len) {
+            None | Some([]) => Ok(()),
+            Some(data) => write(data),
+        }
+    }
+}
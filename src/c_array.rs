@@ -0,0 +1,170 @@
+//! Host-side loader for images which were exported as C arrays by the tools of Pervasive
+//! Displays (feature `std`), so the assets of the vendor SDK can be reused unchanged
+//!
+//! The arrays contain the data of the frame registers of the e-paper, i.e. the black plane
+//! and the red plane in the layout of [`Display::black_plane`](crate::Display::black_plane).
+//! An export consists of one array with the black plane followed by the red plane or of one
+//! array per plane. The planes can be drawn with [`PlanarImage`](crate::PlanarImage) or
+//! loaded with `Display::from_raw_planes`. `include_vendor_image!` of `epd-spectra-macros`
+//! does this at compile time.
+
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Errors of the parsing of C arrays
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum CArrayError {
+    #[error("invalid byte `{value}` in array `{name}`")]
+    Value { name: String, value: String },
+    #[error("initializer of array `{0}` is not terminated or nested")]
+    Initializer(String),
+    #[error("expected one or two arrays, found {0}")]
+    ArrayCount(usize),
+    #[error("the planes have different sizes")]
+    PlaneSize,
+}
+
+/// Byte array of a C source
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CArray {
+    /// Name of the array
+    pub name: String,
+    /// Bytes of the initializer
+    pub data: Vec<u8>,
+}
+
+/// Returns all byte arrays with an initializer in `source` in the order of their definition,
+/// e.g. `const uint8_t image_BW[] = { 0xff, 0x00, ... };`. The values can be hexadecimal,
+/// octal, binary or decimal with integer suffixes. Other definitions are skipped.
+///
+/// # Errors
+///
+/// This function will return an error if a value is no byte or an initializer is not
+/// terminated or contains nested braces.
+pub fn parse_c_arrays(source: &str) -> Result<Vec<CArray>, CArrayError> {
+    let source = strip_comments(source);
+    let mut arrays = Vec::new();
+    let mut rest = source.as_str();
+    while let Some((declaration, initializer)) = rest.split_once('{') {
+        let declaration = declaration
+            .rsplit_once([';', '}'])
+            .map_or(declaration, |(_, declaration)| declaration);
+        rest = initializer;
+        let Some((name, _)) = declaration.split_once('[') else {
+            continue;
+        };
+        let name = name
+            .split_whitespace()
+            .last()
+            .unwrap_or_default()
+            .to_string();
+        let Some((values, tail)) = rest
+            .find(['{', '}'])
+            .and_then(|end| rest.split_at_checked(end))
+            .and_then(|(values, tail)| Some((values, tail.strip_prefix('}')?)))
+        else {
+            return Err(CArrayError::Initializer(name));
+        };
+        let data = values
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| {
+                parse_byte(value).ok_or_else(|| CArrayError::Value {
+                    name: name.clone(),
+                    value: value.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        arrays.push(CArray { name, data });
+        rest = tail;
+    }
+    Ok(arrays)
+}
+
+/// Returns the black and the red plane of an image exported as C source, from one array
+/// with both planes or from two arrays with the black plane first
+///
+/// # Errors
+///
+/// This function will return an error if the source cannot be parsed, if it does not
+/// contain one or two arrays or if the planes differ in size.
+pub fn vendor_planes(source: &str) -> Result<(Vec<u8>, Vec<u8>), CArrayError> {
+    let mut arrays = parse_c_arrays(source)?;
+    match arrays.len() {
+        1 => {
+            let mut black = arrays.remove(0).data;
+            if !black.len().is_multiple_of(2) {
+                return Err(CArrayError::PlaneSize);
+            }
+            let red = black.split_off(black.len() / 2);
+            Ok((black, red))
+        }
+        2 => {
+            let red = arrays.remove(1).data;
+            let black = arrays.remove(0).data;
+            if black.len() != red.len() {
+                return Err(CArrayError::PlaneSize);
+            }
+            Ok((black, red))
+        }
+        count => Err(CArrayError::ArrayCount(count)),
+    }
+}
+
+/// Replaces the comments of C source by spaces, keeping the line breaks
+fn strip_comments(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        stripped.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    if c == '\n' {
+                        stripped.push('\n');
+                    }
+                    last = c;
+                }
+                stripped.push(' ');
+            }
+            _ => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+/// Parses an integer literal of C which fits into a byte
+fn parse_byte(value: &str) -> Option<u8> {
+    let value = value.trim_end_matches(['u', 'U', 'l', 'L']);
+    let (digits, radix) = if let Some(hex) = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        (hex, 16)
+    } else if let Some(binary) = value
+        .strip_prefix("0b")
+        .or_else(|| value.strip_prefix("0B"))
+    {
+        (binary, 2)
+    } else if let Some(octal) = value.strip_prefix('0').filter(|octal| !octal.is_empty()) {
+        (octal, 8)
+    } else {
+        (value, 10)
+    };
+    u8::from_str_radix(digits, radix).ok()
+}
@@ -0,0 +1,235 @@
+//! Run-length encoded frames which are decompressed on the fly while sending them to the e-paper
+
+use crate::{graphics::write_chunked, DisplayBuffer};
+
+/// Maximum number of bytes in one packet
+const MAX_PACKET_LEN: usize = 128;
+
+/// Frame with both planes compressed with the `PackBits` run-length encoding. E-paper frames
+/// usually contain large areas of the same color and compress very well, so many frames
+/// can be stored e.g. in the flash of a microcontroller. The frame is sent to the e-paper
+/// via `Epd::update` like a display buffer, but without the need for a full buffer in RAM.
+///
+/// Each plane consists of packets starting with a header byte `n`:
+/// - `0..=127`: the next `n + 1` bytes are copied
+/// - `129..=255`: the next byte is repeated `257 - n` times
+/// - `128`: no operation
+pub struct CompressedFrame<'a> {
+    black: &'a [u8],
+    red: &'a [u8],
+}
+
+impl<'a> CompressedFrame<'a> {
+    /// Creates a frame from compressed planes, e.g. created by [`Self::compress`] on a host
+    /// and included with `include_bytes!`. The size of the decompressed planes must match
+    /// the plane size of the e-paper ([`Self::plane_size`]).
+    /// Returns `None` if a plane is truncated or the decompressed planes differ in size.
+    #[must_use]
+    pub fn new(black: &'a [u8], red: &'a [u8]) -> Option<Self> {
+        let size = decompressed_size(black)?;
+        if decompressed_size(red)? != size {
+            return None;
+        }
+        Some(Self { black, red })
+    }
+
+    /// Compresses the planes of `display` into `buffer` as they would be sent to the e-paper.
+    /// `buffer` should have a size of at least [`Self::max_size`] to fit any frame.
+    /// Returns `None` if `buffer` is too small.
+    pub fn compress(display: &impl DisplayBuffer, buffer: &'a mut [u8]) -> Option<Self> {
+        let mut encoder = Encoder::new(buffer);
+        display.write_black(|data| encoder.push(data)).ok()?;
+        let black_len = encoder.finish()?;
+        let mut encoder = Encoder::new(buffer.get_mut(black_len..)?);
+        display.write_red(|data| encoder.push(data)).ok()?;
+        let red_len = encoder.finish()?;
+
+        let buffer: &'a [u8] = buffer;
+        let (black, red) = buffer.split_at(black_len);
+        Some(Self {
+            black,
+            red: red.get(..red_len)?,
+        })
+    }
+
+    /// Returns the maximum size of a compressed frame for planes with `plane_size` bytes
+    #[must_use]
+    pub const fn max_size(plane_size: usize) -> usize {
+        2 * (plane_size + plane_size.div_ceil(MAX_PACKET_LEN))
+    }
+
+    /// Returns the compressed black plane
+    #[must_use]
+    pub fn black(&self) -> &'a [u8] {
+        self.black
+    }
+
+    /// Returns the compressed red plane
+    #[must_use]
+    pub fn red(&self) -> &'a [u8] {
+        self.red
+    }
+
+    /// Returns the size of each plane after decompression
+    #[must_use]
+    pub fn plane_size(&self) -> usize {
+        Decoder::new(self.black).count()
+    }
+}
+
+impl DisplayBuffer for CompressedFrame<'_> {
+    fn write_black<E>(&self, write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        write_chunked(Decoder::new(self.black), write)
+    }
+    fn write_red<E>(&self, write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        write_chunked(Decoder::new(self.red), write)
+    }
+}
+
+/// Returns the decompressed size of `data` or `None` if the last packet is truncated
+fn decompressed_size(data: &[u8]) -> Option<usize> {
+    let mut size = 0;
+    let mut pos = 0;
+    while let Some(&header) = data.get(pos) {
+        let (len, packet_len) = match header {
+            0..=127 => (usize::from(header) + 1, usize::from(header) + 2),
+            128 => (0, 1),
+            _ => (257 - usize::from(header), 2),
+        };
+        pos += packet_len;
+        if pos > data.len() {
+            return None;
+        }
+        size += len;
+    }
+    Some(size)
+}
+
+/// Iterator over the decompressed bytes of a plane
+struct Decoder<'a> {
+    data: &'a [u8],
+    /// bytes left in the current packet
+    remaining: usize,
+    /// current packet is a run of one byte
+    repeat: bool,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            remaining: 0,
+            repeat: false,
+        }
+    }
+}
+
+impl Iterator for Decoder<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        while self.remaining == 0 {
+            let (&header, rest) = self.data.split_first()?;
+            self.data = rest;
+            (self.remaining, self.repeat) = match header {
+                0..=127 => (usize::from(header) + 1, false),
+                128 => (0, false),
+                _ => (257 - usize::from(header), true),
+            };
+        }
+        let (&byte, rest) = self.data.split_first()?;
+        self.remaining -= 1;
+        if !self.repeat || self.remaining == 0 {
+            self.data = rest;
+        }
+        Some(byte)
+    }
+}
+
+/// Error if the output buffer of the encoder is too small
+struct BufferTooSmall;
+
+/// Streaming `PackBits` encoder
+struct Encoder<'a> {
+    out: &'a mut [u8],
+    len: usize,
+    /// position of the header of the open literal packet
+    literal: Option<usize>,
+    /// byte and length of the current run
+    run: Option<(u8, usize)>,
+}
+
+impl<'a> Encoder<'a> {
+    fn new(out: &'a mut [u8]) -> Self {
+        Self {
+            out,
+            len: 0,
+            literal: None,
+            run: None,
+        }
+    }
+
+    fn push(&mut self, data: &[u8]) -> Result<(), BufferTooSmall> {
+        for &byte in data {
+            match self.run {
+                Some((value, count)) if value == byte => self.run = Some((value, count + 1)),
+                Some((value, count)) => {
+                    self.flush_run(value, count)?;
+                    self.run = Some((byte, 1));
+                }
+                None => self.run = Some((byte, 1)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Flushes the pending run and returns the size of the output
+    fn finish(mut self) -> Option<usize> {
+        if let Some((value, count)) = self.run.take() {
+            self.flush_run(value, count).ok()?;
+        }
+        Some(self.len)
+    }
+
+    /// Emits a run of `count` bytes `value` as repeat packets or, if it is short, as part of
+    /// a literal packet. Runs of two bytes are only worth a packet if there is no open literal.
+    fn flush_run(&mut self, value: u8, mut count: usize) -> Result<(), BufferTooSmall> {
+        while count > 0 {
+            if count >= 3 || (count == 2 && self.literal.is_none()) {
+                let n = count.min(MAX_PACKET_LEN);
+                self.literal = None;
+                #[allow(clippy::cast_possible_truncation)]
+                self.emit((257 - n) as u8)?;
+                self.emit(value)?;
+                count -= n;
+            } else {
+                self.push_literal(value)?;
+                count -= 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn push_literal(&mut self, value: u8) -> Result<(), BufferTooSmall> {
+        let header = if let Some(header) = self.literal {
+            header
+        } else {
+            self.emit(0)?;
+            self.len - 1
+        };
+        self.emit(value)?;
+        let len = self.len - header - 1;
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            *self.out.get_mut(header).ok_or(BufferTooSmall)? = (len - 1) as u8;
+        }
+        self.literal = (len < MAX_PACKET_LEN).then_some(header);
+        Ok(())
+    }
+
+    fn emit(&mut self, byte: u8) -> Result<(), BufferTooSmall> {
+        *self.out.get_mut(self.len).ok_or(BufferTooSmall)? = byte;
+        self.len += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,425 @@
+//! Host-side conversion of images into the raw formats of this crate and export of display
+//! buffers to images (feature `image`)
+
+use core::str::FromStr;
+use std::{
+    format, fs, io,
+    path::{Path, PathBuf},
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    pixelcolor::{
+        raw::{RawData, RawU2},
+        Rgb888, RgbColor,
+    },
+    primitives::Rectangle,
+    Pixel,
+};
+
+use crate::{Display, DitherMethod, DitherPalette, DitherTarget, ThresholdMatrix, TriColor};
+
+/// Maximum width of images which are converted with error diffusion
+const MAX_DITHER_WIDTH: usize = 2048;
+
+/// Raw formats of converted images
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RawFormat {
+    /// 2 bits per pixel as used by `ImageRaw<TriColor>`
+    #[default]
+    Interleaved,
+    /// Black and red plane as used by [`PlanarImage`](crate::PlanarImage)
+    Planar,
+}
+
+/// Options for the conversion of images, see [`generate_assets`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ConvertOptions {
+    /// Dithering method, `None` maps each pixel like `TriColor::from(Rgb888)`
+    pub dithering: Option<DitherMethod>,
+    /// Colors used for dithering
+    pub palette: DitherPalette,
+    pub format: RawFormat,
+}
+
+/// Error of parsing an option of the conversion, e.g. an unknown dithering method
+#[derive(thiserror::Error, Clone, PartialEq, Eq, Debug)]
+#[error("invalid {option} `{value}`, expected {expected}")]
+pub struct ParseOptionError {
+    option: &'static str,
+    value: String,
+    expected: &'static str,
+}
+
+impl ParseOptionError {
+    fn new(option: &'static str, value: &str, expected: &'static str) -> Self {
+        Self {
+            option,
+            value: value.to_string(),
+            expected,
+        }
+    }
+}
+
+/// Parses the names used by `epd-convert` and `include_epd_image!`: `floyd_steinberg`,
+/// `bayer2`, `bayer4`, `bayer8` or `blue_noise`
+impl FromStr for DitherMethod {
+    type Err = ParseOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "floyd_steinberg" => Ok(DitherMethod::FloydSteinberg),
+            "bayer2" => Ok(DitherMethod::Ordered(ThresholdMatrix::Bayer2)),
+            "bayer4" => Ok(DitherMethod::Ordered(ThresholdMatrix::Bayer4)),
+            "bayer8" => Ok(DitherMethod::Ordered(ThresholdMatrix::Bayer8)),
+            "blue_noise" => Ok(DitherMethod::Ordered(ThresholdMatrix::BlueNoise16)),
+            _ => Err(ParseOptionError::new(
+                "dithering method",
+                s,
+                "floyd_steinberg, bayer2, bayer4, bayer8 or blue_noise",
+            )),
+        }
+    }
+}
+
+/// Parses `tricolor` or `monochrome`
+impl FromStr for DitherPalette {
+    type Err = ParseOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tricolor" => Ok(DitherPalette::TriColor),
+            "monochrome" => Ok(DitherPalette::Monochrome),
+            _ => Err(ParseOptionError::new(
+                "palette",
+                s,
+                "tricolor or monochrome",
+            )),
+        }
+    }
+}
+
+/// Parses `interleaved` or `planar`
+impl FromStr for RawFormat {
+    type Err = ParseOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "interleaved" => Ok(RawFormat::Interleaved),
+            "planar" => Ok(RawFormat::Planar),
+            _ => Err(ParseOptionError::new("format", s, "interleaved or planar")),
+        }
+    }
+}
+
+/// Parses the dithering option of [`ConvertOptions`]: `none` for no dithering or a
+/// [`DitherMethod`]
+///
+/// # Errors
+///
+/// Returns an error if `s` is neither `none` nor the name of a method.
+pub fn parse_dithering(s: &str) -> Result<Option<DitherMethod>, ParseOptionError> {
+    match s {
+        "none" => Ok(None),
+        _ => s.parse().map(Some).map_err(|_| {
+            ParseOptionError::new(
+                "dithering method",
+                s,
+                "none, floyd_steinberg, bayer2, bayer4, bayer8 or blue_noise",
+            )
+        }),
+    }
+}
+
+/// Image converted into [`TriColor`] pixels, e.g. loaded from a PNG or BMP file
+pub struct ConvertedImage {
+    size: Size,
+    pixels: Vec<TriColor>,
+}
+
+impl ConvertedImage {
+    /// Opens and converts the image at `path`, see [`Self::from_rgb`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be read or decoded.
+    pub fn open(
+        path: impl AsRef<Path>,
+        dithering: Option<DitherMethod>,
+        palette: DitherPalette,
+    ) -> image::ImageResult<Self> {
+        Ok(Self::from_rgb(
+            &image::open(path)?.to_rgb8(),
+            dithering,
+            palette,
+        ))
+    }
+
+    /// Converts an RGB image. Without dithering each pixel is mapped like
+    /// `TriColor::from(Rgb888)`, otherwise it is dithered with the colors of `palette`.
+    /// Images wider than 2048 pixels are dithered with errors diffused only in the first columns.
+    #[must_use]
+    pub fn from_rgb(
+        image: &image::RgbImage,
+        dithering: Option<DitherMethod>,
+        palette: DitherPalette,
+    ) -> Self {
+        let size = Size::new(image.width(), image.height());
+        let mut converted = Self {
+            size,
+            pixels: vec![TriColor::White; size.width as usize * size.height as usize],
+        };
+        let colors = image.pixels().map(|p| Rgb888::new(p[0], p[1], p[2]));
+        let area = Rectangle::new(Point::zero(), size);
+        let _ = match dithering {
+            Some(method) => {
+                let mut target: DitherTarget<_, Rgb888, MAX_DITHER_WIDTH> =
+                    DitherTarget::new(&mut converted, palette);
+                target.set_method(method);
+                target.fill_contiguous(&area, colors)
+            }
+            None => converted.fill_contiguous(&area, colors.map(TriColor::from)),
+        };
+        converted
+    }
+
+    /// Returns the pixels row by row
+    #[must_use]
+    pub fn pixels(&self) -> &[TriColor] {
+        &self.pixels
+    }
+
+    /// Returns the image in the interleaved format with 2 bits per pixel as used by
+    /// `ImageRaw<TriColor>` and [`InterleavedDisplay`](crate::InterleavedDisplay).
+    /// Each row is padded to whole bytes.
+    #[must_use]
+    pub fn to_interleaved(&self) -> Vec<u8> {
+        self.rows()
+            .flat_map(|row| {
+                row.chunks(4).map(|pixels| {
+                    (0..4).fold(0, |byte, i| {
+                        let value = pixels
+                            .get(i)
+                            .map_or(0, |color| RawU2::from(*color).into_inner());
+                        (byte << 2) | value
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the black and the red plane with 1 bit per pixel as used by
+    /// [`PlanarImage`](crate::PlanarImage). Each row is padded to whole bytes.
+    #[must_use]
+    pub fn to_planes(&self) -> (Vec<u8>, Vec<u8>) {
+        let plane = |set: TriColor| -> Vec<u8> {
+            self.rows()
+                .flat_map(|row| {
+                    row.chunks(8).map(move |pixels| {
+                        (0..8).fold(0, |byte, i| {
+                            (byte << 1) | u8::from(pixels.get(i) == Some(&set))
+                        })
+                    })
+                })
+                .collect()
+        };
+        (plane(TriColor::Black), plane(TriColor::Red))
+    }
+
+    /// Returns the Rust code of a public constant `name` with the image in `format`, i.e. an
+    /// `ImageRaw<TriColor>` or a `PlanarImage`
+    #[must_use]
+    pub fn to_rust(&self, name: &str, format: RawFormat) -> String {
+        let width = self.size.width;
+        match format {
+            RawFormat::Interleaved => format!(
+                "pub const {name}: ::embedded_graphics::image::ImageRaw<'static, ::epd_spectra::TriColor> =\n    \
+                 ::embedded_graphics::image::ImageRaw::new(&{:?}, {width});\n",
+                self.to_interleaved()
+            ),
+            RawFormat::Planar => {
+                let (black, red) = self.to_planes();
+                format!(
+                    "pub const {name}: ::epd_spectra::PlanarImage<'static> =\n    \
+                     ::epd_spectra::PlanarImage::new(&{black:?}, &{red:?}, {width});\n"
+                )
+            }
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[TriColor]> {
+        self.pixels.chunks(self.size.width.max(1) as usize)
+    }
+}
+
+impl OriginDimensions for ConvertedImage {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl DrawTarget for ConvertedImage {
+    type Color = TriColor;
+    type Error = core::convert::Infallible;
+
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let width = self.size.width as i32;
+        let height = self.size.height as i32;
+        for Pixel(p, color) in pixels {
+            if (0..width).contains(&p.x) && (0..height).contains(&p.y) {
+                if let Some(pixel) = self.pixels.get_mut((p.y * width + p.x) as usize) {
+                    *pixel = color;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>
+    Display<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    /// Returns the content of the display as RGB image in display coordinates (i.e. rotated),
+    /// e.g. to check the rendering on the host without hardware
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn to_image(&self) -> image::RgbImage {
+        let size = self.size();
+        let mut image = image::RgbImage::new(size.width, size.height);
+        for Pixel(p, color) in self.pixels() {
+            let color = Rgb888::from(color);
+            image.put_pixel(
+                p.x as u32,
+                p.y as u32,
+                image::Rgb([color.r(), color.g(), color.b()]),
+            );
+        }
+        image
+    }
+
+    /// Saves the content of the display as image, see [`Self::to_image`].
+    /// The format is determined by the extension of `path` (PNG or BMP).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be encoded or written.
+    pub fn save_image(&self, path: impl AsRef<Path>) -> image::ImageResult<()> {
+        self.to_image().save(path)
+    }
+}
+
+/// Error of [`generate_assets`]
+#[derive(thiserror::Error, Debug)]
+pub enum AssetError {
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The file name of the image contains no letter or digit for the name of the constant
+    #[error("no constant name for `{}`", .0.display())]
+    EmptyName(PathBuf),
+    /// Two images result in the same constant, e.g. `wifi-on.png` and `wifi_on.bmp`
+    #[error("`{}` and `{}` are both named `{name}`", .first.display(), .second.display())]
+    DuplicateName {
+        name: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
+}
+
+/// Converts all PNG and BMP images in the directory `dir` and writes a Rust module with a
+/// constant per image to `out`. The constants are named after the files in upper case
+/// (`icons/wifi-on.png`: `WIFI_ON`). Returns the paths of the converted images, intended for
+/// build scripts which have to be rerun if the directory or an image changes:
+/// ```ignore
+/// // build.rs
+/// let out = Path::new(&std::env::var("OUT_DIR").unwrap()).join("assets.rs");
+/// println!("cargo:rerun-if-changed=assets");
+/// for path in generate_assets("assets", &out, &ConvertOptions::default()).unwrap() {
+///     println!("cargo:rerun-if-changed={}", path.display());
+/// }
+///
+/// // main.rs
+/// mod assets {
+///     include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+/// }
+/// ```
+/// The module requires `embedded-graphics` as dependency for the interleaved format.
+///
+/// # Errors
+///
+/// Returns an error if the directory or an image cannot be read, the module cannot be written
+/// or the file names of the images don't result in distinct constant names.
+pub fn generate_assets(
+    dir: impl AsRef<Path>,
+    out: impl AsRef<Path>,
+    options: &ConvertOptions,
+) -> Result<Vec<PathBuf>, AssetError> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase());
+        if matches!(extension.as_deref(), Some("png" | "bmp")) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut names: Vec<(String, &Path)> = Vec::with_capacity(paths.len());
+    for path in &paths {
+        let name = path
+            .file_stem()
+            .map(|stem| constant_name(&stem.to_string_lossy()))
+            .unwrap_or_default();
+        if !name.contains(|c: char| c.is_ascii_alphanumeric()) {
+            return Err(AssetError::EmptyName(path.clone()));
+        }
+        if let Some((_, first)) = names.iter().find(|(other, _)| *other == name) {
+            return Err(AssetError::DuplicateName {
+                name,
+                first: first.to_path_buf(),
+                second: path.clone(),
+            });
+        }
+        names.push((name, path));
+    }
+
+    let mut module = String::from("// Generated by epd_spectra::convert::generate_assets\n");
+    for (name, path) in &names {
+        let image = ConvertedImage::open(path, options.dithering, options.palette)?;
+        module.push('\n');
+        module.push_str(&image.to_rust(name, options.format));
+    }
+    fs::write(out, module)?;
+    Ok(paths)
+}
+
+/// Returns `name` as name of a constant, e.g. `wifi-on`: `WIFI_ON`. Characters other than
+/// ASCII letters and digits are replaced by `_` and names starting with a digit get a leading
+/// `_`, so the result is an identifier if `name` contains a letter or digit.
+#[must_use]
+pub fn constant_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
@@ -0,0 +1,123 @@
+//! Draw target for a region of a display buffer
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    image::GetPixel,
+    primitives::{PointsIter, Rectangle},
+    Pixel,
+};
+
+use crate::{Display, TriColor};
+
+/// Draw target for a rectangular region of a [`Display`], created by [`Display::cropped`].
+/// Drawing is translated by the top left corner of the region and pixels outside the region are discarded.
+pub struct CroppedDisplay<'a, const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> {
+    display: &'a mut Display<SIZE_V, SIZE_H, IMAGE_SIZE>,
+    area: Rectangle,
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>
+    Display<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    /// Returns a draw target for the region `area` (in display coordinates) of the display.
+    /// The origin of the returned draw target is the top left corner of `area`.
+    pub fn cropped(&mut self, area: &Rectangle) -> CroppedDisplay<'_, SIZE_V, SIZE_H, IMAGE_SIZE> {
+        CroppedDisplay {
+            display: self,
+            area: *area,
+        }
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>
+    CroppedDisplay<'_, SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    /// Returns the region of the display in display coordinates
+    #[must_use]
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+
+    /// Returns the underlying display buffer, e.g. to access the planes
+    #[must_use]
+    pub fn display(&self) -> &Display<SIZE_V, SIZE_H, IMAGE_SIZE> {
+        self.display
+    }
+
+    /// Returns the underlying display buffer as mutable reference
+    pub fn display_mut(&mut self) -> &mut Display<SIZE_V, SIZE_H, IMAGE_SIZE> {
+        self.display
+    }
+
+    /// Converts an area in local coordinates into display coordinates, clipped to the region
+    fn to_display_area(&self, area: &Rectangle) -> Rectangle {
+        Rectangle::new(area.top_left + self.area.top_left, area.size).intersection(&self.area)
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> OriginDimensions
+    for CroppedDisplay<'_, SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    fn size(&self) -> Size {
+        self.area.size
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> DrawTarget
+    for CroppedDisplay<'_, SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    type Color = TriColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let area = self.area;
+        self.display.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(p, color)| Pixel(p + area.top_left, color))
+                .filter(|Pixel(p, _)| area.contains(*p)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let display_area = self.to_display_area(area);
+        if display_area.size == area.size {
+            self.display.fill_contiguous(&display_area, colors)
+        } else {
+            self.draw_iter(area.points().zip(colors).map(|(p, color)| Pixel(p, color)))
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let display_area = self.to_display_area(area);
+        self.display.fill_solid(&display_area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.display.fill_solid(&self.area, color)
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> GetPixel
+    for CroppedDisplay<'_, SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    type Color = TriColor;
+
+    /// Returns the color of the pixel at point `p` in local coordinates
+    /// or `None` if the point is outside the region or the display.
+    fn pixel(&self, p: Point) -> Option<Self::Color> {
+        let p = p + self.area.top_left;
+        if self.area.contains(p) {
+            self.display.pixel(p)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! Dithering draw target which converts `Rgb888`/`Gray8` content into [`TriColor`]
+
+use core::marker::PhantomData;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point},
+    pixelcolor::{PixelColor, Rgb888, RgbColor},
+    primitives::{PointsIter, Rectangle},
+    Pixel,
+};
+
+use crate::TriColor;
+
+/// Colors used for the dithered output
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DitherPalette {
+    /// Black and white, e.g. for grayscale images
+    Monochrome,
+    /// Black, white and red
+    #[default]
+    TriColor,
+}
+
+impl DitherPalette {
+    fn colors(self) -> &'static [TriColor] {
+        match self {
+            DitherPalette::Monochrome => &[TriColor::White, TriColor::Black],
+            DitherPalette::TriColor => &[TriColor::White, TriColor::Black, TriColor::Red],
+        }
+    }
+
+    /// Returns the color of the palette which is nearest to `rgb`
+    pub(crate) fn nearest(self, rgb: [i16; 3]) -> TriColor {
+        let distance = |color: &TriColor| {
+            channels(Rgb888::from(*color))
+                .iter()
+                .zip(rgb)
+                .map(|(a, b)| (i32::from(*a) - i32::from(b)).pow(2))
+                .sum::<i32>()
+        };
+        self.colors()
+            .iter()
+            .copied()
+            .min_by_key(distance)
+            .unwrap_or_default()
+    }
+}
+
+/// Dithering methods
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DitherMethod {
+    /// Floyd–Steinberg error diffusion, see [`DitherTarget`]
+    #[default]
+    FloydSteinberg,
+    /// Ordered dithering with a threshold matrix which is tiled over the display. It is
+    /// deterministic, needs no error buffer (`WIDTH` can be 0) and is cheap enough to be
+    /// used while drawing.
+    Ordered(ThresholdMatrix),
+}
+
+/// Threshold matrices for ordered dithering
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ThresholdMatrix {
+    /// Bayer matrix with 2 x 2 entries
+    Bayer2,
+    /// Bayer matrix with 4 x 4 entries
+    Bayer4,
+    /// Bayer matrix with 8 x 8 entries
+    Bayer8,
+    /// Blue noise matrix with 16 x 16 entries, which avoids the regular pattern of the Bayer matrices
+    BlueNoise16,
+}
+
+impl ThresholdMatrix {
+    /// Returns the offset (-128..128) which is added to the channels of the pixel at (x, y)
+    #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+    fn offset(self, x: u32, y: u32) -> i16 {
+        let (rank, size) = match self {
+            ThresholdMatrix::Bayer2 => (bayer(1, x, y), 2),
+            ThresholdMatrix::Bayer4 => (bayer(2, x, y), 4),
+            ThresholdMatrix::Bayer8 => (bayer(3, x, y), 8),
+            ThresholdMatrix::BlueNoise16 => {
+                let row = BLUE_NOISE_16.get(y as usize % 16);
+                let rank = row.and_then(|row| row.get(x as usize % 16));
+                (rank.copied().map_or(0, u32::from), 16)
+            }
+        };
+        let levels = size * size;
+        ((2 * rank + 1) * 128 / levels) as i16 - 128
+    }
+}
+
+/// Returns the entry at (x, y) of the Bayer matrix with 2^`bits` x 2^`bits` entries
+fn bayer(bits: u32, x: u32, y: u32) -> u32 {
+    (0..bits).fold(0, |rank, bit| {
+        let value = ((((x ^ y) >> bit) & 1) << 1) | ((y >> bit) & 1);
+        rank | (value << (2 * (bits - 1 - bit)))
+    })
+}
+
+/// Blue noise threshold matrix created with the void-and-cluster method
+#[rustfmt::skip]
+const BLUE_NOISE_16: [[u8; 16]; 16] = [
+    [203, 231, 121, 145, 174,  62, 136, 187, 157,  21, 130,  75,  12,  99,  17,  83],
+    [160,  22,   1, 217,  87, 229,  11,  79,  50, 219, 240, 167, 204, 142,  53, 178],
+    [ 93, 242,  68, 189,  44, 117, 165, 236, 101, 195,  30, 118,  45, 188, 253, 115],
+    [ 42, 129, 169, 106, 247, 150,  19, 207, 125, 147,  63,  89, 214,   4,  70, 220],
+    [151, 208,  80,  32, 197,  57,  73, 180,  40,   8, 176, 246, 154, 105, 138,  26],
+    [ 61, 237,  13, 141, 221,  96, 133, 250, 109,  82, 225, 131,  35, 199, 233, 171],
+    [112, 193,  51, 122, 162,   6, 230,  25, 213, 166, 192,  20,  55,  76,  92,  18],
+    [222,  85, 175, 254,  39, 185,  90, 153,  48,  67,  98, 119, 161, 249, 183, 127],
+    [158,   2, 102,  69, 205, 114,  58, 202, 139,   0, 241, 206, 144,  10, 211,  46],
+    [245, 143, 232,  27, 148,  78, 239, 172, 124, 228,  86,  41, 177,  31, 104,  65],
+    [186,  36, 198, 128, 215,   9,  23, 100,  33, 182, 156,  59, 113, 224, 134,  81],
+    [ 15, 116,  60,  91, 164, 248, 135, 194,  74, 218,  14, 252,  72, 196, 235, 163],
+    [209, 170, 226,  43, 107, 181,  54, 234,  47, 120, 103, 140, 173,   5,  49,  94],
+    [251, 137,   7, 191,  71,  16, 152,  84, 168, 200,  28, 210,  88, 123, 149,  24],
+    [108,  77, 155, 243, 212, 126, 111, 223,   3, 146, 244,  56,  38, 190, 216,  64],
+    [ 34, 184,  52,  97,  29, 201,  37, 255,  95,  66, 179, 110, 227, 159, 238, 132],
+];
+
+/// Draw target which dithers colors and draws the result to a [`TriColor`] draw target,
+/// e.g. a display buffer. Content in `C` (like `Rgb888` or `Gray8` images) can be drawn
+/// directly and photos look reasonable on the e-paper. See [`DitherMethod`] for the methods.
+///
+/// With Floyd–Steinberg the error is diffused within areas drawn with `fill_contiguous`
+/// (e.g. images) row by row, so only two rows of errors are kept. `WIDTH` is the maximum
+/// width of these areas, errors of columns beyond are dropped. Single pixels drawn with
+/// `draw_iter` are mapped to the nearest color without diffusion.
+pub struct DitherTarget<'a, D, C, const WIDTH: usize> {
+    target: &'a mut D,
+    palette: DitherPalette,
+    method: DitherMethod,
+    diffusion: ErrorDiffusion<WIDTH>,
+    color: PhantomData<C>,
+}
+
+impl<'a, D, C, const WIDTH: usize> DitherTarget<'a, D, C, WIDTH>
+where
+    D: DrawTarget<Color = TriColor>,
+{
+    /// Creates a dithering draw target which draws to `target` with the colors of `palette`.
+    /// Floyd–Steinberg dithering is used by default.
+    pub fn new(target: &'a mut D, palette: DitherPalette) -> Self {
+        Self {
+            target,
+            palette,
+            method: DitherMethod::default(),
+            diffusion: ErrorDiffusion::new(),
+            color: PhantomData,
+        }
+    }
+
+    pub fn set_method(&mut self, method: DitherMethod) {
+        self.method = method;
+    }
+    #[must_use]
+    pub fn method(&self) -> DitherMethod {
+        self.method
+    }
+}
+
+impl<D, C, const WIDTH: usize> Dimensions for DitherTarget<'_, D, C, WIDTH>
+where
+    D: DrawTarget<Color = TriColor>,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D, C, const WIDTH: usize> DrawTarget for DitherTarget<'_, D, C, WIDTH>
+where
+    D: DrawTarget<Color = TriColor>,
+    C: PixelColor + Into<Rgb888>,
+{
+    type Color = C;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let palette = self.palette;
+        let method = self.method;
+        self.target
+            .draw_iter(pixels.into_iter().map(|Pixel(p, color)| {
+                let mut rgb = channels(color.into());
+                if let DitherMethod::Ordered(matrix) = method {
+                    add_offset(&mut rgb, matrix, p);
+                }
+                Pixel(p, palette.nearest(rgb))
+            }))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if let DitherMethod::Ordered(_) = self.method {
+            // the threshold only depends on the position
+            return self.draw_iter(area.points().zip(colors).map(|(p, color)| Pixel(p, color)));
+        }
+
+        let Self {
+            target,
+            palette,
+            diffusion,
+            ..
+        } = self;
+        *diffusion = ErrorDiffusion::new();
+        let width = area.size.width as usize;
+        if width == 0 {
+            return Ok(());
+        }
+
+        let colors = colors.into_iter().enumerate().map(|(i, color)| {
+            let x = i % width;
+            if x == 0 && i > 0 {
+                diffusion.next_row();
+            }
+            diffusion.dither(x, channels(color.into()), *palette)
+        });
+        target.fill_contiguous(area, colors)
+    }
+}
+
+/// Floyd–Steinberg error diffusion over consecutive rows of up to `WIDTH` pixels
+pub(crate) struct ErrorDiffusion<const WIDTH: usize> {
+    /// errors per channel of the current and the next row
+    errors: [[[i16; 3]; WIDTH]; 2],
+}
+
+impl<const WIDTH: usize> ErrorDiffusion<WIDTH> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            errors: [[[0; 3]; WIDTH]; 2],
+        }
+    }
+
+    /// Moves on to the next row
+    pub(crate) fn next_row(&mut self) {
+        self.errors[0] = self.errors[1];
+        self.errors[1] = [[0; 3]; WIDTH];
+    }
+
+    /// Returns the color of the palette for the pixel in column `x` of the current row
+    /// and diffuses its error
+    pub(crate) fn dither(
+        &mut self,
+        x: usize,
+        mut rgb: [i16; 3],
+        palette: DitherPalette,
+    ) -> TriColor {
+        if let Some(error) = self.errors[0].get(x) {
+            for (value, error) in rgb.iter_mut().zip(error) {
+                *value = (*value + *error).clamp(0, 255);
+            }
+        }
+        let dithered = palette.nearest(rgb);
+        let quantized = channels(Rgb888::from(dithered));
+        let ([r, g, b], [quantized_r, quantized_g, quantized_b]) = (rgb, quantized);
+        let error = [r - quantized_r, g - quantized_g, b - quantized_b];
+
+        // distribute the error to the neighbours: 7/16 right, 3/16 bottom left,
+        // 5/16 bottom and 1/16 bottom right
+        let [current, next] = &mut self.errors;
+        add_error(current, x + 1, error, 7);
+        if let Some(left) = x.checked_sub(1) {
+            add_error(next, left, error, 3);
+        }
+        add_error(next, x, error, 5);
+        add_error(next, x + 1, error, 1);
+        dithered
+    }
+}
+
+/// Adds the offset of the threshold matrix at point `p` to the channels
+#[allow(clippy::cast_sign_loss)]
+pub(crate) fn add_offset(rgb: &mut [i16; 3], matrix: ThresholdMatrix, p: Point) {
+    let offset = matrix.offset(p.x as u32, p.y as u32);
+    for value in rgb {
+        *value += offset;
+    }
+}
+
+/// Adds `weight`/16 of `error` to the error of column `x`
+fn add_error(row: &mut [[i16; 3]], x: usize, error: [i16; 3], weight: i16) {
+    if let Some(value) = row.get_mut(x) {
+        for (value, error) in value.iter_mut().zip(error) {
+            *value += error * weight / 16;
+        }
+    }
+}
+
+/// Returns the red, green and blue channel of `color`
+pub(crate) fn channels(color: Rgb888) -> [i16; 3] {
+    [color.r(), color.g(), color.b()].map(i16::from)
+}
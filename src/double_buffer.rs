@@ -0,0 +1,156 @@
+//! Display buffer wrapper which keeps the previously transmitted frame
+
+use core::{
+    cmp::{max, min},
+    ops::{Deref, DerefMut},
+};
+use embedded_graphics::{
+    geometry::{Dimensions, Point, Size},
+    primitives::Rectangle,
+};
+
+use crate::{Display, DisplayBuffer};
+
+/// Wrapper around a display buffer which keeps a copy of the frame that was sent to the
+/// e-paper last. It reports whether and where the current frame differs, e.g. to skip
+/// refreshes if nothing changed: call `Epd::update` only if [`Self::is_changed`] returns
+/// true and [`Self::mark_transmitted`] afterwards.
+/// The wrapper dereferences to the current display buffer, which is used for drawing.
+pub struct DoubleBuffered<D> {
+    current: D,
+    previous: D,
+}
+
+impl<D: Default> Default for DoubleBuffered<D> {
+    fn default() -> Self {
+        Self {
+            current: D::default(),
+            previous: D::default(),
+        }
+    }
+}
+
+impl<D> Deref for DoubleBuffered<D> {
+    type Target = D;
+
+    fn deref(&self) -> &Self::Target {
+        &self.current
+    }
+}
+
+impl<D> DerefMut for DoubleBuffered<D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.current
+    }
+}
+
+impl<D: DisplayBuffer> DisplayBuffer for DoubleBuffered<D> {
+    #[allow(deprecated)]
+    fn get_buffer_black(&self) -> &[u8] {
+        self.current.get_buffer_black()
+    }
+    #[allow(deprecated)]
+    fn get_buffer_red(&self) -> &[u8] {
+        self.current.get_buffer_red()
+    }
+    fn write_black<E>(&self, write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        self.current.write_black(write)
+    }
+    fn write_red<E>(&self, write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        self.current.write_red(write)
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>
+    DoubleBuffered<Display<SIZE_V, SIZE_H, IMAGE_SIZE>>
+{
+    /// Creates a wrapper around `display`. The previous frame is assumed to be white.
+    #[must_use]
+    pub fn new(display: Display<SIZE_V, SIZE_H, IMAGE_SIZE>) -> Self {
+        Self {
+            current: display,
+            previous: Display::default(),
+        }
+    }
+
+    /// Returns the frame which was sent to the e-paper last
+    #[must_use]
+    pub fn previous(&self) -> &Display<SIZE_V, SIZE_H, IMAGE_SIZE> {
+        &self.previous
+    }
+
+    /// Returns true if the current frame differs from the previous frame, this includes
+    /// changes of [`Display::set_inverted`] and [`Display::set_chromatic_as_black`] which
+    /// change the frame that is sent
+    #[must_use]
+    pub fn is_changed(&self) -> bool {
+        self.flags_changed()
+            || self.current.black_plane() != self.previous.black_plane()
+            || self.current.chromatic_plane() != self.previous.chromatic_plane()
+    }
+
+    /// Returns the bounding box (in display coordinates) of all pixels which differ
+    /// between the current and the previous frame or `None` if the frames are equal.
+    /// The whole display has changed if the flags of the conversion changed.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub fn changed_area(&self) -> Option<Rectangle> {
+        if self.flags_changed() {
+            return Some(self.current.bounding_box());
+        }
+        let bytes_per_row = SIZE_H as usize / 8;
+        // (top left, bottom right) in buffer coordinates
+        let mut corners: Option<(Point, Point)> = None;
+
+        let planes = [
+            (self.current.black_plane(), self.previous.black_plane()),
+            (
+                self.current.chromatic_plane(),
+                self.previous.chromatic_plane(),
+            ),
+        ];
+        for (current, previous) in planes {
+            let bytes = current.iter().zip(previous).map(|(c, p)| c ^ p);
+            for (index, diff) in bytes.enumerate().filter(|(_, diff)| *diff != 0) {
+                let y = (index / bytes_per_row) as i32;
+                let x = (index % bytes_per_row * 8) as i32;
+                let first = Point::new(x + diff.leading_zeros() as i32, y);
+                let last = Point::new(x + 7 - diff.trailing_zeros() as i32, y);
+                corners = Some(corners.map_or((first, last), |(top_left, bottom_right)| {
+                    (
+                        Point::new(min(top_left.x, first.x), min(top_left.y, y)),
+                        Point::new(max(bottom_right.x, last.x), max(bottom_right.y, y)),
+                    )
+                }));
+            }
+        }
+
+        let (top_left, bottom_right) = corners?;
+        let buffer_area = Rectangle::with_corners(top_left, bottom_right);
+        Some(
+            self.current
+                .rotation()
+                .display_area(&buffer_area, Size::new(SIZE_H, SIZE_V)),
+        )
+    }
+
+    /// Marks the current frame as sent to the e-paper, i.e. copies it into the previous frame.
+    /// Call this after a successful update.
+    pub fn mark_transmitted(&mut self) {
+        self.previous.set_inverted(self.current.inverted());
+        self.previous
+            .set_chromatic_as_black(self.current.chromatic_as_black());
+        self.previous
+            .black_plane_mut()
+            .copy_from_slice(self.current.black_plane());
+        self.previous
+            .chromatic_plane_mut()
+            .copy_from_slice(self.current.chromatic_plane());
+    }
+
+    /// Returns true if the flags which convert the frame when it is sent differ
+    fn flags_changed(&self) -> bool {
+        self.current.inverted() != self.previous.inverted()
+            || self.current.chromatic_as_black() != self.previous.chromatic_as_black()
+    }
+}
@@ -1,11 +1,24 @@
 //! Generic SPI driver for all EPDs
 
-use core::marker::PhantomData;
-use embedded_hal::{delay::DelayNs, digital::InputPin, digital::OutputPin, spi::SpiDevice};
+use core::{marker::PhantomData, ops::RangeInclusive};
+use embedded_graphics::geometry::Size;
+use embedded_hal::{
+    delay::DelayNs,
+    digital::InputPin,
+    digital::OutputPin,
+    spi::{Operation, SpiDevice},
+};
+#[cfg(feature = "embedded-storage")]
+use embedded_storage::nor_flash::{NorFlashError, NorFlashErrorKind, ReadNorFlash};
 
-use crate::DisplayBuffer;
+use crate::{
+    graphics::{write_chunked, CHUNK_SIZE},
+    DisplayBuffer, Strip, TriColor,
+};
+#[cfg(feature = "embedded-sdmmc")]
+use crate::{BmpError, BmpHeader, BmpSource, DitherMethod, DitherPalette};
 
-enum Command {
+pub(crate) enum Command {
     Psr = 0x00,
     PowerOff = 0x02,
     PowerOn = 0x04,
@@ -17,18 +30,49 @@ enum Command {
 }
 
 /// Config register data for sizes other than 4.2"
-const REG_DATA_SOFT_RESET: &[u8] = &[0x0e];
-const REG_DATA_INPUT_TEMP: &[u8] = &[0x19];
-const REG_DATA_ACTIVE_TEMP: &[u8] = &[0x02];
-const REG_DATA_PSR: &[u8] = &[0xcf, 0x8d];
+const REG_DATA_SOFT_RESET: [u8; 1] = [0x0e];
+const REG_DATA_ACTIVE_TEMP: [u8; 1] = [0x02];
+const REG_DATA_PSR: [u8; 2] = [0xcf, 0x8d];
+
+/// Config register data of the PSR for the 4.2" size, i.e. the 4.17" panel
+const REG_DATA_PSR_MID: [u8; 2] = [0x0f, 0x89];
+
+/// Unrotated size of the 4.17" panel
+const SIZE_4IN17: Size = Size::new(400, 300);
+
+/// Maximum number of chunks which are written in one SPI transaction
+pub const MAX_WRITES_PER_TRANSACTION: usize = 8;
+
+/// Temperature in °C which is used if none is set
+const DEFAULT_TEMPERATURE: i8 = 25;
+
+/// Operating temperature range of the standard Spectra films in °C
+pub const OPERATING_TEMPERATURE: RangeInclusive<i8> = 0..=40;
 
 /// Timeout value when waiting for busy signal
-const TIMEOUT_MS: i32 = 60_000;
+const TIMEOUT_MS: u32 = 60_000;
+
+/// Maximum duration of a single delay in ms, longer delays are split
+const DELAY_SLICE_MS: u32 = 10;
+
+/// Timeout value when waiting for busy signal after the soft reset during init, which
+/// takes a few milliseconds if a panel is fitted
+const INIT_TIMEOUT_MS: u32 = 1_000;
+
+/// Time in ms within which the busy signal has to get active after the soft reset if
+/// `set_detect_missing_panel` is enabled. Without a panel the busy pin is pulled up and never
+/// gets active.
+const RESET_BUSY_TIMEOUT_MS: u32 = 10;
 
 // Sadly we cannot use #[from] more than once.
 // See here for similiar problem: https://stackoverflow.com/questions/37347311/how-is-there-a-conflicting-implementation-of-from-when-using-a-generic-type
+/// Errors of the driver. With the feature `std` it implements `std::error::Error`
+/// (if the errors of the HAL do), e.g. to be used with `anyhow` on a Raspberry Pi.
+/// Some variants only exist with a feature, e.g. `Flash` with `embedded-storage`, so the enum
+/// is non-exhaustive: enabling a feature does not break matches in other crates.
 #[cfg(feature = "std")]
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum Error<SpiError, DcError, RstError> {
     #[error("SPI error: {0}")]
     Spi(#[source] SpiError),
@@ -36,20 +80,58 @@ pub enum Error<SpiError, DcError, RstError> {
     GpioDc(#[source] DcError),
     #[error("Error with GPIO 'RESET': {0}")]
     GpioRst(#[source] RstError),
+    #[error("Error reading GPIO 'BUSY'")]
+    GpioBusy,
     #[error("Timeout while waiting for busy signal")]
     Timeout,
+    #[error("Panel not responding, busy signal does not follow the reset during init")]
+    PanelNotResponding,
+    #[error("The e-paper is still busy with the refresh started by start_update")]
+    Busy,
+    #[error("Size of the frame data does not match the display")]
+    FrameSize,
+    #[error("Temperature of {0} °C is outside of the operating range of the film")]
+    Temperature(i8),
+    #[error("Driver of the panel is missing after a change of its typestate")]
+    DriverMissing,
+    #[cfg(feature = "embedded-storage")]
+    #[error("Error reading flash: {0}")]
+    Flash(NorFlashErrorKind),
+    #[cfg(feature = "embedded-sdmmc")]
+    #[error("Error reading BMP: {0:?}")]
+    Bmp(BmpError),
 }
 
+/// Errors of the driver. With the feature `std` it implements `std::error::Error`.
+/// The enum is non-exhaustive as some variants only exist with a feature.
 #[cfg(not(feature = "std"))]
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error<SpiError, DcError, RstError> {
     Spi(SpiError),
     GpioDc(DcError),
     GpioRst(RstError),
+    GpioBusy,
     Timeout,
+    PanelNotResponding,
+    Busy,
+    FrameSize,
+    Temperature(i8),
+    DriverMissing,
+    #[cfg(feature = "embedded-storage")]
+    Flash(NorFlashErrorKind),
+    #[cfg(feature = "embedded-sdmmc")]
+    Bmp(BmpError),
+}
+
+#[cfg(feature = "embedded-sdmmc")]
+impl<SpiError, DcError, RstError> From<BmpError> for Error<SpiError, DcError, RstError> {
+    fn from(error: BmpError) -> Self {
+        Error::Bmp(error)
+    }
 }
 
-type EpdError<SPI, DC, RST> = Error<
+pub(crate) type EpdError<SPI, DC, RST> = Error<
     <SPI as embedded_hal::spi::ErrorType>::Error,
     <DC as embedded_hal::digital::ErrorType>::Error,
     <RST as embedded_hal::digital::ErrorType>::Error,
@@ -58,7 +140,21 @@ type EpdError<SPI, DC, RST> = Error<
 type EpdResult<STATE, SPI, BUSY, DC, RST, DELAY> =
     Result<Epd<STATE, SPI, BUSY, DC, RST, DELAY>, EpdError<SPI, DC, RST>>;
 
-/// Actual driver for e-paper display
+/// Function which writes data to the e-paper, see `write_batched`
+type WriteFn<'a, SPI, DC, RST> = dyn FnMut(&[u8]) -> Result<(), EpdError<SPI, DC, RST>> + 'a;
+
+/// Driver in the inactive state or the error together with the driver in the active state
+type PowerOffResult<SPI, BUSY, DC, RST, DELAY> = Result<
+    Epd<Inactive, SPI, BUSY, DC, RST, DELAY>,
+    (
+        Epd<Active, SPI, BUSY, DC, RST, DELAY>,
+        EpdError<SPI, DC, RST>,
+    ),
+>;
+
+/// Actual driver for e-paper display. The typestate `STATE` ([`Active`] or [`Inactive`])
+/// follows the power state of the e-paper. The SPI device and the delay are passed to each
+/// call, so they can be shared with other devices between the calls.
 pub struct Epd<STATE: EpdState, SPI, BUSY, DC, RST, DELAY> {
     /// busy pin, active low
     busy: BUSY,
@@ -68,11 +164,52 @@ pub struct Epd<STATE: EpdState, SPI, BUSY, DC, RST, DELAY> {
     rst: RST,
     /// chunk size used for SPI writes (0: no chunks)
     spi_chunk_size: usize,
+    /// number of chunks written in one SPI transaction
+    writes_per_transaction: usize,
+    /// number of times the transfer of a frame is repeated after an SPI error
+    spi_retries: u8,
+    /// hash of the frame that was shown last (`None`: unknown)
+    frame_hash: Option<u32>,
+    /// temperature of the panel in °C
+    temperature: i8,
+    /// temperatures at which refreshes are allowed (`None`: any temperature)
+    temperature_range: Option<RangeInclusive<i8>>,
+    /// called at the start of each phase
+    phase_callback: Option<fn(UpdatePhase)>,
+    /// called after each slice of a delay and while waiting for the busy signal
+    wait_callback: Option<fn()>,
+    /// number of refreshes since construction
+    refreshes: u32,
+    /// time in ms spent waiting for the busy signal since construction
+    busy_ms: u64,
+    /// true while the refresh started by `start_update` may be in progress
+    refreshing: bool,
+    /// unrotated size of the panel (`None`: unknown)
+    dimensions: Option<Size>,
+    /// whether init requires the busy signal to get active after the soft reset
+    detect_missing_panel: bool,
     spi: PhantomData<SPI>,
     delay: PhantomData<DELAY>,
     state: PhantomData<STATE>,
 }
 
+/// Phases of the operation of the e-paper, see `Epd::set_phase_callback`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UpdatePhase {
+    /// Reset and configuration of the controller by `init`
+    Init,
+    /// Transfer of the frame data
+    Transfer,
+    /// Power on of the e-paper before the refresh
+    PowerOn,
+    /// Refresh of the e-paper, which takes several seconds
+    Refresh,
+    /// Power off of the e-paper
+    PowerOff,
+    /// The e-paper is idle, i.e. the previous phase is complete
+    Idle,
+}
+
 // Typestates for epd states (thanks to https://yoric.github.io/post/rust-typestate/ and https://cliffle.com/blog/rust-typestate/)
 pub struct Active; // e-paper is ready to draw something
 pub struct Inactive; // e-paper is powered off
@@ -91,6 +228,7 @@ where
     /// Create a new e-paper driver. You have to call `init` before sending pages to the e-paper via `update`.
     /// `spi_chunk_size` determines the data chunk size for SPI writes, 0 means no chunks.
     /// E.g. Linux has a default buffer size of 4096. So `spi_chunk_size` must be equal to or smaller than 4096.
+    /// Likewise it must not exceed the maximum DMA transfer size of the SPI peripheral, e.g. 255 for the nRF52832.
     pub fn new(
         _spi: &mut SPI,
         busy: BUSY,
@@ -104,6 +242,18 @@ where
             dc,
             rst,
             spi_chunk_size,
+            writes_per_transaction: MAX_WRITES_PER_TRANSACTION,
+            spi_retries: 0,
+            frame_hash: None,
+            temperature: DEFAULT_TEMPERATURE,
+            temperature_range: Some(OPERATING_TEMPERATURE),
+            phase_callback: None,
+            wait_callback: None,
+            refreshes: 0,
+            busy_ms: 0,
+            refreshing: false,
+            dimensions: None,
+            detect_missing_panel: false,
             spi: PhantomData,
             delay: PhantomData,
             state: PhantomData::<Inactive>,
@@ -117,27 +267,54 @@ where
     /// # Errors
     ///
     /// This function will return an error if there is an error
-    /// with the GPIOs or the SPI device.
+    /// with the GPIOs or the SPI device. `Error::PanelNotResponding` is returned
+    /// if the busy signal does not get inactive after the soft reset, e.g. if the panel is
+    /// defective, or with `set_detect_missing_panel` if it does not get active before.
+    ///
+    /// The 4.17" panel needs another configuration than the other sizes, so its size has to be
+    /// set with `set_dimensions` before, which [`Panel`](crate::Panel) does.
     pub fn init(
         mut self,
         spi: &mut SPI,
         delay: &mut DELAY,
     ) -> EpdResult<Active, SPI, BUSY, DC, RST, DELAY> {
-        self.dc.set_high().map_err(Error::GpioDc)?;
-        self.reset(delay)?;
-        self.soft_reset(spi, delay)?;
-        self.send_data(spi, Command::InputTemperature, REG_DATA_INPUT_TEMP)?;
-        self.send_data(spi, Command::ActiveTemperature, REG_DATA_ACTIVE_TEMP)?;
-        self.send_data(spi, Command::Psr, REG_DATA_PSR)?;
-        Ok(Epd {
-            busy: self.busy,
-            dc: self.dc,
-            rst: self.rst,
-            spi_chunk_size: self.spi_chunk_size,
-            spi: PhantomData,
-            delay: PhantomData,
-            state: PhantomData::<Active>,
-        })
+        self.initialize(spi, delay)?;
+        Ok(self.into_state())
+    }
+
+    /// Initializes the e-paper, calls `f` with the active driver and powers the e-paper off
+    /// afterwards, also if the initialization or `f` fails. So the e-paper is not left powered
+    /// on early returns or errors, which would damage it. The driver is always returned in the
+    /// inactive state, together with the result of `f` or the first error:
+    /// ```ignore
+    /// let (epd, result) = epd.with_active(&mut spi, &mut delay, |epd, spi, delay| {
+    ///     epd.update(&display, spi, delay)
+    /// });
+    /// ```
+    /// The power-off is not performed if `f` panics.
+    ///
+    /// # Errors
+    ///
+    /// The result contains an error if there is an error with the GPIOs or the SPI device
+    /// or if `f` returns an error.
+    pub fn with_active<R, E: From<EpdError<SPI, DC, RST>>>(
+        self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+        f: impl FnOnce(
+            &mut Epd<Active, SPI, BUSY, DC, RST, DELAY>,
+            &mut SPI,
+            &mut DELAY,
+        ) -> Result<R, E>,
+    ) -> (Self, Result<R, E>) {
+        let mut epd = self.into_state::<Active>();
+        let result = match epd.initialize(spi, delay) {
+            Ok(()) => f(&mut epd, spi, delay),
+            Err(error) => Err(error.into()),
+        };
+        let powered_off = epd.shut_down(spi, delay);
+        let result = result.and_then(|value| powered_off.map(|()| value).map_err(E::from));
+        (epd.into_state(), result)
     }
 }
 
@@ -150,7 +327,15 @@ where
     DELAY: DelayNs,
 {
     /// Show display on e-paper. This function is blocking until the update
-    /// process is complete.
+    /// process is complete. The planes of a [`Display`](crate::Display) are sent directly
+    /// from its buffers without copies. Only if the display is inverted or shows red as
+    /// black, the planes are converted on the fly in chunks of 64 bytes, which are collected
+    /// in a batch of 512 bytes on the stack, so they are not written one by one.
+    /// The frame is not hashed, so the next `update_if_changed` refreshes in any case.
+    ///
+    /// The driver has no geometry in its type, so the size of `display` is not checked, neither
+    /// at compile time nor at runtime: a display type of another panel is sent as it is.
+    /// [`Panel`](crate::Panel) only accepts the display type of its size at compile time.
     ///
     /// # Errors
     ///
@@ -162,13 +347,352 @@ where
         spi: &mut SPI,
         delay: &mut DELAY,
     ) -> Result<(), EpdError<SPI, DC, RST>> {
-        self.send_data(spi, Command::BufferBlack, display.get_buffer_black())?;
-        self.send_data(spi, Command::BufferRed, display.get_buffer_red())?;
+        self.update_frame(display, None, spi, delay)
+    }
+
+    /// Show display on e-paper only if it differs from the frame shown last by
+    /// `update_if_changed`. The frames are compared by a hash, any other update in between
+    /// invalidates it. Returns true if an update was performed.
+    /// This function is blocking until the update process is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is an error
+    /// with the GPIOs or the SPI device.
+    pub fn update_if_changed(
+        &mut self,
+        display: &impl DisplayBuffer,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<bool, EpdError<SPI, DC, RST>> {
+        let hash = frame_hash(display);
+        if self.frame_hash == Some(hash) {
+            return Ok(false);
+        }
+        self.update_frame(display, Some(hash), spi, delay)?;
+        Ok(true)
+    }
+
+    /// Show a frame whose planes are produced byte by byte, e.g. generated procedurally,
+    /// without the need for a display buffer. Each iterator has to yield a whole plane
+    /// (see `Display::black_plane` for the layout), `size` is the unrotated panel size
+    /// (width: `SIZE_H`, height: `SIZE_V` of the display type). Surplus bytes are not read.
+    /// This function is blocking until the update process is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return `Error::FrameSize` if `size` differs from the size of the
+    /// panel set by `set_dimensions` or if an iterator yields fewer or more bytes than a plane
+    /// has. Iterators whose `size_hint` rules out the size of a plane, e.g. of arrays or
+    /// slices, are rejected before anything is sent. Other iterators can only be checked while
+    /// they are sent: the frame is not refreshed then, but the bytes sent before remain in the
+    /// RAM of the controller until the next update overwrites them. It will return an error if
+    /// there is an error with the GPIOs or the SPI device.
+    pub fn update_from_iters(
+        &mut self,
+        black: impl IntoIterator<Item = u8>,
+        red: impl IntoIterator<Item = u8>,
+        size: Size,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.check_size(size)?;
+        let plane_size = size.width.div_ceil(8) as usize * size.height as usize;
+        let (black, red) = (black.into_iter(), red.into_iter());
+        if !may_yield(&black, plane_size) || !may_yield(&red, plane_size) {
+            return Err(Error::FrameSize);
+        }
+        self.begin_frame()?;
+        self.send_command(spi, Command::BufferBlack)?;
+        self.write_plane(spi, black, plane_size)?;
+        self.send_command(spi, Command::BufferRed)?;
+        self.write_plane(spi, red, plane_size)?;
+        self.power_on(spi, delay)?;
+        self.display_refresh(spi, delay)
+    }
+
+    /// Show a frame from raw planes, e.g. produced by a separate tool, without the need
+    /// for a display buffer. See `Display::black_plane` for the layout of the planes,
+    /// `size` is the unrotated panel size (width: `SIZE_H`, height: `SIZE_V` of the display type).
+    /// The planes are written as they are, so with SPI peripherals which use DMA they may have
+    /// to be in RAM (use `update_from_iters` for planes in flash then).
+    /// This function is blocking until the update process is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return `Error::FrameSize` if `size` differs from the size of the
+    /// panel set by `set_dimensions` or if the length of a plane does not match `size`, nothing
+    /// is sent then. It will return an error if there is an error with the GPIOs or the SPI
+    /// device.
+    pub fn update_raw(
+        &mut self,
+        black: &[u8],
+        red: &[u8],
+        size: Size,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.check_size(size)?;
+        let plane_size = size.width.div_ceil(8) as usize * size.height as usize;
+        if black.len() != plane_size || red.len() != plane_size {
+            return Err(Error::FrameSize);
+        }
+        self.begin_frame()?;
+        self.send_data(spi, Command::BufferBlack, black)?;
+        self.send_data(spi, Command::BufferRed, red)?;
+        self.power_on(spi, delay)?;
+        self.display_refresh(spi, delay)
+    }
+
+    /// Show a frame whose pixels are evaluated on the fly by `color(x, y)` in transmission
+    /// order, without the need for a display buffer. The coordinates are unrotated panel
+    /// coordinates with `size` being the unrotated panel size (width: `SIZE_H`, height: `SIZE_V`
+    /// of the display type). The planes are sent one after another, so `color` is called
+    /// twice for each pixel. This function is blocking until the update process is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is an error
+    /// with the GPIOs or the SPI device.
+    pub fn update_from_fn(
+        &mut self,
+        size: Size,
+        color: impl Fn(u32, u32) -> TriColor,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        let black = fn_plane(size, &color, TriColor::Black);
+        let red = fn_plane(size, &color, TriColor::Red);
+        self.update_from_iters(black, red, size, spi, delay)
+    }
+
+    /// Drives the e-paper through `cycles` pairs of an all black and an all white frame to
+    /// reduce ghosting, e.g. after the same frame was shown for days. Show the final frame with
+    /// `update` afterwards. `size` is the unrotated panel size (width: `SIZE_H`, height:
+    /// `SIZE_V` of the display type). Each frame is a full refresh, so this takes
+    /// `2 * cycles` refresh times. This function is blocking until the last refresh is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is an error
+    /// with the GPIOs or the SPI device.
+    pub fn deghost(
+        &mut self,
+        size: Size,
+        cycles: u32,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        for _ in 0..cycles {
+            self.update_from_fn(size, |_, _| TriColor::Black, spi, delay)?;
+            self.update_from_fn(size, |_, _| TriColor::White, spi, delay)?;
+        }
+        Ok(())
+    }
+
+    /// Show a frame whose planes are stored in a flash, e.g. an external SPI flash, without
+    /// the need for a display buffer. `size` is the unrotated panel size (width: `SIZE_H`,
+    /// height: `SIZE_V` of the display type), which determines the size of the planes (see
+    /// `Display::black_plane` for the layout). Each plane is read in small chunks starting
+    /// at the given offset. The offsets must be aligned to `F::READ_SIZE`, which must be a
+    /// divisor of 64. This function is blocking until the update process is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return `Error::FrameSize` if `size` differs from the size of the
+    /// panel set by `set_dimensions`. It will return `Error::Flash`
+    /// with `NorFlashErrorKind::NotAligned` or `OutOfBounds` if `F::READ_SIZE` is not a
+    /// divisor of 64, an offset is not aligned or a plane exceeds the capacity of the flash.
+    /// Nothing is sent in these cases. It will return an error if there is an error with the
+    /// GPIOs, the SPI device or the flash.
+    #[cfg(feature = "embedded-storage")]
+    pub fn update_from_flash<F: ReadNorFlash>(
+        &mut self,
+        flash: &mut F,
+        black_offset: u32,
+        red_offset: u32,
+        size: Size,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.check_size(size)?;
+        let plane_size = size.width.div_ceil(8) as usize * size.height as usize;
+        check_flash_plane(flash, black_offset, plane_size)?;
+        check_flash_plane(flash, red_offset, plane_size)?;
+        self.begin_frame()?;
+        self.send_command(spi, Command::BufferBlack)?;
+        self.write_from_flash(flash, black_offset, plane_size, spi)?;
+        self.send_command(spi, Command::BufferRed)?;
+        self.write_from_flash(flash, red_offset, plane_size, spi)?;
+        self.power_on(spi, delay)?;
+        self.display_refresh(spi, delay)
+    }
+
+    /// Show a BMP file, e.g. a photo on an SD card, without the need for a display buffer.
+    /// The file is read row by row and converted to the colors of `palette` with `method`,
+    /// it must be an uncompressed BMP with 24 or 32 bits per pixel. It is placed at the top
+    /// left corner of the unrotated panel with `size` (width: `SIZE_H`, height: `SIZE_V` of the
+    /// display type), pixels outside the image are white. The planes are sent one after another,
+    /// so the file is read twice. `WIDTH` is the maximum width for Floyd–Steinberg dithering
+    /// (see [`DitherTarget`](crate::DitherTarget)), use the panel width.
+    /// This function is blocking until the update process is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is an error
+    /// with the GPIOs, the SPI device or the file.
+    #[cfg(feature = "embedded-sdmmc")]
+    pub fn update_from_bmp<const WIDTH: usize>(
+        &mut self,
+        bmp: &mut impl BmpSource,
+        size: Size,
+        palette: DitherPalette,
+        method: DitherMethod,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        let header = BmpHeader::read(bmp)?;
+        self.begin_frame()?;
+        self.send_command(spi, Command::BufferBlack)?;
+        self.write_batched(spi, |write| {
+            header.write_plane::<WIDTH, _>(bmp, size, TriColor::Black, (palette, method), write)
+        })?;
+        self.send_command(spi, Command::BufferRed)?;
+        self.write_batched(spi, |write| {
+            header.write_plane::<WIDTH, _>(bmp, size, TriColor::Red, (palette, method), write)
+        })?;
+        self.power_on(spi, delay)?;
+        self.display_refresh(spi, delay)?;
+        Ok(())
+    }
+
+    /// Show a frame which is rendered strip by strip, for devices which cannot hold a full
+    /// display buffer. `draw` is called for each strip and has to draw the frame (at least the
+    /// part within [`Strip::area`]), pixels outside the strip are discarded. The planes are sent
+    /// one after another, so `draw` is called twice for each strip and must draw the same
+    /// content each time. This function is blocking until the update process is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is an error
+    /// with the GPIOs or the SPI device.
+    pub fn update_strips<const SIZE_V: u32, const SIZE_H: u32, const STRIP_SIZE: usize>(
+        &mut self,
+        strip: &mut Strip<SIZE_V, SIZE_H, STRIP_SIZE>,
+        mut draw: impl FnMut(&mut Strip<SIZE_V, SIZE_H, STRIP_SIZE>),
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.begin_frame()?;
+        self.send_command(spi, Command::BufferBlack)?;
+        self.write_batched(spi, |write| {
+            for first_row in Strip::<SIZE_V, SIZE_H, STRIP_SIZE>::first_rows() {
+                strip.start(first_row);
+                draw(strip);
+                write(strip.black_plane())?;
+            }
+            Ok(())
+        })?;
+        self.send_command(spi, Command::BufferRed)?;
+        self.write_batched(spi, |write| {
+            for first_row in Strip::<SIZE_V, SIZE_H, STRIP_SIZE>::first_rows() {
+                strip.start(first_row);
+                draw(strip);
+                write(strip.chromatic_plane())?;
+            }
+            Ok(())
+        })?;
+        self.power_on(spi, delay)?;
+        self.display_refresh(spi, delay)?;
+        Ok(())
+    }
+
+    /// Show display on e-paper with the frame data sent by `transfer`, e.g. with the DMA of the
+    /// SPI peripheral. The driver sends the commands via `spi` and sets the DC pin, then it
+    /// calls `transfer` for the data of each plane; `transfer` has to assert the chip select
+    /// and must return when the data is sent. With a [`Display`](crate::Display) `transfer`
+    /// gets the whole planes of the buffer without copies, unless it is inverted or shows
+    /// red as black, then small chunks. `spi_chunk_size` is not applied. This function is
+    /// blocking until the update process is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is an error
+    /// with the GPIOs or the SPI device or if `transfer` fails.
+    pub fn update_with_transfer(
+        &mut self,
+        display: &impl DisplayBuffer,
+        mut transfer: impl FnMut(&[u8]) -> Result<(), SPI::Error>,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.begin_frame()?;
+        self.send_command(spi, Command::BufferBlack)?;
+        display.write_black(|data| transfer(data).map_err(Error::Spi))?;
+        self.send_command(spi, Command::BufferRed)?;
+        display.write_red(|data| transfer(data).map_err(Error::Spi))?;
         self.power_on(spi, delay)?;
         self.display_refresh(spi, delay)?;
         Ok(())
     }
 
+    /// Start to show display on e-paper without waiting for the refresh, which takes several
+    /// seconds. Only sending the frame and powering on the e-paper is blocking. The busy pin
+    /// is low until the refresh is complete, so the end can be detected with an interrupt on its
+    /// rising edge (see `busy_pin_mut`) or by polling `is_busy`, e.g. to do other work or to
+    /// sleep in the meantime. Until the refresh is complete, the other functions which send
+    /// to the e-paper return `Error::Busy` instead of interrupting it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is an error
+    /// with the GPIOs or the SPI device.
+    pub fn start_update(
+        &mut self,
+        display: &impl DisplayBuffer,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.begin_frame()?;
+        self.send_frame(display, spi, delay)?;
+        self.power_on(spi, delay)?;
+        self.send_register(spi, Command::Refresh, [0x0])?;
+        self.refreshes = self.refreshes.wrapping_add(1);
+        self.refreshing = true;
+        Ok(())
+    }
+
+    /// Returns true while the e-paper is busy, e.g. with the refresh after `start_update`
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the busy pin cannot be read.
+    pub fn is_busy(&mut self) -> Result<bool, BUSY::Error> {
+        self.busy.is_low()
+    }
+
+    /// Wait until the e-paper is not busy anymore, e.g. after `start_update`, calling
+    /// `idle` with the busy pin as long as it is busy. So the waiting can be customized,
+    /// e.g. to sleep until an interrupt of the busy pin (clearing it in `idle`) or to
+    /// feed a watchdog. There is no timeout.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the busy pin cannot be read.
+    pub fn wait_while_busy(&mut self, mut idle: impl FnMut(&mut BUSY)) -> Result<(), BUSY::Error> {
+        while self.busy.is_low()? {
+            idle(&mut self.busy);
+        }
+        self.refreshing = false;
+        self.enter_phase(UpdatePhase::Idle);
+        Ok(())
+    }
+
+    /// Returns the busy pin, e.g. to configure its interrupt or clear the pending interrupt
+    pub fn busy_pin_mut(&mut self) -> &mut BUSY {
+        &mut self.busy
+    }
+
     /// Power off the e-paper. This function is blocking until the e-paper
     /// is powered off. The return value is an e-paper driver in
     /// the inactive state. You have to call `init` again before
@@ -179,24 +703,31 @@ where
     /// This function will return an error if there is an error
     /// with the GPIOs or the SPI device.
     pub fn power_off(
-        mut self,
+        self,
         spi: &mut SPI,
         delay: &mut DELAY,
     ) -> EpdResult<Inactive, SPI, BUSY, DC, RST, DELAY> {
-        self.send_data(spi, Command::PowerOff, &[0x0])?;
-        self.wait_busy(delay)?;
-        self.dc.set_low().map_err(Error::GpioDc)?;
-        delay.delay_ms(150);
-        self.rst.set_low().map_err(Error::GpioRst)?;
-        Ok(Epd {
-            busy: self.busy,
-            dc: self.dc,
-            rst: self.rst,
-            spi_chunk_size: self.spi_chunk_size,
-            spi: PhantomData,
-            delay: PhantomData,
-            state: PhantomData::<Inactive>,
-        })
+        self.try_power_off(spi, delay)
+            .map_err(|(_epd, error)| error)
+    }
+
+    /// Power off the e-paper like `power_off`, but return the driver in the active state
+    /// together with the error, as the e-paper may still be powered, so the power-off can
+    /// be retried.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is an error
+    /// with the GPIOs or the SPI device.
+    pub fn try_power_off(
+        mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> PowerOffResult<SPI, BUSY, DC, RST, DELAY> {
+        match self.shut_down(spi, delay) {
+            Ok(()) => Ok(self.into_state()),
+            Err(error) => Err((self, error)),
+        }
     }
 }
 
@@ -209,19 +740,270 @@ where
     RST: OutputPin,
     DELAY: DelayNs,
 {
+    /// Converts the driver into another state
+    pub(crate) fn into_state<NEW: EpdState>(self) -> Epd<NEW, SPI, BUSY, DC, RST, DELAY> {
+        Epd {
+            busy: self.busy,
+            dc: self.dc,
+            rst: self.rst,
+            spi_chunk_size: self.spi_chunk_size,
+            writes_per_transaction: self.writes_per_transaction,
+            spi_retries: self.spi_retries,
+            frame_hash: self.frame_hash,
+            temperature: self.temperature,
+            temperature_range: self.temperature_range,
+            phase_callback: self.phase_callback,
+            wait_callback: self.wait_callback,
+            refreshes: self.refreshes,
+            busy_ms: self.busy_ms,
+            refreshing: self.refreshing,
+            dimensions: self.dimensions,
+            detect_missing_panel: self.detect_missing_panel,
+            spi: PhantomData,
+            delay: PhantomData,
+            state: PhantomData::<NEW>,
+        }
+    }
+
+    /// Sets the number of chunks of `spi_chunk_size` bytes which are written in one SPI
+    /// transaction, limited to 1..=[`MAX_WRITES_PER_TRANSACTION`], which is the default.
+    /// Combining the chunks saves the overhead of a transaction per chunk, e.g. toggling the
+    /// chip select, while each write stays within the DMA limit of the SPI peripheral.
+    /// With `SpidevDevice` of `linux-embedded-hal` the size of a whole transaction is limited
+    /// by the buffer size of spidev, so it has to be set to 1 there.
+    pub fn set_writes_per_transaction(&mut self, writes: usize) {
+        self.writes_per_transaction = writes.clamp(1, MAX_WRITES_PER_TRANSACTION);
+    }
+
+    /// Sets how often the transfer of a frame is repeated after an SPI error, e.g. caused by
+    /// glitches on long cables. The default is 0. Before each retry the controller is reset
+    /// and initialized again, so it does not keep partial frame data. Retries are done for
+    /// frames from a display buffer (`update`, `update_if_changed` and `start_update`) and only
+    /// before the power on, as the controller must not be reset while the film is powered.
+    pub fn set_spi_retries(&mut self, retries: u8) {
+        self.spi_retries = retries;
+    }
+
+    /// Sets a function which is called at the start of each phase of the operation of the
+    /// e-paper (init, transfer, power on, refresh, power off) and with `UpdatePhase::Idle`
+    /// when the e-paper gets idle after init, refreshes and power off. So the phases can be
+    /// timed with a timer of the application, e.g. to estimate the consumed energy. As the
+    /// function cannot capture variables, it has to store the timestamps in a static.
+    pub fn set_phase_callback(&mut self, callback: Option<fn(UpdatePhase)>) {
+        self.phase_callback = callback;
+    }
+
+    /// Sets a function which is called at least every 10 ms while the driver waits, i.e.
+    /// during the delays of init and power off and while waiting for the busy signal, e.g.
+    /// to feed a watchdog with a short window. Longer delays are split into slices of 10 ms.
+    pub fn set_wait_callback(&mut self, callback: Option<fn()>) {
+        self.wait_callback = callback;
+    }
+
+    /// Sets the temperature of the panel in °C, e.g. measured by a sensor next to it, which
+    /// adapts the waveform of the refresh. It is sent to the e-paper by the next `init`.
+    /// The default is 25 °C.
+    pub fn set_temperature(&mut self, temperature: i8) {
+        self.temperature = temperature;
+    }
+
+    /// Returns the temperature of the panel in °C
+    #[must_use]
+    pub fn temperature(&self) -> i8 {
+        self.temperature
+    }
+
+    /// Sets the temperatures at which the e-paper is refreshed. Outside of the range refreshes
+    /// fail with `Error::Temperature` before any data is sent, as they may damage the film or
+    /// leave ghosting. The default is [`OPERATING_TEMPERATURE`] of the standard films from the
+    /// datasheet, e.g. a film for low temperatures needs another range. `None` allows
+    /// refreshes at any temperature.
+    pub fn set_temperature_range(&mut self, range: Option<RangeInclusive<i8>>) {
+        self.temperature_range = range;
+    }
+
+    /// Sets the unrotated size of the panel (width: `SIZE_H`, height: `SIZE_V` of the display
+    /// type), which is returned by `dimensions`. Only the 4.17" panel is configured differently
+    /// by `init`, the other sizes do not depend on it.
+    pub fn set_dimensions(&mut self, size: Size) {
+        self.dimensions = Some(size);
+    }
+
+    /// Sets whether `init` fails with `Error::PanelNotResponding` if the busy signal does not
+    /// get active within 10 ms after the soft reset, as without a panel the busy pin is pulled
+    /// up. The default is `false` like in the sequence of the vendor, which only waits until
+    /// the busy signal is inactive. Only enable it if the busy signal of the panel is known to
+    /// last long enough to be seen by polling every millisecond.
+    pub fn set_detect_missing_panel(&mut self, detect: bool) {
+        self.detect_missing_panel = detect;
+    }
+
+    /// Returns the unrotated size of the panel, e.g. for layout decisions or logging in code
+    /// which is generic over the driver. It is known if it was set with `set_dimensions` or by
+    /// [`Panel`](crate::Panel), otherwise `None` is returned. The sizes passed with a frame,
+    /// e.g. to `update_raw`, are checked against it but do not set it.
+    #[must_use]
+    pub fn dimensions(&self) -> Option<Size> {
+        self.dimensions
+    }
+
+    /// Returns the number of refreshes since the driver was created, e.g. to monitor the wear
+    /// of the panel
+    #[must_use]
+    pub fn refresh_count(&self) -> u32 {
+        self.refreshes
+    }
+
+    /// Returns the time in ms the driver waited for the busy signal since it was created,
+    /// i.e. the time the e-paper was busy with refreshes, power on and off. The time is
+    /// measured with the delay, so it is a lower bound. Waiting with `wait_while_busy`
+    /// is not included.
+    #[must_use]
+    pub fn busy_time_ms(&self) -> u64 {
+        self.busy_ms
+    }
+
+    /// Initializes the e-paper (see `init`) without changing the state
+    pub(crate) fn initialize(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.enter_phase(UpdatePhase::Init);
+        self.dc.set_high().map_err(Error::GpioDc)?;
+        self.reset(delay)?;
+        self.soft_reset(spi, delay)?;
+        let temperature = [self.temperature.cast_unsigned()];
+        self.send_register(spi, Command::InputTemperature, temperature)?;
+        self.send_register(spi, Command::ActiveTemperature, REG_DATA_ACTIVE_TEMP)?;
+        let psr = if self.dimensions == Some(SIZE_4IN17) {
+            REG_DATA_PSR_MID
+        } else {
+            REG_DATA_PSR
+        };
+        self.send_register(spi, Command::Psr, psr)?;
+        self.enter_phase(UpdatePhase::Idle);
+        Ok(())
+    }
+
+    /// Powers off the e-paper (see `power_off`) without changing the state
+    pub(crate) fn shut_down(
+        &mut self,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.send_register(spi, Command::PowerOff, [0x0])?;
+        self.wait_busy(delay)?;
+        self.dc.set_low().map_err(Error::GpioDc)?;
+        self.pause(delay, 150);
+        self.rst.set_low().map_err(Error::GpioRst)?;
+        self.enter_phase(UpdatePhase::Idle);
+        Ok(())
+    }
+
+    /// Shows `display` like `update` and stores its hash for `update_if_changed`, e.g. for
+    /// the maintenance refreshes of [`RefreshPolicy`](crate::RefreshPolicy)
+    pub(crate) fn update_hashed(
+        &mut self,
+        display: &impl DisplayBuffer,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.update_frame(display, Some(frame_hash(display)), spi, delay)
+    }
+
+    /// Shows `display` and stores `hash` as the hash of the shown frame
+    fn update_frame(
+        &mut self,
+        display: &impl DisplayBuffer,
+        hash: Option<u32>,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.begin_frame()?;
+        self.send_frame(display, spi, delay)?;
+        self.power_on(spi, delay)?;
+        self.display_refresh(spi, delay)?;
+        self.frame_hash = hash;
+        Ok(())
+    }
+
+    /// Returns `Error::FrameSize` if `size` differs from the size of the panel set by
+    /// `set_dimensions`
+    fn check_size(&self, size: Size) -> Result<(), EpdError<SPI, DC, RST>> {
+        if self.dimensions.is_some_and(|dimensions| dimensions != size) {
+            return Err(Error::FrameSize);
+        }
+        Ok(())
+    }
+
+    /// Checks whether a frame may be sent and invalidates the hash of the frame shown last,
+    /// as the controller gets new frame data. A frame rejected with `Error::Temperature` or
+    /// `Error::Busy` leaves the controller and the hash untouched.
+    fn begin_frame(&mut self) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.check_temperature()?;
+        self.check_idle()?;
+        self.frame_hash = None;
+        Ok(())
+    }
+
+    /// Sends the planes of `display`, after an SPI error the controller is initialized again
+    /// and the transfer is repeated up to `spi_retries` times
+    fn send_frame(
+        &mut self,
+        display: &impl DisplayBuffer,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        let mut retries = self.spi_retries;
+        loop {
+            match self.send_planes(display, spi) {
+                Err(Error::Spi(_)) if retries > 0 => {
+                    retries -= 1;
+                    self.initialize(spi, delay)?;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn send_planes(
+        &mut self,
+        display: &impl DisplayBuffer,
+        spi: &mut SPI,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.send_command(spi, Command::BufferBlack)?;
+        self.write_batched(spi, |write| display.write_black(write))?;
+        self.send_command(spi, Command::BufferRed)?;
+        self.write_batched(spi, |write| display.write_red(write))?;
+        Ok(())
+    }
+
     fn reset(&mut self, delay: &mut DELAY) -> Result<(), EpdError<SPI, DC, RST>> {
-        delay.delay_ms(1);
+        self.pause(delay, 1);
         self.rst.set_high().map_err(Error::GpioRst)?;
-        delay.delay_ms(5);
+        self.pause(delay, 5);
         self.rst.set_low().map_err(Error::GpioRst)?;
-        delay.delay_ms(10);
+        self.pause(delay, 10);
         self.rst.set_high().map_err(Error::GpioRst)?;
-        delay.delay_ms(5);
+        self.pause(delay, 5);
         Ok(())
     }
 
+    /// Returns `Error::Temperature` if the temperature is outside of the allowed range. It is
+    /// checked before a frame is sent, so a rejected frame leaves the controller untouched.
+    fn check_temperature(&self) -> Result<(), EpdError<SPI, DC, RST>> {
+        match &self.temperature_range {
+            Some(range) if !range.contains(&self.temperature) => {
+                Err(Error::Temperature(self.temperature))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Powers on the e-paper before a refresh
     fn power_on(&mut self, spi: &mut SPI, delay: &mut DELAY) -> Result<(), EpdError<SPI, DC, RST>> {
-        self.send_data(spi, Command::PowerOn, &[0x0])?;
+        self.send_register(spi, Command::PowerOn, [0x0])?;
         self.wait_busy(delay)?;
         Ok(())
     }
@@ -232,32 +1014,177 @@ where
         cmd: Command,
         data: &[u8],
     ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.send_command(spi, cmd)?;
+        self.write(spi, data)?;
+        Ok(())
+    }
+
+    /// Sends a command with register data. The data is passed by value, so it is on the stack,
+    /// as SPI peripherals with DMA (e.g. the `EasyDMA` of the nRF52) cannot read constants from flash.
+    fn send_register<const N: usize>(
+        &mut self,
+        spi: &mut SPI,
+        cmd: Command,
+        data: [u8; N],
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.send_data(spi, cmd, &data)
+    }
+
+    /// Delays for `ms` in slices of up to `DELAY_SLICE_MS`, calling the wait callback after each
+    fn pause(&self, delay: &mut DELAY, ms: u32) {
+        let mut remaining = ms;
+        while remaining > 0 {
+            let slice = remaining.min(DELAY_SLICE_MS);
+            delay.delay_ms(slice);
+            remaining -= slice;
+            if let Some(callback) = self.wait_callback {
+                callback();
+            }
+        }
+    }
+
+    fn enter_phase(&self, phase: UpdatePhase) {
+        if let Some(callback) = self.phase_callback {
+            callback(phase);
+        }
+    }
+
+    /// Sends the command byte and switches to data mode afterwards. Returns `Error::Busy`
+    /// if the refresh started by `start_update` is not complete yet.
+    fn send_command(&mut self, spi: &mut SPI, cmd: Command) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.check_idle()?;
+        match cmd {
+            Command::BufferBlack => self.enter_phase(UpdatePhase::Transfer),
+            Command::PowerOn => self.enter_phase(UpdatePhase::PowerOn),
+            Command::Refresh => self.enter_phase(UpdatePhase::Refresh),
+            Command::PowerOff => self.enter_phase(UpdatePhase::PowerOff),
+            _ => (),
+        }
         self.dc.set_low().map_err(Error::GpioDc)?;
         self.write(spi, &[cmd as u8])?;
         self.dc.set_high().map_err(Error::GpioDc)?;
-        self.write(spi, data)?;
         Ok(())
     }
 
-    fn write(&mut self, spi: &mut SPI, data: &[u8]) -> Result<(), EpdError<SPI, DC, RST>> {
-        if self.spi_chunk_size > 0 {
-            for chunk in data.chunks(self.spi_chunk_size) {
-                spi.write(chunk).map_err(Error::Spi)?;
+    /// Returns `Error::Busy` if the refresh started by `start_update` is not complete yet
+    fn check_idle(&mut self) -> Result<(), EpdError<SPI, DC, RST>> {
+        if self.refreshing {
+            if self.busy.is_low().map_err(|_| Error::GpioBusy)? {
+                return Err(Error::Busy);
             }
-        } else {
-            spi.write(data).map_err(Error::Spi)?;
+            self.refreshing = false;
+        }
+        Ok(())
+    }
+
+    /// Reads `size` bytes from `flash` starting at `offset` and writes them in chunks
+    #[cfg(feature = "embedded-storage")]
+    fn write_from_flash<F: ReadNorFlash>(
+        &mut self,
+        flash: &mut F,
+        mut offset: u32,
+        size: usize,
+        spi: &mut SPI,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        self.write_batched(spi, |write| {
+            let mut chunk = [0; CHUNK_SIZE];
+            let mut remaining = size;
+            while remaining > 0 {
+                let len = remaining.min(CHUNK_SIZE);
+                // reads must be a multiple of the read size, surplus bytes are ignored
+                let read_len = len
+                    .checked_next_multiple_of(F::READ_SIZE)
+                    .unwrap_or(len)
+                    .min(CHUNK_SIZE);
+                let read = chunk.get_mut(..read_len).ok_or(Error::FrameSize)?;
+                flash
+                    .read(offset, read)
+                    .map_err(|e| Error::Flash(e.kind()))?;
+                write(chunk.get(..len).ok_or(Error::FrameSize)?)?;
+                #[allow(clippy::cast_possible_truncation)]
+                {
+                    offset = offset.wrapping_add(len as u32);
+                }
+                remaining -= len;
+            }
+            Ok(())
+        })
+    }
+
+    /// Writes the first `plane_size` bytes of `plane`, returns `Error::FrameSize` if it
+    /// has fewer or more bytes
+    fn write_plane(
+        &mut self,
+        spi: &mut SPI,
+        plane: impl IntoIterator<Item = u8>,
+        plane_size: usize,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        let mut plane = plane.into_iter();
+        let mut len = 0;
+        let bytes = plane.by_ref().take(plane_size).inspect(|_| len += 1);
+        self.write_batched(spi, |write| write_chunked(bytes, write))?;
+        if len != plane_size || plane.next().is_some() {
+            return Err(Error::FrameSize);
         }
         Ok(())
     }
 
+    /// Writes the data which `source` passes to `write`. Small writes, e.g. the converted
+    /// chunks of 64 bytes, are collected in a [`Batch`], so they are not written in a
+    /// transaction each.
+    fn write_batched(
+        &mut self,
+        spi: &mut SPI,
+        source: impl FnOnce(&mut WriteFn<'_, SPI, DC, RST>) -> Result<(), EpdError<SPI, DC, RST>>,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        let mut batch = Batch::new();
+        source(&mut |data: &[u8]| batch.push(data, |bytes| self.write(spi, bytes)))?;
+        batch.flush(|bytes| self.write(spi, bytes))
+    }
+
+    /// Writes `data` in chunks of `spi_chunk_size`, up to `writes_per_transaction` chunks
+    /// in one SPI transaction
+    fn write(&mut self, spi: &mut SPI, data: &[u8]) -> Result<(), EpdError<SPI, DC, RST>> {
+        if self.spi_chunk_size == 0 {
+            return spi.write(data).map_err(Error::Spi);
+        }
+        let mut chunks = data.chunks(self.spi_chunk_size);
+        loop {
+            let mut operations: [Operation<'_, u8>; MAX_WRITES_PER_TRANSACTION] =
+                core::array::from_fn(|_| Operation::Write(&[]));
+            let mut len = 0;
+            for (operation, chunk) in operations
+                .iter_mut()
+                .take(self.writes_per_transaction)
+                .zip(&mut chunks)
+            {
+                *operation = Operation::Write(chunk);
+                len += 1;
+            }
+            match operations.get_mut(..len) {
+                None | Some([]) => return Ok(()),
+                Some([Operation::Write(chunk)]) => spi.write(chunk),
+                Some(operations) => spi.transaction(operations),
+            }
+            .map_err(Error::Spi)?;
+        }
+    }
+
+    /// Resets the COG by software, returns `Error::PanelNotResponding` if the busy signal
+    /// stays active, or with `detect_missing_panel` if it does not get active
     fn soft_reset(
         &mut self,
         spi: &mut SPI,
         delay: &mut DELAY,
     ) -> Result<(), EpdError<SPI, DC, RST>> {
-        self.send_data(spi, Command::Psr, REG_DATA_SOFT_RESET)?;
-        self.wait_busy(delay)?;
-        Ok(())
+        self.send_register(spi, Command::Psr, REG_DATA_SOFT_RESET)?;
+        if self.detect_missing_panel {
+            self.wait_busy_active(delay, RESET_BUSY_TIMEOUT_MS)?;
+        }
+        match self.wait_busy_for(delay, INIT_TIMEOUT_MS) {
+            Err(Error::Timeout) => Err(Error::PanelNotResponding),
+            result => result,
+        }
     }
 
     fn display_refresh(
@@ -265,23 +1192,192 @@ where
         spi: &mut SPI,
         delay: &mut DELAY,
     ) -> Result<(), EpdError<SPI, DC, RST>> {
-        self.send_data(spi, Command::Refresh, &[0x0])?;
+        self.send_register(spi, Command::Refresh, [0x0])?;
+        self.refreshes = self.refreshes.wrapping_add(1);
+        self.wait_busy(delay)?;
+        self.enter_phase(UpdatePhase::Idle);
+        Ok(())
+    }
+
+    /// Waits until the e-paper is not busy anymore, e.g. after `start_update`, and enters the
+    /// idle phase. Returns `Error::Timeout` after 60 s.
+    pub(crate) fn wait_until_idle(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
         self.wait_busy(delay)?;
+        self.enter_phase(UpdatePhase::Idle);
         Ok(())
     }
 
+    /// Waits until the e-paper is not busy anymore, returns `Error::Timeout` after 60 s
     fn wait_busy(&mut self, delay: &mut DELAY) -> Result<(), EpdError<SPI, DC, RST>> {
-        let delay_ms = 1;
-        let mut timeout = TIMEOUT_MS;
-        while self.busy.is_low().unwrap() && timeout > 0 {
-            delay.delay_ms(delay_ms);
-            timeout -= i32::try_from(delay_ms).unwrap();
+        self.wait_busy_for(delay, TIMEOUT_MS)
+    }
+
+    /// Waits until the busy signal gets active, returns `Error::PanelNotResponding` after
+    /// `timeout_ms` and `Error::GpioBusy` if the busy pin cannot be read.
+    fn wait_busy_active(
+        &mut self,
+        delay: &mut DELAY,
+        timeout_ms: u32,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        let mut waited_ms = 0;
+        while !self.busy.is_low().map_err(|_| Error::GpioBusy)? {
+            if waited_ms >= timeout_ms {
+                return Err(Error::PanelNotResponding);
+            }
+            self.pause(delay, 1);
+            waited_ms += 1;
         }
-        if timeout <= 0 {
-            Err(Error::Timeout)
-        } else {
-            Ok(())
+        Ok(())
+    }
+
+    /// Waits until the e-paper is not busy anymore, returns `Error::Timeout` after `timeout_ms`
+    /// and `Error::GpioBusy` if the busy pin cannot be read.
+    fn wait_busy_for(
+        &mut self,
+        delay: &mut DELAY,
+        timeout_ms: u32,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        let mut waited_ms = 0;
+        let result = loop {
+            let Ok(busy) = self.busy.is_low() else {
+                break Err(Error::GpioBusy);
+            };
+            if !busy {
+                self.refreshing = false;
+                break Ok(());
+            }
+            if waited_ms >= timeout_ms {
+                break Err(Error::Timeout);
+            }
+            self.pause(delay, 1);
+            waited_ms += 1;
+        };
+        self.busy_ms += u64::from(waited_ms);
+        result
+    }
+}
+
+/// Returns false if the `size_hint` of `iter` rules out that it yields `len` items
+fn may_yield(iter: &impl Iterator, len: usize) -> bool {
+    let (lower, upper) = iter.size_hint();
+    lower <= len && upper.is_none_or(|upper| upper >= len)
+}
+
+/// Returns the bytes of the plane in which the bits of the pixels with color `set` are set
+fn fn_plane(
+    size: Size,
+    color: &impl Fn(u32, u32) -> TriColor,
+    set: TriColor,
+) -> impl Iterator<Item = u8> + '_ {
+    (0..size.height).flat_map(move |y| {
+        (0..size.width.div_ceil(8)).map(move |column| {
+            (0..8).fold(0, |byte, bit| {
+                let x = column * 8 + bit;
+                (byte << 1) | u8::from(x < size.width && color(x, y) == set)
+            })
+        })
+    })
+}
+
+/// Checks that a plane of `plane_size` bytes at `offset` can be read from `flash` in chunks
+/// of `CHUNK_SIZE`, including the surplus bytes up to the next multiple of the read size.
+/// Each chunk has to be a multiple of the read size, so the offsets of the following reads
+/// stay aligned.
+#[cfg(feature = "embedded-storage")]
+fn check_flash_plane<F: ReadNorFlash, SpiError, DcError, RstError>(
+    flash: &F,
+    offset: u32,
+    plane_size: usize,
+) -> Result<(), Error<SpiError, DcError, RstError>> {
+    let offset = offset as usize;
+    if F::READ_SIZE == 0
+        || !CHUNK_SIZE.is_multiple_of(F::READ_SIZE)
+        || !offset.is_multiple_of(F::READ_SIZE)
+    {
+        return Err(Error::Flash(NorFlashErrorKind::NotAligned));
+    }
+    let read_size = plane_size
+        .checked_next_multiple_of(F::READ_SIZE)
+        .unwrap_or(plane_size);
+    match offset.checked_add(read_size) {
+        Some(end) if end <= flash.capacity() => Ok(()),
+        _ => Err(Error::Flash(NorFlashErrorKind::OutOfBounds)),
+    }
+}
+
+/// Calculates the 32 bit FNV-1a hash of both planes of the display buffer
+fn frame_hash(display: &impl DisplayBuffer) -> u32 {
+    const FNV_OFFSET_BASIS: u32 = 0x811c_9dc5;
+    const FNV_PRIME: u32 = 0x0100_0193;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut add = |data: &[u8]| {
+        for byte in data {
+            hash = (hash ^ u32::from(*byte)).wrapping_mul(FNV_PRIME);
+        }
+        Ok::<(), core::convert::Infallible>(())
+    };
+    let _ = display.write_black(&mut add);
+    let _ = display.write_red(&mut add);
+    hash
+}
+
+/// Size of a [`Batch`], which is written in one transaction of up to
+/// [`MAX_WRITES_PER_TRANSACTION`] chunks of 64 bytes
+const BATCH_SIZE: usize = CHUNK_SIZE * MAX_WRITES_PER_TRANSACTION;
+
+/// Buffer on the stack which collects small writes until `BATCH_SIZE` bytes are pending
+struct Batch {
+    buffer: [u8; BATCH_SIZE],
+    len: usize,
+}
+
+impl Batch {
+    fn new() -> Self {
+        Self {
+            buffer: [0; BATCH_SIZE],
+            len: 0,
+        }
+    }
+
+    /// Adds `data` to the batch and writes the batch each time it is full. Data of at least
+    /// `BATCH_SIZE` bytes is written directly without a copy if nothing is pending.
+    fn push<E>(
+        &mut self,
+        mut data: &[u8],
+        mut write: impl FnMut(&[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        while !data.is_empty() {
+            if self.len == 0 && data.len() >= BATCH_SIZE {
+                return write(data);
+            }
+            let len = BATCH_SIZE.saturating_sub(self.len).min(data.len());
+            let Some((head, tail)) = data.split_at_checked(len) else {
+                break;
+            };
+            if let Some(free) = self.buffer.get_mut(self.len..self.len + len) {
+                free.copy_from_slice(head);
+            }
+            self.len += len;
+            data = tail;
+            if self.len == BATCH_SIZE {
+                self.flush(&mut write)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the pending bytes
+    fn flush<E>(&mut self, write: impl FnOnce(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        let pending = self.buffer.get(..self.len).unwrap_or_default();
+        self.len = 0;
+        if pending.is_empty() {
+            return Ok(());
         }
+        write(pending)
     }
 }
 
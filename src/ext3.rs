@@ -0,0 +1,108 @@
+//! Pin mappings and settings of the EXT3-1 extension kit of Pervasive Displays on common
+//! development boards, as used by the examples. With the feature `rppal` the driver for a
+//! Raspberry Pi is created by one call:
+//! ```ignore
+//! let (epd, mut spi, mut delay) = ext3::raspberry::connect()?;
+//! let mut epd = epd.init(&mut spi, &mut delay)?;
+//! ```
+
+/// SPI clock frequency in Hz which works with the EXT3-1 and its ribbon cable
+pub const SPI_FREQUENCY: u32 = 4_000_000;
+
+/// EXT3-1 on the 40 pin header of a Raspberry Pi (SPI0 with CE0)
+///
+/// | Raspberry      | EXT3-1 |
+/// |----------------|--------|
+/// | GPIO 11 (SCLK) | SCK    |
+/// | GPIO 10 (MOSI) | MOSI   |
+/// | GPIO 8  (CE0)  | CS     |
+/// | GPIO 24        | BUSY   |
+/// | GPIO 25        | DC     |
+/// | GPIO 17        | RESET  |
+pub mod raspberry {
+    /// BCM number of the GPIO connected to BUSY
+    pub const BUSY: u8 = 24;
+    /// BCM number of the GPIO connected to DC
+    pub const DC: u8 = 25;
+    /// BCM number of the GPIO connected to RESET
+    pub const RESET: u8 = 17;
+    /// Chunk size for SPI writes, the default buffer size of spidev
+    pub const SPI_CHUNK_SIZE: usize = 4096;
+
+    #[cfg(feature = "rppal")]
+    pub use connect::*;
+
+    #[cfg(feature = "rppal")]
+    mod connect {
+        use rppal::{
+            gpio::{Gpio, InputPin, OutputPin},
+            hal::Delay,
+            spi::{Bus, Mode, SimpleHalSpiDevice, SlaveSelect, Spi},
+        };
+
+        use super::{BUSY, DC, RESET, SPI_CHUNK_SIZE};
+        use crate::{ext3::SPI_FREQUENCY, Epd, Inactive};
+
+        /// SPI device of the EXT3-1 on a Raspberry Pi
+        pub type RaspberrySpi = SimpleHalSpiDevice<Spi>;
+
+        /// Driver of the EXT3-1 on a Raspberry Pi
+        pub type RaspberryEpd = Epd<Inactive, RaspberrySpi, InputPin, OutputPin, OutputPin, Delay>;
+
+        /// Errors of the setup of the peripherals
+        #[derive(thiserror::Error, Debug)]
+        pub enum ConnectError {
+            #[error("GPIO error: {0}")]
+            Gpio(#[source] rppal::gpio::Error),
+            #[error("SPI error: {0}")]
+            Spi(#[source] rppal::spi::Error),
+        }
+
+        impl From<rppal::gpio::Error> for ConnectError {
+            fn from(error: rppal::gpio::Error) -> Self {
+                ConnectError::Gpio(error)
+            }
+        }
+
+        impl From<rppal::spi::Error> for ConnectError {
+            fn from(error: rppal::spi::Error) -> Self {
+                ConnectError::Spi(error)
+            }
+        }
+
+        /// Sets up SPI0 and the GPIOs of the pin mapping and returns the driver, which still
+        /// has to be initialized, together with the SPI device and the delay to pass to it.
+        /// SPI has to be enabled, e.g. with raspi-config.
+        ///
+        /// # Errors
+        ///
+        /// This function will return an error if the SPI device or a GPIO cannot be opened.
+        pub fn connect() -> Result<(RaspberryEpd, RaspberrySpi, Delay), ConnectError> {
+            let spi = Spi::new(Bus::Spi0, SlaveSelect::Ss0, SPI_FREQUENCY, Mode::Mode0)?;
+            let mut spi = SimpleHalSpiDevice::new(spi);
+            let gpio = Gpio::new()?;
+            let busy = gpio.get(BUSY)?.into_input();
+            let dc = gpio.get(DC)?.into_output();
+            let rst = gpio.get(RESET)?.into_output();
+            let mut delay = Delay::new();
+            let epd = Epd::new(&mut spi, busy, dc, rst, &mut delay, SPI_CHUNK_SIZE);
+            Ok((epd, spi, delay))
+        }
+    }
+}
+
+/// EXT3-1 on the Arduino header of an STM32 Nucleo F401RE (SPI1), the pins have to be
+/// configured with the HAL of the board
+///
+/// | Nucleo | EXT3-1 |
+/// |--------|--------|
+/// | PB3    | SCK    |
+/// | PB5    | MOSI   |
+/// | PA6    | CS     |
+/// | PA7    | BUSY   |
+/// | PB6    | DC     |
+/// | PA9    | RESET  |
+pub mod nucleo_f401re {
+    /// Chunk size for SPI writes, no chunks are needed
+    pub const SPI_CHUNK_SIZE: usize = 0;
+}
@@ -3,14 +3,18 @@
 use core::cmp::{max, min};
 use embedded_graphics::{
     draw_target::DrawTarget,
-    geometry::{OriginDimensions, Size},
+    geometry::{Angle, Dimensions, OriginDimensions, Point, Size},
+    image::GetPixel,
     pixelcolor::{
         raw::{RawData, RawU2},
-        BinaryColor, PixelColor, Rgb888, RgbColor,
+        BinaryColor, PixelColor, Rgb565, Rgb888, RgbColor,
     },
+    primitives::{PointsIter, Rectangle},
     Pixel,
 };
 
+use crate::packing::{self, plane_bytes};
+
 /// Colors supported by the e-paper displays
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum TriColor {
@@ -20,6 +24,36 @@ pub enum TriColor {
     Red,
 }
 
+impl TriColor {
+    /// Returns true for the chromatic color (red)
+    #[must_use]
+    pub fn is_chromatic(self) -> bool {
+        self == TriColor::Red
+    }
+
+    /// Returns the luma (perceived brightness) of the color
+    #[must_use]
+    pub fn luma(self) -> u8 {
+        match self {
+            TriColor::White => u8::MAX,
+            TriColor::Black => 0,
+            TriColor::Red => 76,
+        }
+    }
+
+    /// Swaps black and white, red stays red
+    #[must_use]
+    pub fn invert(self) -> Self {
+        match self {
+            TriColor::White => TriColor::Black,
+            TriColor::Black => TriColor::White,
+            TriColor::Red => TriColor::Red,
+        }
+    }
+}
+
+/// The raw data is the 2 bit encoding of the interleaved buffer (white: 0, black: 1, red: 2),
+/// so `IntoStorage` and `RawU2` conversions can be used by generic code.
 impl PixelColor for TriColor {
     type Raw = RawU2;
 }
@@ -37,6 +71,16 @@ impl From<RawU2> for TriColor {
     }
 }
 
+impl From<TriColor> for RawU2 {
+    fn from(color: TriColor) -> Self {
+        match color {
+            TriColor::White => RawU2::new(0b00),
+            TriColor::Black => RawU2::new(0b01),
+            TriColor::Red => RawU2::new(0b10),
+        }
+    }
+}
+
 impl From<BinaryColor> for TriColor {
     fn from(b: BinaryColor) -> TriColor {
         match b {
@@ -56,6 +100,18 @@ impl From<TriColor> for Rgb888 {
     }
 }
 
+impl From<TriColor> for Rgb565 {
+    fn from(b: TriColor) -> Self {
+        Rgb888::from(b).into()
+    }
+}
+
+impl From<Rgb565> for TriColor {
+    fn from(p: Rgb565) -> TriColor {
+        Rgb888::from(p).into()
+    }
+}
+
 impl From<Rgb888> for TriColor {
     fn from(p: Rgb888) -> TriColor {
         let min = min(min(p.r(), p.g()), p.b());
@@ -72,8 +128,9 @@ impl From<Rgb888> for TriColor {
     }
 }
 
-/// Display rotation, only 90° increments supported
-#[derive(Clone, Copy, Default)]
+/// Display rotation, only 90° increments supported. Rotations can be composed with `+` and
+/// converted from and to degrees or the [`Angle`] of embedded-graphics.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
 pub enum DisplayRotation {
     /// No rotation
     #[default]
@@ -86,52 +143,664 @@ pub enum DisplayRotation {
     Rotate270,
 }
 
+impl DisplayRotation {
+    /// Returns the clockwise rotation in degrees (0, 90, 180 or 270)
+    #[must_use]
+    pub const fn degrees(self) -> u16 {
+        match self {
+            DisplayRotation::Rotate0 => 0,
+            DisplayRotation::Rotate90 => 90,
+            DisplayRotation::Rotate180 => 180,
+            DisplayRotation::Rotate270 => 270,
+        }
+    }
+
+    /// Returns the rotation for a clockwise angle in degrees, e.g. -90 for `Rotate270`.
+    /// Returns `None` if the angle is not a multiple of 90°.
+    #[must_use]
+    pub const fn from_degrees(degrees: i32) -> Option<Self> {
+        if degrees % 90 != 0 {
+            return None;
+        }
+        Some(match degrees.rem_euclid(360) / 90 {
+            0 => DisplayRotation::Rotate0,
+            1 => DisplayRotation::Rotate90,
+            2 => DisplayRotation::Rotate180,
+            _ => DisplayRotation::Rotate270,
+        })
+    }
+
+    /// Returns the rotation which reverts this one
+    #[must_use]
+    pub const fn inverse(self) -> Self {
+        match self {
+            DisplayRotation::Rotate0 => DisplayRotation::Rotate0,
+            DisplayRotation::Rotate90 => DisplayRotation::Rotate270,
+            DisplayRotation::Rotate180 => DisplayRotation::Rotate180,
+            DisplayRotation::Rotate270 => DisplayRotation::Rotate90,
+        }
+    }
+
+    /// Converts a point in display coordinates into coordinates (x, y) of an unrotated
+    /// buffer with the given size. Returns `None` if the point is outside the buffer.
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+    pub(crate) fn buffer_position(self, p: Point, buffer_size: Size) -> Option<(usize, usize)> {
+        let width = buffer_size.width as i32;
+        let height = buffer_size.height as i32;
+        let (x, y) = match self {
+            DisplayRotation::Rotate0 => (p.x, p.y),
+            DisplayRotation::Rotate90 => (width - 1 - p.y, p.x),
+            DisplayRotation::Rotate180 => (width - 1 - p.x, height - 1 - p.y),
+            DisplayRotation::Rotate270 => (p.y, height - 1 - p.x),
+        };
+
+        if (x < 0) || (x >= width) || (y < 0) || y >= height {
+            return None;
+        }
+        Some((x as usize, y as usize))
+    }
+
+    /// Converts an area of an unrotated buffer with the given size into display coordinates
+    #[allow(clippy::cast_possible_wrap)]
+    pub(crate) fn display_area(self, buffer_area: &Rectangle, buffer_size: Size) -> Rectangle {
+        let Some(bottom_right) = buffer_area.bottom_right() else {
+            return Rectangle::zero();
+        };
+        let width = buffer_size.width as i32;
+        let height = buffer_size.height as i32;
+        let to_display = |p: Point| match self {
+            DisplayRotation::Rotate0 => p,
+            DisplayRotation::Rotate90 => Point::new(p.y, width - 1 - p.x),
+            DisplayRotation::Rotate180 => Point::new(width - 1 - p.x, height - 1 - p.y),
+            DisplayRotation::Rotate270 => Point::new(height - 1 - p.y, p.x),
+        };
+        Rectangle::with_corners(to_display(buffer_area.top_left), to_display(bottom_right))
+    }
+
+    /// Returns the size in display coordinates of an unrotated buffer with the given size
+    pub(crate) const fn display_size(self, buffer_size: Size) -> Size {
+        match self {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => buffer_size,
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                Size::new(buffer_size.height, buffer_size.width)
+            }
+        }
+    }
+}
+
+impl core::ops::Add for DisplayRotation {
+    type Output = Self;
+
+    /// Composes two rotations, e.g. the rotation of a display and of an image drawn on it
+    fn add(self, other: Self) -> Self {
+        let degrees = i32::from(self.degrees()) + i32::from(other.degrees());
+        Self::from_degrees(degrees).unwrap_or_default()
+    }
+}
+
+impl From<DisplayRotation> for Angle {
+    fn from(rotation: DisplayRotation) -> Self {
+        Angle::from_degrees(f32::from(rotation.degrees()))
+    }
+}
+
+impl TryFrom<Angle> for DisplayRotation {
+    type Error = Angle;
+
+    /// Converts a clockwise angle which is a multiple of 90° (within 0.1°, e.g. for angles
+    /// converted from radians), returns the angle otherwise
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn try_from(angle: Angle) -> Result<Self, Self::Error> {
+        let quarters = angle.to_degrees() / 90.0;
+        let rounded = if quarters < 0.0 {
+            quarters - 0.5
+        } else {
+            quarters + 0.5
+        };
+        let whole = rounded as i32;
+        let deviation = (quarters - whole as f32) * 90.0;
+        if !(-0.1..=0.1).contains(&deviation) {
+            return Err(angle);
+        }
+        Self::from_degrees(whole % 4 * 90).ok_or(angle)
+    }
+}
+
+/// Data source for the driver: provides the black and the red plane which are sent to the e-paper.
+/// A plane contains one bit per pixel (set: black resp. red) in unrotated panel orientation,
+/// row by row and MSB first. Implementations should pass their buffers to `write` as they
+/// are, so that no copies are needed for sending them.
+///
+/// Buffers implement `write_black` and `write_red`. The getters `get_buffer_black` and
+/// `get_buffer_red` are deprecated and only kept for buffers which were written before the
+/// `write_*` methods existed: the default `write_*` methods send the slices of the getters.
+/// The getters of the other buffers return an empty slice, so code which sends a frame has to
+/// call the `write_*` methods instead.
 pub trait DisplayBuffer {
-    fn get_buffer_black(&self) -> &[u8];
-    fn get_buffer_red(&self) -> &[u8];
+    /// Returns the black plane as it is stored. Buffers which implement `write_black` do not
+    /// have to implement it, the default returns an empty slice. The plane may differ from
+    /// the one written by `write_black`, e.g. for an inverted [`Display`].
+    #[deprecated(
+        note = "returns an empty slice unless implemented and may differ from the sent plane, \
+                call `write_black` to send the plane and implement it instead of this getter"
+    )]
+    fn get_buffer_black(&self) -> &[u8] {
+        &[]
+    }
+
+    /// Returns the red plane as it is stored, like `get_buffer_black`
+    #[deprecated(
+        note = "returns an empty slice unless implemented and may differ from the sent plane, \
+                call `write_red` to send the plane and implement it instead of this getter"
+    )]
+    fn get_buffer_red(&self) -> &[u8] {
+        &[]
+    }
+
+    /// Writes the black plane by calling `write` with consecutive chunks of the plane.
+    /// The default implementation writes [`Self::get_buffer_black`] at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error returned by `write`.
+    fn write_black<E>(&self, mut write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        #[allow(deprecated)]
+        write(self.get_buffer_black())
+    }
+
+    /// Writes the red plane by calling `write` with consecutive chunks of the plane.
+    /// The default implementation writes [`Self::get_buffer_red`] at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error returned by `write`.
+    fn write_red<E>(&self, mut write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        #[allow(deprecated)]
+        write(self.get_buffer_red())
+    }
+}
+
+/// Size of the stack buffer used for writing data which is converted on the fly
+pub(crate) const CHUNK_SIZE: usize = 64;
+
+/// Collects the bytes of `data` on the stack and writes them in chunks via `write`
+pub(crate) fn write_chunked<E>(
+    data: impl IntoIterator<Item = u8>,
+    mut write: impl FnMut(&[u8]) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut data = data.into_iter();
+    let mut chunk = [0; CHUNK_SIZE];
+    loop {
+        let mut len = 0;
+        for (dst, src) in chunk.iter_mut().zip(&mut data) {
+            *dst = src;
+            len += 1;
+        }
+        match chunk.get(..len) {
+            None | Some([]) => return Ok(()),
+            Some(data) => write(data)?,
+        }
+        if len < CHUNK_SIZE {
+            return Ok(());
+        }
+    }
+}
+
+/// Error of [`Display::load_bytes`]: the length of the snapshot does not match the display
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SizeError {
+    /// Length of a snapshot of the display, [`Display::SNAPSHOT_SIZE`]
+    pub expected: usize,
+    /// Length of the data which was loaded
+    pub actual: usize,
 }
 
 /// Display buffer used for drawing with `embedded_graphics`.
 /// The concrete types are dependent on the size.
 /// Examples: `Display1in54`, `Display2in13`, ...
+///
+/// Drawing is clipped to the display: pixels outside of it in display coordinates, i.e. after
+/// the rotation, are discarded with every rotation. So e.g. text can be positioned partly
+/// off-screen for scroll-in effects.
 pub struct Display<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> {
     buffer_black: [u8; IMAGE_SIZE],
     buffer_red: [u8; IMAGE_SIZE],
     rotation: DisplayRotation,
+    inverted: bool,
+    chromatic_as_black: bool,
+    /// bounding box of all pixels modified since the last call of `take_dirty_region`
+    dirty: Option<DirtyRegion>,
 }
 
 impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>
     Display<SIZE_V, SIZE_H, IMAGE_SIZE>
 {
-    pub fn set_rotation(&mut self, rotation: DisplayRotation) {
+    /// Checks at compile time that `IMAGE_SIZE` matches the size, it is evaluated when a
+    /// display buffer is created. So e.g. `Display<296, 152, 5000>` fails to compile.
+    pub(crate) const SIZE_CHECK: () = assert!(
+        SIZE_H.is_multiple_of(8) && IMAGE_SIZE == SIZE_V as usize * (SIZE_H as usize / 8),
+        "IMAGE_SIZE does not match SIZE_V * SIZE_H / 8"
+    );
+
+    /// Width of the unrotated panel in pixels (`SIZE_H`)
+    pub const WIDTH: u32 = SIZE_H;
+    /// Height of the unrotated panel in pixels (`SIZE_V`)
+    pub const HEIGHT: u32 = SIZE_V;
+    /// Number of bytes of a row of a plane, 8 pixels per byte
+    pub const BYTES_PER_ROW: usize = SIZE_H as usize / 8;
+    /// Number of bytes of each plane (`IMAGE_SIZE`), a frame consists of the black and the
+    /// chromatic plane. E.g. a buffer for a frame in flash needs `2 * PLANE_SIZE` bytes.
+    pub const PLANE_SIZE: usize = IMAGE_SIZE;
+    /// Number of bytes of a snapshot of the buffer, see [`Self::bytes`]
+    pub const SNAPSHOT_SIZE: usize = 2 * IMAGE_SIZE;
+
+    /// Creates an empty (white) display buffer. All fields are zero, so a static buffer
+    /// ends up in `.bss` and neither takes space in flash nor has to be copied at startup:
+    /// ```ignore
+    /// static DISPLAY: Mutex<RefCell<Display2in66>> = Mutex::new(RefCell::new(Display2in66::new()));
+    /// ```
+    /// Large displays should be created like this instead of on the stack.
+    #[must_use]
+    pub const fn new() -> Self {
+        let () = Self::SIZE_CHECK;
+        Self {
+            buffer_black: [0; IMAGE_SIZE],
+            buffer_red: [0; IMAGE_SIZE],
+            rotation: DisplayRotation::Rotate0,
+            inverted: false,
+            chromatic_as_black: false,
+            dirty: None,
+        }
+    }
+
+    /// Creates a display buffer from raw black and chromatic planes, e.g. a pre-rendered
+    /// frame stored in flash. See [`Self::black_plane`] for the layout of the planes.
+    /// Returns `None` if the length of a plane does not match the display size.
+    #[must_use]
+    pub fn from_raw_planes(black: &[u8], red: &[u8]) -> Option<Self> {
+        let () = Self::SIZE_CHECK;
+        Some(Self {
+            buffer_black: black.try_into().ok()?,
+            buffer_red: red.try_into().ok()?,
+            rotation: DisplayRotation::default(),
+            inverted: false,
+            chromatic_as_black: false,
+            dirty: Some(DirtyRegion::all(SIZE_H, SIZE_V)),
+        })
+    }
+
+    pub const fn set_rotation(&mut self, rotation: DisplayRotation) {
         self.rotation = rotation;
     }
     #[must_use]
     pub fn rotation(&self) -> DisplayRotation {
         self.rotation
     }
+
+    /// Swaps black and white when the buffer is sent to the e-paper, red stays red.
+    /// The content of the buffer is not changed. Together with
+    /// [`Self::set_chromatic_as_black`] red is shown as black first and then inverted, so
+    /// red pixels are shown white.
+    pub fn set_inverted(&mut self, inverted: bool) {
+        self.inverted = inverted;
+    }
+    #[must_use]
+    pub fn inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Shows red pixels as black when the buffer is sent to the e-paper, e.g. to share
+    /// drawing code with black and white displays. The content of the buffer is not changed.
+    pub fn set_chromatic_as_black(&mut self, chromatic_as_black: bool) {
+        self.chromatic_as_black = chromatic_as_black;
+    }
+    #[must_use]
+    pub fn chromatic_as_black(&self) -> bool {
+        self.chromatic_as_black
+    }
+
+    /// Returns the black plane of the buffer. The plane contains one bit per pixel
+    /// (set: black) in unrotated panel orientation, row by row and MSB first.
+    #[must_use]
+    pub fn black_plane(&self) -> &[u8] {
+        &self.buffer_black
+    }
+
+    /// Returns the black plane of the buffer as mutable slice, see [`Self::black_plane`]
+    pub fn black_plane_mut(&mut self) -> &mut [u8] {
+        self.mark_all_dirty();
+        &mut self.buffer_black
+    }
+
+    /// Returns the chromatic (red) plane of the buffer. The plane contains one bit per pixel
+    /// (set: red) in unrotated panel orientation, row by row and MSB first.
+    /// A set bit in the black plane takes precedence.
+    #[must_use]
+    pub fn chromatic_plane(&self) -> &[u8] {
+        &self.buffer_red
+    }
+
+    /// Returns the chromatic (red) plane of the buffer as mutable slice, see [`Self::chromatic_plane`]
+    pub fn chromatic_plane_mut(&mut self) -> &mut [u8] {
+        self.mark_all_dirty();
+        &mut self.buffer_red
+    }
+
+    /// Returns the content of the buffer as snapshot of [`Self::SNAPSHOT_SIZE`] bytes: the
+    /// black plane followed by the chromatic plane, e.g. to persist the last shown frame in
+    /// flash. Settings like the rotation are not included.
+    pub fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.buffer_black
+            .iter()
+            .chain(self.buffer_red.iter())
+            .copied()
+    }
+
+    /// Restores the content of the buffer from a snapshot created by [`Self::bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error and leaves the buffer unchanged if the length of `bytes` is not
+    /// [`Self::SNAPSHOT_SIZE`].
+    pub fn load_bytes(&mut self, bytes: &[u8]) -> Result<(), SizeError> {
+        if bytes.len() != Self::SNAPSHOT_SIZE {
+            return Err(SizeError {
+                expected: Self::SNAPSHOT_SIZE,
+                actual: bytes.len(),
+            });
+        }
+        let (black, red) = bytes.split_at(IMAGE_SIZE);
+        self.buffer_black.copy_from_slice(black);
+        self.buffer_red.copy_from_slice(red);
+        self.mark_all_dirty();
+        Ok(())
+    }
+
+    /// Rotates the content of the buffer by 180 degrees
+    pub fn rotate_180(&mut self) {
+        self.mark_all_dirty();
+        for plane in [&mut self.buffer_black, &mut self.buffer_red] {
+            plane.reverse();
+            for byte in plane.iter_mut() {
+                *byte = byte.reverse_bits();
+            }
+        }
+    }
+
+    /// Mirrors the content of the buffer horizontally (in display coordinates,
+    /// i.e. the current rotation is taken into account)
+    pub fn flip_horizontal(&mut self) {
+        match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => self.flip_buffer_columns(),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => self.flip_buffer_rows(),
+        }
+    }
+
+    /// Mirrors the content of the buffer vertically (in display coordinates,
+    /// i.e. the current rotation is taken into account)
+    pub fn flip_vertical(&mut self) {
+        match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => self.flip_buffer_rows(),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => self.flip_buffer_columns(),
+        }
+    }
+
+    /// Scrolls the content up by `rows` rows (in display coordinates, i.e. the current
+    /// rotation is taken into account). The vacated band at the bottom is filled with
+    /// `color` and returned, so that new content can be drawn into it.
+    pub fn scroll_up(&mut self, rows: u32, color: TriColor) -> Rectangle {
+        self.scroll_vertical(rows, true, color)
+    }
+
+    /// Scrolls the content down by `rows` rows (in display coordinates, i.e. the current
+    /// rotation is taken into account). The vacated band at the top is filled with
+    /// `color` and returned, so that new content can be drawn into it.
+    pub fn scroll_down(&mut self, rows: u32, color: TriColor) -> Rectangle {
+        self.scroll_vertical(rows, false, color)
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn scroll_vertical(&mut self, rows: u32, up: bool, color: TriColor) -> Rectangle {
+        let size = self.size();
+        let rows = min(rows, size.height);
+        let pixels = rows as usize;
+        if rows > 0 {
+            self.mark_all_dirty();
+        }
+        match (self.rotation, up) {
+            (DisplayRotation::Rotate0, true) | (DisplayRotation::Rotate180, false) => {
+                self.shift_buffer_rows(pixels, false);
+            }
+            (DisplayRotation::Rotate0, false) | (DisplayRotation::Rotate180, true) => {
+                self.shift_buffer_rows(pixels, true);
+            }
+            (DisplayRotation::Rotate90, true) | (DisplayRotation::Rotate270, false) => {
+                self.shift_buffer_columns(pixels, true);
+            }
+            (DisplayRotation::Rotate90, false) | (DisplayRotation::Rotate270, true) => {
+                self.shift_buffer_columns(pixels, false);
+            }
+        }
+
+        let band_y = if up { size.height - rows } else { 0 };
+        let band = Rectangle::new(Point::new(0, band_y as i32), Size::new(size.width, rows));
+        // drawing into the buffer is infallible
+        let _ = self.fill_solid(&band, color);
+        band
+    }
+
+    /// Moves the rows of the buffer by `rows` towards the last row (`down`) or the first row.
+    /// The vacated rows keep their old content.
+    fn shift_buffer_rows(&mut self, rows: usize, down: bool) {
+        let bytes = min(rows * SIZE_H as usize / 8, IMAGE_SIZE);
+        for plane in [&mut self.buffer_black, &mut self.buffer_red] {
+            if down {
+                plane.copy_within(..IMAGE_SIZE - bytes, bytes);
+            } else {
+                plane.copy_within(bytes.., 0);
+            }
+        }
+    }
+
+    /// Moves the pixels in each row of the buffer by `columns` towards the last column (`right`)
+    /// or the first column. The vacated columns are set to zero.
+    fn shift_buffer_columns(&mut self, columns: usize, right: bool) {
+        let bytes_per_row = SIZE_H as usize / 8;
+        let (bytes, bits) = (columns / 8, columns % 8);
+        for plane in [&mut self.buffer_black, &mut self.buffer_red] {
+            for row in plane.chunks_exact_mut(bytes_per_row) {
+                if right {
+                    for i in (0..bytes_per_row).rev() {
+                        let get = |offset| {
+                            i.checked_sub(offset)
+                                .and_then(|j| row.get(j))
+                                .copied()
+                                .unwrap_or(0)
+                        };
+                        let [_, byte] = (u16::from_be_bytes([get(bytes + 1), get(bytes)]) >> bits)
+                            .to_be_bytes();
+                        if let Some(dst) = row.get_mut(i) {
+                            *dst = byte;
+                        }
+                    }
+                } else {
+                    for i in 0..bytes_per_row {
+                        let get = |offset| row.get(i + offset).copied().unwrap_or(0);
+                        let [byte, _] = (u16::from_be_bytes([get(bytes), get(bytes + 1)]) << bits)
+                            .to_be_bytes();
+                        if let Some(dst) = row.get_mut(i) {
+                            *dst = byte;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reverses the order of the rows of the buffer
+    fn flip_buffer_rows(&mut self) {
+        self.mark_all_dirty();
+        let bytes_per_row = SIZE_H as usize / 8;
+        for plane in [&mut self.buffer_black, &mut self.buffer_red] {
+            let (top, bottom) = plane.split_at_mut(IMAGE_SIZE / 2);
+            let rows_top = top.chunks_exact_mut(bytes_per_row);
+            let rows_bottom = bottom.rchunks_exact_mut(bytes_per_row);
+            for (row_top, row_bottom) in rows_top.zip(rows_bottom) {
+                row_top.swap_with_slice(row_bottom);
+            }
+        }
+    }
+
+    /// Reverses the order of the pixels in each row of the buffer
+    fn flip_buffer_columns(&mut self) {
+        self.mark_all_dirty();
+        let bytes_per_row = SIZE_H as usize / 8;
+        for plane in [&mut self.buffer_black, &mut self.buffer_red] {
+            for row in plane.chunks_exact_mut(bytes_per_row) {
+                row.reverse();
+                for byte in row.iter_mut() {
+                    *byte = byte.reverse_bits();
+                }
+            }
+        }
+    }
+
+    /// Returns the bounding box (in display coordinates) of all pixels which were modified
+    /// since the last call of this function and resets it. Returns `None` if nothing changed.
+    /// E.g. call this function before each update to decide whether a refresh is necessary.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub fn take_dirty_region(&mut self) -> Option<Rectangle> {
+        let DirtyRegion {
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+        } = self.dirty.take()?;
+        let to_point = |x: usize, y: usize| Point::new(x as i32, y as i32);
+        let buffer_area = Rectangle::with_corners(to_point(x_min, y_min), to_point(x_max, y_max));
+        Some(
+            self.rotation
+                .display_area(&buffer_area, Size::new(SIZE_H, SIZE_V)),
+        )
+    }
+
+    /// Returns an iterator over all pixels of the display in display coordinates.
+    /// The pixels are yielded row by row, from top left to bottom right.
+    pub fn pixels(&self) -> impl Iterator<Item = Pixel<TriColor>> + '_ {
+        self.bounding_box()
+            .points()
+            .filter_map(|p| self.pixel(p).map(|color| Pixel(p, color)))
+    }
+
+    /// Draws the set bits of a bitmap with one bit per pixel in `color` with its top left
+    /// corner at `top_left`, clear bits are transparent. The bitmap contains rows of `width`
+    /// pixels, each row padded to whole bytes and MSB first (like `ImageRaw<BinaryColor>`
+    /// or the planes of this buffer). E.g. frames composed in an off-screen 1 bpp framebuffer
+    /// can be copied into the planes. Without rotation and with `top_left` within the display
+    /// whole bytes are copied, which is much faster than drawing the pixels one by one.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    pub fn draw_bitmap(&mut self, bitmap: &[u8], width: u32, top_left: Point, color: TriColor) {
+        let row_bytes = width.div_ceil(8) as usize;
+        if row_bytes == 0 {
+            return;
+        }
+        if !matches!(self.rotation, DisplayRotation::Rotate0)
+            || top_left.x < 0
+            || top_left.y < 0
+            || top_left.x >= SIZE_H as i32
+            || top_left.y >= SIZE_V as i32
+        {
+            let bit = |x: u32, y: u32| {
+                let byte = bitmap.get(y as usize * row_bytes + x as usize / 8);
+                byte.is_some_and(|byte| byte & packing::bit_mask(x as usize) != 0)
+            };
+            let height = (bitmap.len() / row_bytes) as u32;
+            let pixels = (0..height).flat_map(|y| (0..width).map(move |x| (x, y)));
+            let _ = self.draw_iter(
+                pixels
+                    .filter(|&(x, y)| bit(x, y))
+                    .map(|(x, y)| Pixel(top_left + Point::new(x as i32, y as i32), color)),
+            );
+            return;
+        }
+
+        let (black, red) = plane_bytes(color);
+        let (x0, y0) = (top_left.x as usize, top_left.y as usize);
+        let buffer_row_bytes = SIZE_H as usize / 8;
+        let rows = min(bitmap.len() / row_bytes, SIZE_V as usize - y0);
+        let columns = min(width as usize, SIZE_H as usize - x0);
+        if rows == 0 || columns == 0 {
+            return;
+        }
+        self.mark_dirty((x0, y0), (x0 + columns - 1, y0 + rows - 1));
+        let shift = x0 % 8;
+        for (y, row) in bitmap.chunks_exact(row_bytes).take(rows).enumerate() {
+            let row_start = (y0 + y) * buffer_row_bytes;
+            for (i, &byte) in row.iter().enumerate() {
+                // bits of the padding and beyond the right edge of the display are dropped
+                let valid = columns.saturating_sub(i * 8).min(8);
+                let byte = byte & !(0xff_u16 >> valid) as u8;
+                if byte == 0 {
+                    continue;
+                }
+                let index = row_start + (x0 + i * 8) / 8;
+                let window = u16::from(byte) << (8 - shift);
+                for (index, mask) in [(index, (window >> 8) as u8), (index + 1, window as u8)] {
+                    if mask != 0 {
+                        self.write_bits(ByteBits {
+                            index,
+                            mask,
+                            black,
+                            red,
+                        });
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> DisplayBuffer
     for Display<SIZE_V, SIZE_H, IMAGE_SIZE>
 {
+    /// Returns the black plane as it is stored, i.e. without the conversion of
+    /// [`Self::set_inverted`] and [`Self::set_chromatic_as_black`]
     fn get_buffer_black(&self) -> &[u8] {
         &self.buffer_black
     }
+    /// Returns the red plane as it is stored, i.e. without the conversion of
+    /// [`Self::set_chromatic_as_black`]
     fn get_buffer_red(&self) -> &[u8] {
         &self.buffer_red
     }
+    fn write_black<E>(&self, mut write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        let planes = self.buffer_black.iter().zip(&self.buffer_red);
+        if self.inverted {
+            write_chunked(planes.map(|(black, red)| !(black | red)), write)
+        } else if self.chromatic_as_black {
+            write_chunked(planes.map(|(black, red)| black | red), write)
+        } else {
+            write(&self.buffer_black)
+        }
+    }
+    fn write_red<E>(&self, mut write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        if self.chromatic_as_black {
+            write_chunked(core::iter::repeat_n(0, IMAGE_SIZE), write)
+        } else {
+            write(&self.buffer_red)
+        }
+    }
 }
 
 impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> Default
     for Display<SIZE_V, SIZE_H, IMAGE_SIZE>
 {
     fn default() -> Self {
-        Self {
-            buffer_black: [0; IMAGE_SIZE],
-            buffer_red: [0; IMAGE_SIZE],
-            rotation: DisplayRotation::default(),
-        }
+        Self::new()
     }
 }
 
@@ -139,61 +808,331 @@ impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> OriginDimens
     for Display<SIZE_V, SIZE_H, IMAGE_SIZE>
 {
     fn size(&self) -> Size {
-        match self.rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => Size::new(SIZE_H, SIZE_V),
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => Size::new(SIZE_V, SIZE_H),
+        self.rotation.display_size(Size::new(SIZE_H, SIZE_V))
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>
+    Display<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    /// Extends the dirty region by the buffer area spanned by the given corners (x, y)
+    fn mark_dirty(&mut self, first: (usize, usize), last: (usize, usize)) {
+        self.dirty = Some(DirtyRegion::extended(self.dirty, first, last));
+    }
+
+    fn mark_all_dirty(&mut self) {
+        self.dirty = Some(DirtyRegion::all(SIZE_H, SIZE_V));
+    }
+
+    /// Returns the byte index and the bit mask of the pixel at buffer position (x, y)
+    fn byte_position(x: usize, y: usize) -> (usize, u8) {
+        packing::byte_position(x, y, SIZE_H)
+    }
+
+    /// Returns the bits of the buffer byte which contains the pixel at buffer position (x, y)
+    fn pixel_bits(x: usize, y: usize, color: TriColor) -> ByteBits {
+        let (index, mask) = Self::byte_position(x, y);
+        let (black, red) = plane_bytes(color);
+        ByteBits {
+            index,
+            mask,
+            black: black & mask,
+            red: red & mask,
+        }
+    }
+
+    /// Draws `pixels`. Consecutive pixels which end up in the same byte, e.g. of a row of
+    /// a glyph, are collected and written at once.
+    fn draw_merged(&mut self, pixels: impl IntoIterator<Item = Pixel<TriColor>>) {
+        self.draw_rotated(self.rotation, pixels);
+    }
+
+    /// Draws `pixels` in display coordinates of `rotation` instead of the current rotation,
+    /// e.g. a constant one of [`OrientedDisplay`](crate::OrientedDisplay)
+    #[inline]
+    pub(crate) fn draw_rotated(
+        &mut self,
+        rotation: DisplayRotation,
+        pixels: impl IntoIterator<Item = Pixel<TriColor>>,
+    ) {
+        let mut pending: Option<ByteBits> = None;
+        // the drawn area is collected locally and added to the dirty region once
+        let mut drawn: Option<DirtyRegion> = None;
+
+        for Pixel(p, color) in pixels {
+            let Some((x, y)) = rotation.buffer_position(p, Size::new(SIZE_H, SIZE_V)) else {
+                continue;
+            };
+            drawn = Some(DirtyRegion::extended(drawn, (x, y), (x, y)));
+            let bits = Self::pixel_bits(x, y, color);
+
+            let merged = pending.as_mut().is_some_and(|pending| pending.merge(bits));
+            if !merged {
+                if let Some(pending) = pending.replace(bits) {
+                    self.write_bits(pending);
+                }
+            }
+        }
+        if let Some(pending) = pending {
+            self.write_bits(pending);
+        }
+        if let Some(drawn) = drawn {
+            self.mark_dirty((drawn.x_min, drawn.y_min), (drawn.x_max, drawn.y_max));
+        }
+    }
+
+    /// Writes the bits selected by the mask into both buffers
+    fn write_bits(&mut self, bits: ByteBits) {
+        let ByteBits {
+            index,
+            mask,
+            black,
+            red,
+        } = bits;
+        if let (Some(dst_black), Some(dst_red)) = (
+            self.buffer_black.get_mut(index),
+            self.buffer_red.get_mut(index),
+        ) {
+            *dst_black = (*dst_black & !mask) | (black & mask);
+            *dst_red = (*dst_red & !mask) | (red & mask);
+        }
+    }
+
+    /// Fills the pixels `x_start..=x_end` of buffer row `y` with `color`.
+    /// Whole bytes are written at once, only the edges are masked.
+    fn fill_span(&mut self, y: usize, x_start: usize, x_end: usize, color: TriColor) {
+        let (black, red) = plane_bytes(color);
+        let row = y * SIZE_H as usize / 8;
+        let first = row + x_start / 8;
+        let last = row + x_end / 8;
+        let first_mask = 0xff_u8 >> (x_start % 8);
+        let last_mask = 0xff_u8 << (7 - x_end % 8);
+
+        let bits = |index, mask| ByteBits {
+            index,
+            mask,
+            black,
+            red,
+        };
+
+        if first == last {
+            self.write_bits(bits(first, first_mask & last_mask));
+        } else {
+            self.write_bits(bits(first, first_mask));
+            if let Some(bytes) = self.buffer_black.get_mut(first + 1..last) {
+                bytes.fill(black);
+            }
+            if let Some(bytes) = self.buffer_red.get_mut(first + 1..last) {
+                bytes.fill(red);
+            }
+            self.write_bits(bits(last, last_mask));
+        }
+    }
+
+    /// Returns `true` if `area` in buffer coordinates is within the buffer and starts and
+    /// ends at byte boundaries
+    #[allow(clippy::cast_sign_loss)]
+    fn is_aligned(area: &Rectangle) -> bool {
+        let buffer = Rectangle::new(Point::zero(), Size::new(SIZE_H, SIZE_V));
+        !area.is_zero_sized()
+            && buffer.intersection(area) == *area
+            && (area.top_left.x as u32).is_multiple_of(8)
+            && area.size.width.is_multiple_of(8)
+    }
+
+    /// Fills the byte-aligned `area` in buffer coordinates with `colors` row by row.
+    /// The colors of 8 pixels are packed into one byte per plane. If `colors` ends early,
+    /// the rest of the area is left unchanged.
+    #[allow(clippy::cast_sign_loss)]
+    fn fill_aligned(&mut self, area: &Rectangle, mut colors: impl Iterator<Item = TriColor>) {
+        let (x, y) = (area.top_left.x as usize, area.top_left.y as usize);
+        let (width, height) = (area.size.width as usize, area.size.height as usize);
+        self.mark_dirty((x, y), (x + width - 1, y + height - 1));
+        for row in y..y + height {
+            let start = row * Self::BYTES_PER_ROW + x / 8;
+            for index in start..start + width / 8 {
+                let mut bits = ByteBits {
+                    index,
+                    mask: 0,
+                    black: 0,
+                    red: 0,
+                };
+                for (bit, color) in (0..8).zip(colors.by_ref()) {
+                    let mask = packing::bit_mask(bit);
+                    let (black, red) = plane_bytes(color);
+                    bits.mask |= mask;
+                    bits.black |= black & mask;
+                    bits.red |= red & mask;
+                }
+                self.write_bits(bits);
+                if bits.mask != 0xff {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Bounding box in buffer coordinates (inclusive)
+#[derive(Clone, Copy)]
+struct DirtyRegion {
+    x_min: usize,
+    y_min: usize,
+    x_max: usize,
+    y_max: usize,
+}
+
+impl DirtyRegion {
+    /// Returns the region which covers a whole buffer with the given size
+    fn all(width: u32, height: u32) -> Self {
+        Self {
+            x_min: 0,
+            y_min: 0,
+            x_max: width as usize - 1,
+            y_max: height as usize - 1,
+        }
+    }
+
+    /// Returns `region` extended by the area spanned by the given corners (x, y)
+    fn extended(
+        region: Option<Self>,
+        (x_min, y_min): (usize, usize),
+        (x_max, y_max): (usize, usize),
+    ) -> Self {
+        match region {
+            Some(region) => Self {
+                x_min: min(region.x_min, x_min),
+                y_min: min(region.y_min, y_min),
+                x_max: max(region.x_max, x_max),
+                y_max: max(region.y_max, y_max),
+            },
+            None => Self {
+                x_min,
+                y_min,
+                x_max,
+                y_max,
+            },
         }
     }
 }
 
+/// Bits of one or more pixels located in the same byte of the buffers.
+/// Only the bits selected by `mask` are relevant.
+#[derive(Clone, Copy)]
+struct ByteBits {
+    index: usize,
+    mask: u8,
+    black: u8,
+    red: u8,
+}
+
+impl ByteBits {
+    /// Adds the bits of `other` if it refers to the same byte. Returns false otherwise.
+    fn merge(&mut self, other: ByteBits) -> bool {
+        if self.index != other.index {
+            return false;
+        }
+        self.mask |= other.mask;
+        self.black = (self.black & !other.mask) | other.black;
+        self.red = (self.red & !other.mask) | other.red;
+        true
+    }
+}
+
 impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> DrawTarget
     for Display<SIZE_V, SIZE_H, IMAGE_SIZE>
 {
     type Color = TriColor;
     type Error = core::convert::Infallible;
 
-    #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
     fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
-        for pixel in pixels {
-            let Pixel(p, color) = pixel;
-
-            let (x, y) = match self.rotation {
-                DisplayRotation::Rotate0 => (p.x, p.y),
-                DisplayRotation::Rotate90 => (SIZE_H as i32 - 1 - p.y, p.x),
-                DisplayRotation::Rotate180 => (SIZE_H as i32 - 1 - p.x, SIZE_V as i32 - 1 - p.y),
-                DisplayRotation::Rotate270 => (p.y, SIZE_V as i32 - 1 - p.x),
-            };
+        self.draw_merged(pixels);
+        Ok(())
+    }
 
-            if (x < 0) || (x >= SIZE_H as i32) || (y < 0) || y >= SIZE_V as i32 {
-                continue;
-            }
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if self.rotation == DisplayRotation::Rotate0 && Self::is_aligned(area) {
+            self.fill_aligned(area, colors.into_iter());
+        } else {
+            self.draw_merged(area.points().zip(colors).map(|(p, color)| Pixel(p, color)));
+        }
+        Ok(())
+    }
 
-            let mask: u8 = 1 << (7 - (x % 8));
-            let index = y as usize * SIZE_H as usize / 8 + x as usize / 8;
-            assert!(index < IMAGE_SIZE);
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid_rotated(self.rotation, area, color);
+        Ok(())
+    }
 
-            match color {
-                TriColor::White => {
-                    self.buffer_black[index] &= !mask;
-                    self.buffer_red[index] &= !mask;
-                }
-                TriColor::Black => {
-                    self.buffer_black[index] |= mask;
-                    self.buffer_red[index] &= !mask;
-                }
-                TriColor::Red => {
-                    self.buffer_black[index] &= !mask;
-                    self.buffer_red[index] |= mask;
-                }
-            }
-        }
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let (black, red) = plane_bytes(color);
+        self.mark_all_dirty();
+        self.buffer_black.fill(black);
+        self.buffer_red.fill(red);
         Ok(())
     }
 }
 
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> GetPixel
+    for Display<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    type Color = TriColor;
+
+    /// Returns the color of the pixel at point `p` in display coordinates
+    /// or `None` if the point is outside the display.
+    fn pixel(&self, p: Point) -> Option<Self::Color> {
+        self.pixel_rotated(self.rotation, p)
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>
+    Display<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    /// Fills `area` in display coordinates of `rotation` with `color`
+    #[inline]
+    pub(crate) fn fill_solid_rotated(
+        &mut self,
+        rotation: DisplayRotation,
+        area: &Rectangle,
+        color: TriColor,
+    ) {
+        let buffer_size = Size::new(SIZE_H, SIZE_V);
+        let area = area.intersection(&Rectangle::new(
+            Point::zero(),
+            rotation.display_size(buffer_size),
+        ));
+        let Some(bottom_right) = area.bottom_right() else {
+            return;
+        };
+        let (Some(a), Some(b)) = (
+            rotation.buffer_position(area.top_left, buffer_size),
+            rotation.buffer_position(bottom_right, buffer_size),
+        ) else {
+            return;
+        };
+
+        let (x_start, x_end) = (min(a.0, b.0), max(a.0, b.0));
+        let (y_start, y_end) = (min(a.1, b.1), max(a.1, b.1));
+        self.mark_dirty((x_start, y_start), (x_end, y_end));
+        for y in y_start..=y_end {
+            self.fill_span(y, x_start, x_end, color);
+        }
+    }
+
+    /// Returns the color of the pixel at point `p` in display coordinates of `rotation`
+    #[inline]
+    pub(crate) fn pixel_rotated(&self, rotation: DisplayRotation, p: Point) -> Option<TriColor> {
+        let position = rotation.buffer_position(p, Size::new(SIZE_H, SIZE_V))?;
+        packing::get_pixel(&self.buffer_black, &self.buffer_red, SIZE_H, position)
+    }
+}
+
 macro_rules! display_type {
     ($a:expr, $b:expr) => {
         Display<$a, $b, {$a * ($b / 8)}>
@@ -0,0 +1,269 @@
+//! Parametric status icons for dashboards on tri-color e-paper displays (feature `widgets`):
+//! battery, Wi-Fi and alert. The icons are drawn pixel by pixel without antialiasing and
+//! scale with their size. A state which needs attention, e.g. a low battery, is shown in the
+//! accent color, which is red by default and can be set to black for black and white panels.
+//! The icons are opaque (white background), so they can be redrawn in place.
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point, Size},
+    primitives::{PointsIter, Rectangle},
+    Drawable,
+};
+
+use crate::TriColor;
+
+/// Battery with a fill level, the fill is drawn in the accent color at low levels
+pub struct Battery {
+    area: Rectangle,
+    level: u8,
+    low_level: u8,
+    color: TriColor,
+    accent: TriColor,
+}
+
+impl Battery {
+    /// Level in percent up to which the fill is drawn in the accent color by default
+    pub const LOW_LEVEL: u8 = 20;
+
+    /// Creates a black battery within `area` with `level` in percent (limited to 100),
+    /// the terminal is on the right
+    #[must_use]
+    pub fn new(area: Rectangle, level: u8) -> Self {
+        Self {
+            area,
+            level: level.min(100),
+            low_level: Self::LOW_LEVEL,
+            color: TriColor::Black,
+            accent: TriColor::Red,
+        }
+    }
+
+    /// Sets the level in percent up to which the fill is drawn in the accent color
+    #[must_use]
+    pub fn with_low_level(self, low_level: u8) -> Self {
+        Self { low_level, ..self }
+    }
+
+    /// Sets the color of the outline and the fill
+    #[must_use]
+    pub fn with_color(self, color: TriColor) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Sets the color of the fill at low levels
+    #[must_use]
+    pub fn with_accent(self, accent: TriColor) -> Self {
+        Self { accent, ..self }
+    }
+}
+
+impl Dimensions for Battery {
+    fn bounding_box(&self) -> Rectangle {
+        self.area
+    }
+}
+
+impl Drawable for Battery {
+    type Color = TriColor;
+    type Output = ();
+
+    #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = TriColor>,
+    {
+        let Size { width, height } = self.area.size;
+        let top_left = self.area.top_left;
+        target.fill_solid(&self.area, TriColor::White)?;
+
+        let stroke = (height / 8).max(1);
+        let terminal_width = (width / 10).max(1);
+        let body = Size::new(width.saturating_sub(terminal_width), height);
+        let rect = |x: u32, y: u32, width: u32, height: u32| {
+            Rectangle::new(
+                top_left + Point::new(x as i32, y as i32),
+                Size::new(width, height),
+            )
+        };
+        for side in [
+            rect(0, 0, body.width, stroke),
+            rect(0, height.saturating_sub(stroke), body.width, stroke),
+            rect(0, 0, stroke, height),
+            rect(body.width.saturating_sub(stroke), 0, stroke, height),
+            rect(
+                body.width,
+                height / 4,
+                terminal_width,
+                height - 2 * (height / 4),
+            ),
+        ] {
+            target.fill_solid(&side, self.color)?;
+        }
+
+        // the fill keeps a gap of one stroke to the outline
+        let inner_width = body.width.saturating_sub(4 * stroke);
+        let fill_width = (u64::from(inner_width) * u64::from(self.level) / 100) as u32;
+        let fill_color = if self.level <= self.low_level {
+            self.accent
+        } else {
+            self.color
+        };
+        let fill = rect(
+            2 * stroke,
+            2 * stroke,
+            fill_width,
+            height.saturating_sub(4 * stroke),
+        );
+        target.fill_solid(&fill, fill_color)
+    }
+}
+
+/// Wi-Fi signal as a dot with up to three arcs above it. Without signal all arcs are drawn
+/// in the accent color to show that there is no connection.
+pub struct Wifi {
+    area: Rectangle,
+    strength: u8,
+    color: TriColor,
+    accent: TriColor,
+}
+
+impl Wifi {
+    /// Strength with all arcs
+    pub const MAX_STRENGTH: u8 = 3;
+
+    /// Creates a black icon with the given height and `strength` from 0 (no connection) to
+    /// [`Self::MAX_STRENGTH`]. The icon is `2 * height - 1` pixels wide.
+    #[must_use]
+    pub fn new(top_left: Point, height: u32, strength: u8) -> Self {
+        let height = height.max(1);
+        Self {
+            area: Rectangle::new(top_left, Size::new(2 * height - 1, height)),
+            strength: strength.min(Self::MAX_STRENGTH),
+            color: TriColor::Black,
+            accent: TriColor::Red,
+        }
+    }
+
+    /// Sets the color of the dot and the arcs
+    #[must_use]
+    pub fn with_color(self, color: TriColor) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Sets the color of the icon without connection
+    #[must_use]
+    pub fn with_accent(self, accent: TriColor) -> Self {
+        Self { accent, ..self }
+    }
+
+    /// Returns the color of the pixel at `p` relative to the top left corner
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+    fn color(&self, p: Point) -> TriColor {
+        let height = self.area.size.height;
+        let dx = p.x.abs_diff(height as i32 - 1);
+        let dy = height - 1 - p.y as u32;
+        // dot, gap, arc, gap, ... from the bottom center within a quarter circle
+        let band = (dx * dx + dy * dy).isqrt() * 7 / height;
+        let lit = dx <= dy
+            && band < 7
+            && band.is_multiple_of(2)
+            && (self.strength == 0 || band / 2 <= u32::from(self.strength));
+        match (lit, self.strength) {
+            (false, _) => TriColor::White,
+            (true, 0) => self.accent,
+            (true, _) => self.color,
+        }
+    }
+}
+
+impl Dimensions for Wifi {
+    fn bounding_box(&self) -> Rectangle {
+        self.area
+    }
+}
+
+impl Drawable for Wifi {
+    type Color = TriColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = TriColor>,
+    {
+        let origin = self.area.top_left;
+        target.fill_contiguous(
+            &self.area,
+            self.area.points().map(|p| self.color(p - origin)),
+        )
+    }
+}
+
+/// Warning triangle with an exclamation mark, drawn in the accent color
+pub struct Alert {
+    area: Rectangle,
+    accent: TriColor,
+}
+
+impl Alert {
+    /// Creates a red triangle with the given height, the width is the height rounded up
+    /// to an odd number of pixels
+    #[must_use]
+    pub fn new(top_left: Point, height: u32) -> Self {
+        let height = height.max(1);
+        Self {
+            area: Rectangle::new(top_left, Size::new(height | 1, height)),
+            accent: TriColor::Red,
+        }
+    }
+
+    /// Sets the color of the triangle
+    #[must_use]
+    pub fn with_accent(self, accent: TriColor) -> Self {
+        Self { accent, ..self }
+    }
+}
+
+impl Dimensions for Alert {
+    fn bounding_box(&self) -> Rectangle {
+        self.area
+    }
+}
+
+impl Drawable for Alert {
+    type Color = TriColor;
+    type Output = ();
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = TriColor>,
+    {
+        let Size { width, height } = self.area.size;
+        let top_left = self.area.top_left;
+        target.fill_solid(&self.area, TriColor::White)?;
+
+        let center = width / 2;
+        for y in 0..height {
+            let half = y * center / (height - 1).max(1);
+            let row = Rectangle::new(
+                top_left + Point::new((center - half) as i32, y as i32),
+                Size::new(2 * half + 1, 1),
+            );
+            target.fill_solid(&row, self.accent)?;
+        }
+
+        let stroke = (width / 8).max(1);
+        let x = (center - stroke / 2) as i32;
+        let bar = Rectangle::new(
+            top_left + Point::new(x, (height * 3 / 8) as i32),
+            Size::new(stroke, height / 4),
+        );
+        let dot = Rectangle::new(
+            top_left + Point::new(x, (height * 3 / 4) as i32),
+            Size::new(stroke, stroke),
+        );
+        target.fill_solid(&bar, TriColor::White)?;
+        target.fill_solid(&dot, TriColor::White)
+    }
+}
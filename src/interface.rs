@@ -0,0 +1,113 @@
+//! Adapter for transports implementing `WriteOnlyDataCommand` of `display-interface`
+//! (feature `display-interface`), e.g. SPI via an IO expander or test doubles
+
+use core::cell::{Cell, RefCell};
+use core::convert::Infallible;
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::{
+    digital::{self, OutputPin},
+    spi::{self, ErrorKind, Operation, SpiDevice},
+};
+
+/// Error of the transport of an [`Interface`]
+#[derive(Clone, Debug)]
+pub enum InterfaceError {
+    /// Error of the display interface
+    Interface(DisplayError),
+    /// The transaction contains operations other than writes, which are not supported
+    Unsupported,
+}
+
+impl spi::Error for InterfaceError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// Transport of a display interface, which provides the SPI device and the DC pin for the
+/// driver. Commands and data are sent with `send_commands` resp. `send_data` depending on
+/// the state of the DC pin, so the driver works without changes:
+/// ```ignore
+/// let interface = Interface::new(display_interface);
+/// let (mut spi, dc) = interface.split();
+/// let epd = Epd::new(&mut spi, busy, dc, rst, &mut delay, 0);
+/// ```
+pub struct Interface<DI> {
+    interface: RefCell<DI>,
+    /// true while the DC pin is low
+    command: Cell<bool>,
+}
+
+impl<DI: WriteOnlyDataCommand> Interface<DI> {
+    /// Creates the transport for `interface`
+    pub fn new(interface: DI) -> Self {
+        Self {
+            interface: RefCell::new(interface),
+            command: Cell::new(false),
+        }
+    }
+
+    /// Returns the SPI device and the DC pin to pass to the driver
+    pub fn split(&self) -> (InterfaceSpi<'_, DI>, InterfaceDc<'_>) {
+        (
+            InterfaceSpi { transport: self },
+            InterfaceDc {
+                command: &self.command,
+            },
+        )
+    }
+
+    /// Returns the display interface
+    pub fn into_inner(self) -> DI {
+        self.interface.into_inner()
+    }
+}
+
+/// SPI device of an [`Interface`]
+pub struct InterfaceSpi<'a, DI> {
+    transport: &'a Interface<DI>,
+}
+
+impl<DI> spi::ErrorType for InterfaceSpi<'_, DI> {
+    type Error = InterfaceError;
+}
+
+impl<DI: WriteOnlyDataCommand> SpiDevice for InterfaceSpi<'_, DI> {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        let mut interface = self.transport.interface.borrow_mut();
+        for operation in operations {
+            let Operation::Write(words) = operation else {
+                return Err(InterfaceError::Unsupported);
+            };
+            if self.transport.command.get() {
+                interface.send_commands(DataFormat::U8(words))
+            } else {
+                interface.send_data(DataFormat::U8(words))
+            }
+            .map_err(InterfaceError::Interface)?;
+        }
+        Ok(())
+    }
+}
+
+/// DC pin of an [`Interface`], which selects between commands (low) and data (high)
+pub struct InterfaceDc<'a> {
+    command: &'a Cell<bool>,
+}
+
+impl digital::ErrorType for InterfaceDc<'_> {
+    type Error = Infallible;
+}
+
+impl OutputPin for InterfaceDc<'_> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.command.set(true);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.command.set(false);
+        Ok(())
+    }
+}
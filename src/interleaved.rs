@@ -0,0 +1,228 @@
+//! Display buffers with an interleaved layout (2 bits per pixel) as alternative to [`Display`](crate::Display)
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    image::GetPixel,
+    pixelcolor::raw::{RawData, RawU2},
+    primitives::PointsIter,
+    Pixel,
+};
+
+use crate::{graphics::write_chunked, DisplayBuffer, DisplayRotation, TriColor};
+
+/// Display buffer used for drawing with `embedded_graphics`. In contrast to
+/// [`Display`](crate::Display) the pixels are stored with 2 bits per pixel in one buffer
+/// (4 pixels per byte, MSB first) using the raw encoding of [`TriColor`]. Drawing a pixel
+/// needs only one read-modify-write and the buffer can be used like an
+/// `ImageRaw<TriColor>`. The black and red planes are created on the fly while sending
+/// the buffer to the e-paper.
+/// The concrete types are dependent on the size.
+/// Examples: `InterleavedDisplay1in54`, `InterleavedDisplay2in13`, ...
+pub struct InterleavedDisplay<const SIZE_V: u32, const SIZE_H: u32, const BUFFER_SIZE: usize> {
+    buffer: [u8; BUFFER_SIZE],
+    rotation: DisplayRotation,
+    inverted: bool,
+    chromatic_as_black: bool,
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const BUFFER_SIZE: usize>
+    InterleavedDisplay<SIZE_V, SIZE_H, BUFFER_SIZE>
+{
+    /// Checks at compile time that `BUFFER_SIZE` matches the size, it is evaluated when a
+    /// display buffer is created. So e.g. `InterleavedDisplay<296, 152, 5000>` fails to compile:
+    /// ```compile_fail
+    /// let display = epd_spectra::InterleavedDisplay::<296, 152, 5000>::new();
+    /// ```
+    pub(crate) const SIZE_CHECK: () = assert!(
+        SIZE_H.is_multiple_of(8) && BUFFER_SIZE == SIZE_V as usize * (SIZE_H as usize / 4),
+        "BUFFER_SIZE does not match SIZE_V * SIZE_H / 4"
+    );
+
+    /// Width of the unrotated panel in pixels, see [`Display::WIDTH`](crate::Display::WIDTH)
+    pub const WIDTH: u32 = SIZE_H;
+    /// Height of the unrotated panel in pixels
+    pub const HEIGHT: u32 = SIZE_V;
+    /// Number of bytes of a row of the planes which are sent to the e-paper, the buffer has
+    /// twice as many bytes per row
+    pub const BYTES_PER_ROW: usize = SIZE_H as usize / 8;
+    /// Number of bytes of each plane which is sent to the e-paper, half of `BUFFER_SIZE`
+    pub const PLANE_SIZE: usize = SIZE_V as usize * Self::BYTES_PER_ROW;
+
+    /// Creates an empty (white) display buffer
+    #[must_use]
+    pub const fn new() -> Self {
+        let () = Self::SIZE_CHECK;
+        Self {
+            buffer: [0; BUFFER_SIZE],
+            rotation: DisplayRotation::Rotate0,
+            inverted: false,
+            chromatic_as_black: false,
+        }
+    }
+
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) {
+        self.rotation = rotation;
+    }
+    #[must_use]
+    pub fn rotation(&self) -> DisplayRotation {
+        self.rotation
+    }
+
+    /// Swaps black and white when the buffer is sent to the e-paper, red stays red.
+    /// The content of the buffer is not changed.
+    pub fn set_inverted(&mut self, inverted: bool) {
+        self.inverted = inverted;
+    }
+    #[must_use]
+    pub fn inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Shows red pixels as black when the buffer is sent to the e-paper, e.g. to share
+    /// drawing code with black and white displays. The content of the buffer is not changed.
+    pub fn set_chromatic_as_black(&mut self, chromatic_as_black: bool) {
+        self.chromatic_as_black = chromatic_as_black;
+    }
+    #[must_use]
+    pub fn chromatic_as_black(&self) -> bool {
+        self.chromatic_as_black
+    }
+
+    /// Returns the raw buffer with 2 bits per pixel in unrotated panel orientation
+    #[must_use]
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Returns an iterator over all pixels of the display in display coordinates.
+    /// The pixels are yielded row by row, from top left to bottom right.
+    pub fn pixels(&self) -> impl Iterator<Item = Pixel<TriColor>> + '_ {
+        self.bounding_box()
+            .points()
+            .filter_map(|p| self.pixel(p).map(|color| Pixel(p, color)))
+    }
+
+    fn buffer_position(&self, p: Point) -> Option<(usize, usize)> {
+        self.rotation.buffer_position(p, Size::new(SIZE_H, SIZE_V))
+    }
+
+    /// Returns the byte index and the bit shift of the pixel at buffer position (x, y)
+    fn byte_position(x: usize, y: usize) -> (usize, usize) {
+        (y * SIZE_H as usize / 4 + x / 4, 6 - 2 * (x % 4))
+    }
+
+    /// Returns the bytes of a plane, see [`plane_byte`]
+    fn plane(&self, is_set: fn(u8) -> bool) -> impl Iterator<Item = u8> + '_ {
+        self.buffer
+            .chunks_exact(2)
+            .map(move |pixels| plane_byte(pixels, is_set))
+    }
+}
+
+/// Converts 8 interleaved pixels (2 bytes) into a byte of a plane. A bit is set if `is_set`
+/// returns true for the raw value of the pixel.
+fn plane_byte(pixels: &[u8], is_set: fn(u8) -> bool) -> u8 {
+    (0..8).fold(0, |byte, i| {
+        let value = pixels
+            .get(i / 4)
+            .map_or(0, |pixels| (pixels >> (6 - 2 * (i % 4))) & 0b11);
+        (byte << 1) | u8::from(is_set(value))
+    })
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const BUFFER_SIZE: usize> DisplayBuffer
+    for InterleavedDisplay<SIZE_V, SIZE_H, BUFFER_SIZE>
+{
+    fn write_black<E>(&self, write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        if self.inverted {
+            write_chunked(self.plane(|value| value == 0b00), write)
+        } else if self.chromatic_as_black {
+            write_chunked(self.plane(|value| value != 0b00), write)
+        } else {
+            write_chunked(self.plane(|value| value & 0b01 != 0), write)
+        }
+    }
+    fn write_red<E>(&self, write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        if self.chromatic_as_black {
+            write_chunked(self.plane(|_| false), write)
+        } else {
+            write_chunked(self.plane(|value| value == 0b10), write)
+        }
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const BUFFER_SIZE: usize> Default
+    for InterleavedDisplay<SIZE_V, SIZE_H, BUFFER_SIZE>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const BUFFER_SIZE: usize> OriginDimensions
+    for InterleavedDisplay<SIZE_V, SIZE_H, BUFFER_SIZE>
+{
+    fn size(&self) -> Size {
+        self.rotation.display_size(Size::new(SIZE_H, SIZE_V))
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const BUFFER_SIZE: usize> DrawTarget
+    for InterleavedDisplay<SIZE_V, SIZE_H, BUFFER_SIZE>
+{
+    type Color = TriColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(p, color) in pixels {
+            let Some((x, y)) = self.buffer_position(p) else {
+                continue;
+            };
+            let (index, shift) = Self::byte_position(x, y);
+            let value = RawU2::from(color).into_inner();
+            if let Some(byte) = self.buffer.get_mut(index) {
+                *byte = (*byte & !(0b11 << shift)) | (value << shift);
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let value = RawU2::from(color).into_inner();
+        self.buffer.fill(value * 0b0101_0101);
+        Ok(())
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const BUFFER_SIZE: usize> GetPixel
+    for InterleavedDisplay<SIZE_V, SIZE_H, BUFFER_SIZE>
+{
+    type Color = TriColor;
+
+    /// Returns the color of the pixel at point `p` in display coordinates
+    /// or `None` if the point is outside the display.
+    fn pixel(&self, p: Point) -> Option<Self::Color> {
+        let (x, y) = self.buffer_position(p)?;
+        let (index, shift) = Self::byte_position(x, y);
+        Some(RawU2::new((self.buffer.get(index)? >> shift) & 0b11).into())
+    }
+}
+
+macro_rules! interleaved_display_type {
+    ($a:expr, $b:expr) => {
+        InterleavedDisplay<$a, $b, {$a * ($b / 4)}>
+    };
+}
+pub type InterleavedDisplay1in54 = interleaved_display_type!(152, 152);
+pub type InterleavedDisplay2in13 = interleaved_display_type!(212, 104);
+pub type InterleavedDisplay2in66 = interleaved_display_type!(296, 152);
+pub type InterleavedDisplay2in71 = interleaved_display_type!(264, 176);
+pub type InterleavedDisplay2in87 = interleaved_display_type!(296, 128);
+pub type InterleavedDisplay3in70 = interleaved_display_type!(416, 240);
+pub type InterleavedDisplay4in17 = interleaved_display_type!(300, 400);
+pub type InterleavedDisplay4in37 = interleaved_display_type!(480, 176);
+pub type InterleavedDisplay2in9 = interleaved_display_type!(384, 168);
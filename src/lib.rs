@@ -3,13 +3,91 @@
 //! [Pervasive Displays Inc](https://github.com/PervasiveDisplays).
 //!
 //! See the examples folder to get started.
+//!
+//! [`Epd`] is the driver, it uses typestates for the power state of the e-paper and gets the
+//! SPI device and the delay with each call. Frames are drawn with embedded-graphics on
+//! display buffers like [`Display2in66`], [`MonoDisplay2in66`] or [`Display2in66Landscape`].
+//! Code which is generic over the panel uses [`EpdPanel`], and [`EpdDisplay`] owns its
+//! display buffer like the drivers of other displays. Optional parts, e.g. the widgets, the
+//! image conversion or the browser preview, are behind cargo features named in their modules.
+//!
+//! The library does not panic: it contains no unwraps, indexing or slicing which could fail,
+//! which is enforced by the clippy lints below. CI checks that the `no_std` example links
+//! without any panic.
 #![no_std]
+#![deny(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::unreachable,
+    clippy::indexing_slicing
+)]
 
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "embedded-sdmmc")]
+pub mod bmp;
+#[cfg(feature = "std")]
+pub mod c_array;
+pub mod compressed;
+#[cfg(feature = "image")]
+pub mod convert;
+pub mod cropped;
+pub mod dither;
+pub mod double_buffer;
 pub mod driver;
+pub mod ext3;
 pub mod graphics;
+#[cfg(feature = "widgets")]
+pub mod icons;
+#[cfg(feature = "display-interface")]
+pub mod interface;
+pub mod interleaved;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod mono;
+pub mod oriented;
+pub mod packing;
+pub mod panel;
+pub mod planar_image;
+pub mod refresh_policy;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "simulator")]
+pub mod simulator;
+#[cfg(feature = "slint")]
+pub mod slint_renderer;
+pub mod spi_bus;
+pub mod sprite;
+pub mod strip;
+pub mod terminal;
+pub mod test_pattern;
+pub mod tiled;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "widgets")]
+pub mod widgets;
 
+#[cfg(feature = "embedded-sdmmc")]
+pub use bmp::*;
+pub use compressed::*;
+pub use cropped::*;
+pub use dither::*;
+pub use double_buffer::*;
 pub use driver::*;
 pub use graphics::*;
+#[cfg(feature = "display-interface")]
+pub use interface::*;
+pub use interleaved::*;
+pub use mono::*;
+pub use oriented::*;
+pub use panel::*;
+pub use planar_image::*;
+pub use refresh_policy::*;
+pub use spi_bus::*;
+pub use sprite::*;
+pub use strip::*;
+pub use terminal::*;
+pub use test_pattern::*;
+pub use tiled::*;
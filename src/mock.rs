@@ -0,0 +1,351 @@
+//! Mocks of the SPI device, the pins and the delay which record what the driver sends
+//! (feature `mock`), e.g. to test display logic on the host without hardware:
+//! ```ignore
+//! let log = MockLog::default();
+//! let (mut spi, mut delay) = (log.spi(), log.delay());
+//! let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+//! let mut epd = epd.init(&mut spi, &mut delay)?;
+//! epd.update(&display, &mut spi, &mut delay)?;
+//! assert_eq!(log.commands().last().map(|c| c.command), Some(0x12)); // refresh
+//! let frame: Display2in66 = log.frame().unwrap(); // content of the panel
+//! ```
+
+use core::{convert::Infallible, fmt};
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{ErrorType as DigitalErrorType, InputPin, OutputPin},
+    spi::{Error as SpiError, ErrorKind, ErrorType as SpiErrorType, Operation, SpiDevice},
+};
+use std::{
+    cell::{RefCell, RefMut},
+    rc::Rc,
+    vec::Vec,
+};
+
+use crate::{driver::Command, Display};
+
+/// Data of the command `Psr` which resets the controller by software
+const SOFT_RESET: u8 = 0x0e;
+
+/// Event recorded by the mocks
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum MockEvent {
+    /// Bytes written via SPI while the DC pin was low
+    Command(Vec<u8>),
+    /// Bytes written via SPI while the DC pin was high
+    Data(Vec<u8>),
+    /// Level of the reset pin was set (true: high)
+    Reset(bool),
+    /// Delay in nanoseconds
+    Delay(u32),
+}
+
+/// Command with the data sent after it, see [`MockLog::commands`]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MockCommand {
+    pub command: u8,
+    pub data: Vec<u8>,
+}
+
+#[derive(Default)]
+struct MockState {
+    events: Vec<MockEvent>,
+    dc_high: bool,
+    busy: bool,
+    busy_polls: u32,
+    transactions: usize,
+    last_command: Option<u8>,
+    reset_busy_polls: u32,
+    /// Command whose data transactions fail and the number of remaining failures
+    failures: Option<(u8, u32)>,
+}
+
+/// Shared log of the mocks. The mocks are created from the log and record their events in it,
+/// so it can be inspected after the mocks have been moved into the driver.
+#[derive(Clone, Default)]
+pub struct MockLog(Rc<RefCell<MockState>>);
+
+impl MockLog {
+    #[must_use]
+    pub fn spi(&self) -> MockSpi {
+        MockSpi(self.clone())
+    }
+    #[must_use]
+    pub fn busy(&self) -> MockBusy {
+        MockBusy(self.clone())
+    }
+    #[must_use]
+    pub fn dc(&self) -> MockDc {
+        MockDc(self.clone())
+    }
+    #[must_use]
+    pub fn rst(&self) -> MockRst {
+        MockRst(self.clone())
+    }
+    #[must_use]
+    pub fn delay(&self) -> MockDelay {
+        MockDelay(self.clone())
+    }
+
+    /// Sets whether the busy pin signals that the display is busy (low). The display is never
+    /// busy by default, otherwise the driver runs into its timeout.
+    pub fn set_busy(&self, busy: bool) {
+        let mut state = self.0.borrow_mut();
+        state.busy = busy;
+        state.busy_polls = 0;
+    }
+
+    /// Lets the busy pin signal busy for the next `polls` reads, afterwards the display is not
+    /// busy anymore, e.g. to simulate a refresh which takes some time
+    pub fn set_busy_polls(&self, polls: u32) {
+        let mut state = self.0.borrow_mut();
+        state.busy = polls > 0;
+        state.busy_polls = polls;
+    }
+
+    /// Lets the busy pin signal busy for `polls` reads after each soft reset, like a panel
+    /// which is fitted. By default it stays inactive, like the pulled up pin without a panel.
+    pub fn set_busy_after_reset(&self, polls: u32) {
+        self.0.borrow_mut().reset_busy_polls = polls;
+    }
+
+    /// Lets the next `count` SPI transactions fail which write data of `command`, e.g. of the
+    /// black plane (`0x10`), to simulate glitches during the transfer of a frame. Failed
+    /// transactions are counted but not recorded.
+    pub fn fail_data_of(&self, command: u8, count: u32) {
+        self.0.borrow_mut().failures = Some((command, count));
+    }
+
+    /// Returns all recorded events
+    #[must_use]
+    pub fn events(&self) -> Vec<MockEvent> {
+        self.0.borrow().events.clone()
+    }
+
+    /// Returns the number of SPI transactions, each single write counts as one transaction
+    #[must_use]
+    pub fn transactions(&self) -> usize {
+        self.0.borrow().transactions
+    }
+
+    /// Removes all recorded events and resets the number of transactions, e.g. after
+    /// initialization
+    pub fn clear(&self) {
+        let mut state = self.0.borrow_mut();
+        state.events.clear();
+        state.transactions = 0;
+    }
+
+    /// Returns the recorded commands, each with the data which was sent until the next command
+    #[must_use]
+    pub fn commands(&self) -> Vec<MockCommand> {
+        let mut commands: Vec<MockCommand> = Vec::new();
+        for event in &self.0.borrow().events {
+            match event {
+                MockEvent::Command(bytes) => {
+                    commands.extend(bytes.iter().map(|&command| MockCommand {
+                        command,
+                        data: Vec::new(),
+                    }));
+                }
+                MockEvent::Data(bytes) => {
+                    if let Some(command) = commands.last_mut() {
+                        command.data.extend_from_slice(bytes);
+                    }
+                }
+                MockEvent::Reset(_) | MockEvent::Delay(_) => {}
+            }
+        }
+        commands
+    }
+
+    /// Returns the frame which the panel shows after the last refresh, reconstructed from the
+    /// buffers sent before it, or `None` if there was no refresh or the buffers do not match the
+    /// size of the display. This allows end-to-end tests: draw, run the driver against the mocks
+    /// and check the resulting pixels, e.g. with `GetPixel`.
+    #[must_use]
+    pub fn frame<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>(
+        &self,
+    ) -> Option<Display<SIZE_V, SIZE_H, IMAGE_SIZE>> {
+        let (mut black, mut red, mut shown) = (None, None, None);
+        for command in self.commands() {
+            match command.command {
+                c if c == Command::BufferBlack as u8 => black = Some(command.data),
+                c if c == Command::BufferRed as u8 => red = Some(command.data),
+                c if c == Command::Refresh as u8 => shown = black.clone().zip(red.clone()),
+                _ => {}
+            }
+        }
+        let (black, red) = shown?;
+        Display::from_raw_planes(&black, &red)
+    }
+
+    /// Returns the number of refreshes of the panel
+    #[must_use]
+    pub fn refreshes(&self) -> usize {
+        self.commands()
+            .iter()
+            .filter(|command| command.command == Command::Refresh as u8)
+            .count()
+    }
+
+    fn state(&self) -> RefMut<'_, MockState> {
+        self.0.borrow_mut()
+    }
+
+    fn push(&self, event: MockEvent) {
+        self.state().events.push(event);
+    }
+}
+
+/// Mock of the SPI device
+pub struct MockSpi(MockLog);
+
+/// Error of [`MockSpi`] injected by [`MockLog::fail_data_of`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MockSpiError;
+
+impl SpiError for MockSpiError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl fmt::Display for MockSpiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("injected SPI error")
+    }
+}
+
+impl std::error::Error for MockSpiError {}
+
+impl SpiErrorType for MockSpi {
+    type Error = MockSpiError;
+}
+
+impl SpiDevice for MockSpi {
+    /// Records written bytes, read bytes are 0
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        {
+            let mut state = self.0.state();
+            state.transactions += 1;
+            if let Some((command, count)) = state.failures {
+                if count > 0 && state.dc_high && state.last_command == Some(command) {
+                    state.failures = Some((command, count - 1));
+                    return Err(MockSpiError);
+                }
+            }
+        }
+        for operation in operations {
+            let written = match operation {
+                Operation::Write(data) => data.to_vec(),
+                Operation::Transfer(read, write) => {
+                    read.fill(0);
+                    write.to_vec()
+                }
+                Operation::TransferInPlace(data) => {
+                    let written = data.to_vec();
+                    data.fill(0);
+                    written
+                }
+                Operation::Read(read) => {
+                    read.fill(0);
+                    continue;
+                }
+                Operation::DelayNs(ns) => {
+                    self.0.push(MockEvent::Delay(*ns));
+                    continue;
+                }
+            };
+            let mut state = self.0.state();
+            let event = if state.dc_high {
+                let soft_reset =
+                    state.last_command == Some(Command::Psr as u8) && written == [SOFT_RESET];
+                if soft_reset && state.reset_busy_polls > 0 && !state.busy {
+                    state.busy = true;
+                    state.busy_polls = state.reset_busy_polls;
+                }
+                MockEvent::Data(written)
+            } else {
+                state.last_command = written.last().copied().or(state.last_command);
+                MockEvent::Command(written)
+            };
+            state.events.push(event);
+        }
+        Ok(())
+    }
+}
+
+/// Mock of the busy pin
+pub struct MockBusy(MockLog);
+
+impl DigitalErrorType for MockBusy {
+    type Error = Infallible;
+}
+
+impl MockBusy {
+    /// Reads the busy state, counting down the polls set by `MockLog::set_busy_polls`
+    fn poll(&self) -> bool {
+        let mut state = self.0.state();
+        let busy = state.busy;
+        if state.busy_polls > 0 {
+            state.busy_polls -= 1;
+            state.busy = state.busy_polls > 0;
+        }
+        busy
+    }
+}
+
+impl InputPin for MockBusy {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.poll())
+    }
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.poll())
+    }
+}
+
+/// Mock of the DC pin, which determines whether SPI writes are recorded as command or data
+pub struct MockDc(MockLog);
+
+impl DigitalErrorType for MockDc {
+    type Error = Infallible;
+}
+
+impl OutputPin for MockDc {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.state().dc_high = false;
+        Ok(())
+    }
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.state().dc_high = true;
+        Ok(())
+    }
+}
+
+/// Mock of the reset pin
+pub struct MockRst(MockLog);
+
+impl DigitalErrorType for MockRst {
+    type Error = Infallible;
+}
+
+impl OutputPin for MockRst {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.push(MockEvent::Reset(false));
+        Ok(())
+    }
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.push(MockEvent::Reset(true));
+        Ok(())
+    }
+}
+
+/// Mock of the delay
+pub struct MockDelay(MockLog);
+
+impl DelayNs for MockDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.push(MockEvent::Delay(ns));
+    }
+}
@@ -0,0 +1,163 @@
+//! Display buffer without the chromatic plane for black and white panels
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    image::GetPixel,
+    Pixel,
+};
+
+use crate::{graphics::write_chunked, packing, Display, DisplayBuffer, DisplayRotation, TriColor};
+
+/// Display buffer with only the black plane, e.g. for products with black and white panels,
+/// which needs half the RAM of a [`Display`](crate::Display). Red is drawn as black, so
+/// drawing code can be shared with tri-color displays and it is clipped like for `Display`.
+/// The chromatic plane is sent as white.
+/// The const parameters are the same as for `Display` and are checked at compile time,
+/// e.g. [`MonoDisplay2in66`].
+pub struct MonoDisplay<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> {
+    buffer: [u8; IMAGE_SIZE],
+    rotation: DisplayRotation,
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>
+    MonoDisplay<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    /// Width of the unrotated panel in pixels, see [`Display::WIDTH`]
+    pub const WIDTH: u32 = SIZE_H;
+    /// Height of the unrotated panel in pixels, see [`Display::HEIGHT`]
+    pub const HEIGHT: u32 = SIZE_V;
+    /// Number of bytes of a row of the black plane
+    pub const BYTES_PER_ROW: usize = SIZE_H as usize / 8;
+    /// Number of bytes of the black plane, the driver sends a chromatic plane of the same size
+    pub const PLANE_SIZE: usize = IMAGE_SIZE;
+
+    /// Creates an empty (white) display buffer, which ends up in `.bss` if it is static
+    #[must_use]
+    pub const fn new() -> Self {
+        let () = Display::<SIZE_V, SIZE_H, IMAGE_SIZE>::SIZE_CHECK;
+        Self {
+            buffer: [0; IMAGE_SIZE],
+            rotation: DisplayRotation::Rotate0,
+        }
+    }
+
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) {
+        self.rotation = rotation;
+    }
+    #[must_use]
+    pub fn rotation(&self) -> DisplayRotation {
+        self.rotation
+    }
+
+    /// Returns the black plane of the buffer, see `Display::black_plane` for the layout
+    #[must_use]
+    pub fn black_plane(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Returns the black plane of the buffer as mutable slice
+    pub fn black_plane_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
+
+    /// Returns the byte index and the bit mask of the pixel at point `p` in display coordinates
+    fn byte_position(&self, p: Point) -> Option<(usize, u8)> {
+        let (x, y) = self
+            .rotation
+            .buffer_position(p, Size::new(SIZE_H, SIZE_V))?;
+        Some(packing::byte_position(x, y, SIZE_H))
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> Default
+    for MonoDisplay<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> DisplayBuffer
+    for MonoDisplay<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    fn get_buffer_black(&self) -> &[u8] {
+        &self.buffer
+    }
+    fn write_black<E>(&self, mut write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        write(&self.buffer)
+    }
+    fn write_red<E>(&self, write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        write_chunked(core::iter::repeat_n(0, IMAGE_SIZE), write)
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> OriginDimensions
+    for MonoDisplay<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    fn size(&self) -> Size {
+        self.rotation.display_size(Size::new(SIZE_H, SIZE_V))
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> DrawTarget
+    for MonoDisplay<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    type Color = TriColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(p, color) in pixels {
+            let Some((index, mask)) = self.byte_position(p) else {
+                continue;
+            };
+            if let Some(byte) = self.buffer.get_mut(index) {
+                if color == TriColor::White {
+                    *byte &= !mask;
+                } else {
+                    *byte |= mask;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let byte = if color == TriColor::White { 0x00 } else { 0xff };
+        self.buffer.fill(byte);
+        Ok(())
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> GetPixel
+    for MonoDisplay<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    type Color = TriColor;
+
+    fn pixel(&self, p: Point) -> Option<Self::Color> {
+        let (index, mask) = self.byte_position(p)?;
+        Some(if self.buffer.get(index)? & mask == 0 {
+            TriColor::White
+        } else {
+            TriColor::Black
+        })
+    }
+}
+
+macro_rules! mono_display_type {
+    ($a:expr, $b:expr) => {
+        MonoDisplay<$a, $b, {$a * ($b / 8)}>
+    };
+}
+pub type MonoDisplay1in54 = mono_display_type!(152, 152);
+pub type MonoDisplay2in13 = mono_display_type!(212, 104);
+pub type MonoDisplay2in66 = mono_display_type!(296, 152);
+pub type MonoDisplay2in71 = mono_display_type!(264, 176);
+pub type MonoDisplay2in87 = mono_display_type!(296, 128);
+pub type MonoDisplay3in70 = mono_display_type!(416, 240);
+pub type MonoDisplay4in17 = mono_display_type!(300, 400);
+pub type MonoDisplay4in37 = mono_display_type!(480, 176);
+pub type MonoDisplay2in9 = mono_display_type!(384, 168);
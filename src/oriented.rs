@@ -0,0 +1,229 @@
+//! Display buffers with an orientation which is fixed by their type
+
+use core::{marker::PhantomData, ops::Deref};
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    image::GetPixel,
+    primitives::{PointsIter, Rectangle},
+    Pixel,
+};
+
+use crate::{Display, DisplayBuffer, DisplayRotation, TriColor};
+
+/// Orientation of an [`OrientedDisplay`], implemented by [`Deg0`], [`Deg90`], [`Deg180`]
+/// and [`Deg270`]
+pub trait Orientation {
+    /// Clockwise rotation of the display
+    const ROTATION: DisplayRotation;
+}
+
+/// No rotation
+pub struct Deg0;
+/// Rotation by 90 degrees clockwise
+pub struct Deg90;
+/// Rotation by 180 degrees clockwise
+pub struct Deg180;
+/// Rotation by 270 degrees clockwise
+pub struct Deg270;
+
+impl Orientation for Deg0 {
+    const ROTATION: DisplayRotation = DisplayRotation::Rotate0;
+}
+impl Orientation for Deg90 {
+    const ROTATION: DisplayRotation = DisplayRotation::Rotate90;
+}
+impl Orientation for Deg180 {
+    const ROTATION: DisplayRotation = DisplayRotation::Rotate180;
+}
+impl Orientation for Deg270 {
+    const ROTATION: DisplayRotation = DisplayRotation::Rotate270;
+}
+
+/// [`Display`] whose rotation is given by the orientation `O` and cannot be changed, e.g.
+/// [`Display2in66Landscape`]. So layout code does not have to handle the rotation. The
+/// methods of `Display` which do not change the rotation are available by deref or forwarded.
+/// Drawing uses the constant rotation, so its conversion is resolved at compile time, and the
+/// size is available as [`Self::WIDTH`] and [`Self::HEIGHT`] for layout constants. In contrast
+/// to [`Display::WIDTH`] they are in display coordinates, i.e. with the rotation:
+/// ```ignore
+/// const COLUMN: u32 = Display2in66Landscape::WIDTH / 2;
+/// ```
+pub struct OrientedDisplay<
+    O: Orientation,
+    const SIZE_V: u32,
+    const SIZE_H: u32,
+    const IMAGE_SIZE: usize,
+> {
+    display: Display<SIZE_V, SIZE_H, IMAGE_SIZE>,
+    orientation: PhantomData<O>,
+}
+
+impl<O: Orientation, const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>
+    OrientedDisplay<O, SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    /// Size in display coordinates, i.e. with the rotation
+    pub const SIZE: Size = O::ROTATION.display_size(Size::new(SIZE_H, SIZE_V));
+    /// Width in display coordinates
+    pub const WIDTH: u32 = Self::SIZE.width;
+    /// Height in display coordinates
+    pub const HEIGHT: u32 = Self::SIZE.height;
+    /// See [`Display::BYTES_PER_ROW`]
+    pub const BYTES_PER_ROW: usize = Display::<SIZE_V, SIZE_H, IMAGE_SIZE>::BYTES_PER_ROW;
+    /// See [`Display::PLANE_SIZE`]
+    pub const PLANE_SIZE: usize = IMAGE_SIZE;
+
+    /// Creates an empty (white) display buffer
+    #[must_use]
+    pub const fn new() -> Self {
+        let mut display = Display::new();
+        display.set_rotation(O::ROTATION);
+        Self {
+            display,
+            orientation: PhantomData,
+        }
+    }
+
+    /// See [`Display::set_inverted`]
+    pub fn set_inverted(&mut self, inverted: bool) {
+        self.display.set_inverted(inverted);
+    }
+
+    /// See [`Display::set_chromatic_as_black`]
+    pub fn set_chromatic_as_black(&mut self, chromatic_as_black: bool) {
+        self.display.set_chromatic_as_black(chromatic_as_black);
+    }
+
+    /// See [`Display::take_dirty_region`]
+    pub fn take_dirty_region(&mut self) -> Option<Rectangle> {
+        self.display.take_dirty_region()
+    }
+
+    /// See [`Display::scroll_up`]
+    pub fn scroll_up(&mut self, rows: u32, color: TriColor) -> Rectangle {
+        self.display.scroll_up(rows, color)
+    }
+
+    /// See [`Display::scroll_down`]
+    pub fn scroll_down(&mut self, rows: u32, color: TriColor) -> Rectangle {
+        self.display.scroll_down(rows, color)
+    }
+
+    /// See [`Display::draw_bitmap`]
+    pub fn draw_bitmap(&mut self, bitmap: &[u8], width: u32, top_left: Point, color: TriColor) {
+        self.display.draw_bitmap(bitmap, width, top_left, color);
+    }
+
+    /// Returns the display buffer, which keeps the rotation
+    #[must_use]
+    pub fn into_inner(self) -> Display<SIZE_V, SIZE_H, IMAGE_SIZE> {
+        self.display
+    }
+}
+
+impl<O: Orientation, const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> Default
+    for OrientedDisplay<O, SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O: Orientation, const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> Deref
+    for OrientedDisplay<O, SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    type Target = Display<SIZE_V, SIZE_H, IMAGE_SIZE>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.display
+    }
+}
+
+impl<O: Orientation, const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> DisplayBuffer
+    for OrientedDisplay<O, SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    #[allow(deprecated)]
+    fn get_buffer_black(&self) -> &[u8] {
+        self.display.get_buffer_black()
+    }
+    #[allow(deprecated)]
+    fn get_buffer_red(&self) -> &[u8] {
+        self.display.get_buffer_red()
+    }
+    fn write_black<E>(&self, write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        self.display.write_black(write)
+    }
+    fn write_red<E>(&self, write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        self.display.write_red(write)
+    }
+}
+
+impl<O: Orientation, const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> OriginDimensions
+    for OrientedDisplay<O, SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    fn size(&self) -> Size {
+        Self::SIZE
+    }
+}
+
+impl<O: Orientation, const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> DrawTarget
+    for OrientedDisplay<O, SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    type Color = TriColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.display.draw_rotated(O::ROTATION, pixels);
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.display.draw_rotated(
+            O::ROTATION,
+            area.points().zip(colors).map(|(p, color)| Pixel(p, color)),
+        );
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.display.fill_solid_rotated(O::ROTATION, area, color);
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.display.clear(color)
+    }
+}
+
+impl<O: Orientation, const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> GetPixel
+    for OrientedDisplay<O, SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    type Color = TriColor;
+
+    fn pixel(&self, p: Point) -> Option<Self::Color> {
+        self.display.pixel_rotated(O::ROTATION, p)
+    }
+}
+
+macro_rules! landscape_type {
+    ($o:ty, $a:expr, $b:expr) => {
+        OrientedDisplay<$o, $a, $b, {$a * ($b / 8)}>
+    };
+}
+// the panels are portrait in their unrotated orientation, except the 4.17" and the square 1.54"
+pub type Display1in54Landscape = landscape_type!(Deg0, 152, 152);
+pub type Display2in13Landscape = landscape_type!(Deg90, 212, 104);
+pub type Display2in66Landscape = landscape_type!(Deg90, 296, 152);
+pub type Display2in71Landscape = landscape_type!(Deg90, 264, 176);
+pub type Display2in87Landscape = landscape_type!(Deg90, 296, 128);
+pub type Display3in70Landscape = landscape_type!(Deg90, 416, 240);
+pub type Display4in17Landscape = landscape_type!(Deg0, 300, 400);
+pub type Display4in37Landscape = landscape_type!(Deg90, 480, 176);
+pub type Display2in9Landscape = landscape_type!(Deg90, 384, 168);
@@ -0,0 +1,92 @@
+//! Bit packing of the black and chromatic planes, e.g. for image conversion tools or tests
+//! which create or check planes without a display buffer
+//!
+//! A plane has 1 bit per pixel, 8 pixels per byte with the leftmost pixel in the MSB. The rows
+//! are stored from top to bottom and each row starts with a new byte. A set bit is black in the
+//! black plane and red in the chromatic plane, black wins if both are set. This is the layout
+//! of [`Display::black_plane`](crate::Display::black_plane) in unrotated panel orientation.
+
+use crate::TriColor;
+
+/// Returns the number of bytes of a row with `width` pixels
+#[must_use]
+pub const fn bytes_per_row(width: u32) -> usize {
+    width.div_ceil(8) as usize
+}
+
+/// Returns the number of bytes of a plane with the given size in pixels
+#[must_use]
+pub const fn plane_size(width: u32, height: u32) -> usize {
+    bytes_per_row(width) * height as usize
+}
+
+/// Returns the bit mask of the pixel in column `x` within its byte
+#[must_use]
+pub const fn bit_mask(x: usize) -> u8 {
+    0x80 >> (x % 8)
+}
+
+/// Returns the byte index and the bit mask of the pixel (x, y) in a plane with `width` pixels
+/// per row
+#[must_use]
+pub const fn byte_position(x: usize, y: usize, width: u32) -> (usize, u8) {
+    (y * bytes_per_row(width) + x / 8, bit_mask(x))
+}
+
+/// Returns the bytes of the black and the chromatic plane for 8 pixels of the given color
+#[must_use]
+pub const fn plane_bytes(color: TriColor) -> (u8, u8) {
+    match color {
+        TriColor::White => (0x00, 0x00),
+        TriColor::Black => (0xff, 0x00),
+        TriColor::Red => (0x00, 0xff),
+    }
+}
+
+/// Returns the color of a pixel from its bits in the black and the chromatic plane
+#[must_use]
+pub const fn color_from_bits(black: bool, red: bool) -> TriColor {
+    if black {
+        TriColor::Black
+    } else if red {
+        TriColor::Red
+    } else {
+        TriColor::White
+    }
+}
+
+/// Sets the pixel (x, y) to `color` in the planes `black` and `red` with `width` pixels per
+/// row. Returns `false` if the pixel is outside the planes.
+pub fn set_pixel(
+    black: &mut [u8],
+    red: &mut [u8],
+    width: u32,
+    (x, y): (usize, usize),
+    color: TriColor,
+) -> bool {
+    if x >= width as usize {
+        return false;
+    }
+    let (index, mask) = byte_position(x, y, width);
+    let (Some(black), Some(red)) = (black.get_mut(index), red.get_mut(index)) else {
+        return false;
+    };
+    let (black_bits, red_bits) = plane_bytes(color);
+    *black = (*black & !mask) | (black_bits & mask);
+    *red = (*red & !mask) | (red_bits & mask);
+    true
+}
+
+/// Returns the color of the pixel (x, y) in the planes `black` and `red` with `width` pixels
+/// per row, or `None` if the pixel is outside the planes
+#[must_use]
+pub fn get_pixel(black: &[u8], red: &[u8], width: u32, (x, y): (usize, usize)) -> Option<TriColor> {
+    if x >= width as usize {
+        return None;
+    }
+    let (index, mask) = byte_position(x, y, width);
+    Some(color_from_bits(
+        black.get(index)? & mask != 0,
+        red.get(index)? & mask != 0,
+    ))
+}
@@ -0,0 +1,470 @@
+//! Generic interface of an e-paper panel, e.g. for GUI layers which are generic over the panel
+
+use embedded_graphics::{
+    geometry::{Dimensions, Size},
+    pixelcolor::PixelColor,
+    prelude::DrawTarget,
+    primitives::Rectangle,
+    Pixel,
+};
+use embedded_hal::{delay::DelayNs, digital::InputPin, digital::OutputPin, spi::SpiDevice};
+
+use crate::{driver::EpdError, Active, Display, DisplayBuffer, Epd, Error, Inactive, TriColor};
+
+/// An e-paper panel: its size, color and display buffer type and how to show a frame.
+/// Code which is generic over this trait does not depend on the size of the panel or
+/// on the generic parameters of the driver.
+pub trait EpdPanel {
+    /// Color of the pixels
+    type Color: PixelColor;
+    /// Display buffer matching the panel
+    type Framebuffer: DisplayBuffer + DrawTarget<Color = Self::Color> + Default;
+    /// Delay used while updating and powering off the panel
+    type Delay: DelayNs;
+    /// Errors of the panel
+    type Error;
+
+    /// Returns the unrotated size of the panel in pixels
+    fn size(&self) -> Size;
+
+    /// Show the framebuffer on the panel, initializing the panel first if it is powered off.
+    /// This function is blocking until the update process is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the panel cannot be updated.
+    fn update(
+        &mut self,
+        framebuffer: &Self::Framebuffer,
+        delay: &mut Self::Delay,
+    ) -> Result<(), Self::Error>;
+
+    /// Start to show the framebuffer on the panel without waiting for the refresh, like
+    /// [`update`](EpdPanel::update). Only sending the frame and powering on the panel is blocking.
+    /// The end of the refresh has to be awaited with `wait_until_idle` before the panel is used
+    /// again.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the panel cannot be updated.
+    fn start_update(
+        &mut self,
+        framebuffer: &Self::Framebuffer,
+        delay: &mut Self::Delay,
+    ) -> Result<(), Self::Error>;
+
+    /// Wait until the panel is not busy anymore, e.g. with the refresh after `start_update`
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error on a timeout.
+    fn wait_until_idle(&mut self, delay: &mut Self::Delay) -> Result<(), Self::Error>;
+
+    /// Power off the panel, the next `update` initializes it again.
+    /// Does nothing if the panel is already powered off.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the panel cannot be powered off.
+    fn power_off(&mut self, delay: &mut Self::Delay) -> Result<(), Self::Error>;
+}
+
+/// Driver in the inactive state and SPI device of a released [`Panel`]
+type ReleaseResult<SPI, BUSY, DC, RST, DELAY> =
+    Result<(Epd<Inactive, SPI, BUSY, DC, RST, DELAY>, SPI), EpdError<SPI, DC, RST>>;
+
+/// Driver of a [`Panel`] in the typestate matching the power state of the e-paper
+enum Driver<SPI, BUSY, DC, RST, DELAY> {
+    /// the e-paper is powered off
+    Inactive(Epd<Inactive, SPI, BUSY, DC, RST, DELAY>),
+    /// the e-paper is initialized
+    Active(Epd<Active, SPI, BUSY, DC, RST, DELAY>),
+}
+
+/// [`EpdPanel`] of the driver with the SPI device and the size of the display type,
+/// e.g. `Panel<296, 152, 5624, ...>` for a `Display2in66`.
+/// The panel keeps the driver in the typestate of the e-paper, it is initialized by the
+/// next update after a power off.
+///
+/// The framebuffer has to be the display type of the size of the panel:
+///
+/// ```
+/// # use embedded_hal::{delay::DelayNs, digital::{InputPin, OutputPin}, spi::SpiDevice};
+/// use epd_spectra::{Display2in66, EpdPanel, Panel};
+///
+/// fn show<SPI, BUSY, DC, RST, DELAY>(
+///     panel: &mut Panel<296, 152, 5624, SPI, BUSY, DC, RST, DELAY>,
+///     delay: &mut DELAY,
+/// ) where
+///     SPI: SpiDevice,
+///     BUSY: InputPin,
+///     DC: OutputPin,
+///     RST: OutputPin,
+///     DELAY: DelayNs,
+/// {
+///     let _ = panel.update(&Display2in66::new(), delay);
+/// }
+/// ```
+///
+/// A display type of another size does not compile:
+///
+/// ```compile_fail
+/// # use embedded_hal::{delay::DelayNs, digital::{InputPin, OutputPin}, spi::SpiDevice};
+/// use epd_spectra::{Display2in13, EpdPanel, Panel};
+///
+/// fn show<SPI, BUSY, DC, RST, DELAY>(
+///     panel: &mut Panel<296, 152, 5624, SPI, BUSY, DC, RST, DELAY>,
+///     delay: &mut DELAY,
+/// ) where
+///     SPI: SpiDevice,
+///     BUSY: InputPin,
+///     DC: OutputPin,
+///     RST: OutputPin,
+///     DELAY: DelayNs,
+/// {
+///     let _ = panel.update(&Display2in13::new(), delay);
+/// }
+/// ```
+pub struct Panel<
+    const SIZE_V: u32,
+    const SIZE_H: u32,
+    const IMAGE_SIZE: usize,
+    SPI,
+    BUSY,
+    DC,
+    RST,
+    DELAY,
+> {
+    /// driver, `None` only during a change of its typestate. It is put back on every path,
+    /// `Error::DriverMissing` is returned instead of a panic if it were missing.
+    driver: Option<Driver<SPI, BUSY, DC, RST, DELAY>>,
+    spi: SPI,
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize, SPI, BUSY, DC, RST, DELAY>
+    Panel<SIZE_V, SIZE_H, IMAGE_SIZE, SPI, BUSY, DC, RST, DELAY>
+{
+    /// Width of the unrotated panel in pixels, see [`Display::WIDTH`]
+    pub const WIDTH: u32 = SIZE_H;
+    /// Height of the unrotated panel in pixels, see [`Display::HEIGHT`]
+    pub const HEIGHT: u32 = SIZE_V;
+    /// See [`Display::BYTES_PER_ROW`]
+    pub const BYTES_PER_ROW: usize = Display::<SIZE_V, SIZE_H, IMAGE_SIZE>::BYTES_PER_ROW;
+    /// See [`Display::PLANE_SIZE`]
+    pub const PLANE_SIZE: usize = IMAGE_SIZE;
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize, SPI, BUSY, DC, RST, DELAY>
+    Panel<SIZE_V, SIZE_H, IMAGE_SIZE, SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    /// Create a panel from a driver which is not initialized yet and its SPI device.
+    /// The panel is initialized by the first `update`.
+    /// The size is checked at compile time like for `Display` and set as dimensions of the
+    /// driver.
+    pub fn new(mut epd: Epd<Inactive, SPI, BUSY, DC, RST, DELAY>, spi: SPI) -> Self {
+        let () = Display::<SIZE_V, SIZE_H, IMAGE_SIZE>::SIZE_CHECK;
+        epd.set_dimensions(Size::new(SIZE_H, SIZE_V));
+        Self {
+            driver: Some(Driver::Inactive(epd)),
+            spi,
+        }
+    }
+
+    /// Power off the panel if necessary and return the driver and the SPI device
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is an error
+    /// with the GPIOs or the SPI device.
+    pub fn release(mut self, delay: &mut DELAY) -> ReleaseResult<SPI, BUSY, DC, RST, DELAY> {
+        match self.driver {
+            Some(Driver::Inactive(epd)) => Ok((epd, self.spi)),
+            Some(Driver::Active(epd)) => {
+                let epd = epd.power_off(&mut self.spi, delay)?;
+                Ok((epd, self.spi))
+            }
+            None => Err(Error::DriverMissing),
+        }
+    }
+
+    /// Initializes the e-paper if it is powered off and calls `f` with the driver in the active
+    /// state. If the initialization fails, the driver stays in the inactive state.
+    fn with_active(
+        &mut self,
+        delay: &mut DELAY,
+        f: impl FnOnce(
+            &mut Epd<Active, SPI, BUSY, DC, RST, DELAY>,
+            &mut SPI,
+            &mut DELAY,
+        ) -> Result<(), EpdError<SPI, DC, RST>>,
+    ) -> Result<(), EpdError<SPI, DC, RST>> {
+        let mut epd = match self.driver.take() {
+            Some(Driver::Active(epd)) => epd,
+            Some(Driver::Inactive(epd)) => {
+                let mut epd = epd.into_state::<Active>();
+                if let Err(error) = epd.initialize(&mut self.spi, delay) {
+                    self.driver = Some(Driver::Inactive(epd.into_state()));
+                    return Err(error);
+                }
+                epd
+            }
+            None => return Err(Error::DriverMissing),
+        };
+        let result = f(&mut epd, &mut self.spi, delay);
+        self.driver = Some(Driver::Active(epd));
+        result
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize, SPI, BUSY, DC, RST, DELAY>
+    EpdPanel for Panel<SIZE_V, SIZE_H, IMAGE_SIZE, SPI, BUSY, DC, RST, DELAY>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin,
+    DC: OutputPin,
+    RST: OutputPin,
+    DELAY: DelayNs,
+{
+    type Color = TriColor;
+    type Framebuffer = Display<SIZE_V, SIZE_H, IMAGE_SIZE>;
+    type Delay = DELAY;
+    type Error = EpdError<SPI, DC, RST>;
+
+    fn size(&self) -> Size {
+        Size::new(SIZE_H, SIZE_V)
+    }
+
+    fn update(
+        &mut self,
+        framebuffer: &Self::Framebuffer,
+        delay: &mut DELAY,
+    ) -> Result<(), Self::Error> {
+        self.with_active(delay, |epd, spi, delay| epd.update(framebuffer, spi, delay))
+    }
+
+    fn start_update(
+        &mut self,
+        framebuffer: &Self::Framebuffer,
+        delay: &mut DELAY,
+    ) -> Result<(), Self::Error> {
+        self.with_active(delay, |epd, spi, delay| {
+            epd.start_update(framebuffer, spi, delay)
+        })
+    }
+
+    /// There is nothing to wait for while the panel is powered off
+    fn wait_until_idle(&mut self, delay: &mut DELAY) -> Result<(), Self::Error> {
+        match &mut self.driver {
+            Some(Driver::Active(epd)) => epd.wait_until_idle(delay),
+            _ => Ok(()),
+        }
+    }
+
+    fn power_off(&mut self, delay: &mut DELAY) -> Result<(), Self::Error> {
+        match self.driver.take() {
+            Some(Driver::Active(epd)) => match epd.try_power_off(&mut self.spi, delay) {
+                Ok(epd) => {
+                    self.driver = Some(Driver::Inactive(epd));
+                    Ok(())
+                }
+                Err((epd, error)) => {
+                    self.driver = Some(Driver::Active(epd));
+                    Err(error)
+                }
+            },
+            driver => {
+                self.driver = driver;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Panel with its own display buffer, like the drivers of other displays: draw on it with
+/// [`DrawTarget`] and show the result with `flush`.
+pub struct EpdDisplay<P: EpdPanel> {
+    panel: P,
+    framebuffer: P::Framebuffer,
+}
+
+impl<P: EpdPanel> EpdDisplay<P> {
+    /// Create a display with an empty (white) display buffer
+    pub fn new(panel: P) -> Self {
+        Self {
+            panel,
+            framebuffer: P::Framebuffer::default(),
+        }
+    }
+
+    /// Show the display buffer on the panel.
+    /// This function is blocking until the update process is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the panel cannot be updated.
+    pub fn flush(&mut self, delay: &mut P::Delay) -> Result<(), P::Error> {
+        self.panel.update(&self.framebuffer, delay)
+    }
+
+    /// Start to show the display buffer on the panel without waiting for the refresh.
+    /// Call `wait_until_idle` before using the panel again.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the panel cannot be updated.
+    pub fn start_flush(&mut self, delay: &mut P::Delay) -> Result<(), P::Error> {
+        self.panel.start_update(&self.framebuffer, delay)
+    }
+
+    /// Wait until the panel is not busy anymore, e.g. with the refresh after `start_flush`
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error on a timeout.
+    pub fn wait_until_idle(&mut self, delay: &mut P::Delay) -> Result<(), P::Error> {
+        self.panel.wait_until_idle(delay)
+    }
+
+    /// Power off the panel, the next `flush` initializes it again
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the panel cannot be powered off.
+    pub fn power_off(&mut self, delay: &mut P::Delay) -> Result<(), P::Error> {
+        self.panel.power_off(delay)
+    }
+
+    /// Returns the display buffer, e.g. to set the rotation
+    pub fn framebuffer_mut(&mut self) -> &mut P::Framebuffer {
+        &mut self.framebuffer
+    }
+
+    /// Returns the display buffer
+    pub fn framebuffer(&self) -> &P::Framebuffer {
+        &self.framebuffer
+    }
+
+    /// Returns the panel
+    pub fn panel_mut(&mut self) -> &mut P {
+        &mut self.panel
+    }
+
+    /// Returns the panel and the display buffer
+    pub fn into_inner(self) -> (P, P::Framebuffer) {
+        (self.panel, self.framebuffer)
+    }
+}
+
+impl<P: EpdPanel> Dimensions for EpdDisplay<P> {
+    fn bounding_box(&self) -> Rectangle {
+        self.framebuffer.bounding_box()
+    }
+}
+
+impl<P: EpdPanel> DrawTarget for EpdDisplay<P> {
+    type Color = P::Color;
+    type Error = <P::Framebuffer as DrawTarget>::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.framebuffer.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.framebuffer.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.framebuffer.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.framebuffer.clear(color)
+    }
+}
+
+/// Several displays, e.g. panels of different sizes with their own chip select on a shared
+/// SPI bus (see `embedded-hal-bus`). It is implemented for tuples of up to four [`EpdDisplay`]s
+/// whose panels have the same delay and error types and for slices of [`EpdDisplay`]s.
+/// A single display can still be updated on its own with [`EpdDisplay::flush`].
+pub trait EpdDisplays {
+    /// Delay used by all panels
+    type Delay;
+    /// Errors of all panels
+    type Error;
+
+    /// Show the display buffers of all displays. The frames are sent one after another
+    /// and the panels refresh at the same time, so this takes about as long as updating
+    /// a single display. This function is blocking until all refreshes are complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return the first error of a panel.
+    fn flush_all(&mut self, delay: &mut Self::Delay) -> Result<(), Self::Error>;
+
+    /// Power off all panels
+    ///
+    /// # Errors
+    ///
+    /// This function will return the first error of a panel.
+    fn power_off_all(&mut self, delay: &mut Self::Delay) -> Result<(), Self::Error>;
+}
+
+impl<P: EpdPanel> EpdDisplays for [EpdDisplay<P>] {
+    type Delay = P::Delay;
+    type Error = P::Error;
+
+    fn flush_all(&mut self, delay: &mut Self::Delay) -> Result<(), Self::Error> {
+        for display in self.iter_mut() {
+            display.start_flush(delay)?;
+        }
+        for display in self.iter_mut() {
+            display.wait_until_idle(delay)?;
+        }
+        Ok(())
+    }
+
+    fn power_off_all(&mut self, delay: &mut Self::Delay) -> Result<(), Self::Error> {
+        for display in self.iter_mut() {
+            display.power_off(delay)?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_epd_displays {
+    ($first:ident $(, $panel:ident)*; $($index:tt),+) => {
+        impl<$first: EpdPanel $(, $panel: EpdPanel<Delay = $first::Delay, Error = $first::Error>)*>
+            EpdDisplays for (EpdDisplay<$first>, $(EpdDisplay<$panel>),*)
+        {
+            type Delay = $first::Delay;
+            type Error = $first::Error;
+
+            fn flush_all(&mut self, delay: &mut Self::Delay) -> Result<(), Self::Error> {
+                $(self.$index.start_flush(delay)?;)+
+                $(self.$index.wait_until_idle(delay)?;)+
+                Ok(())
+            }
+
+            fn power_off_all(&mut self, delay: &mut Self::Delay) -> Result<(), Self::Error> {
+                $(self.$index.power_off(delay)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_epd_displays!(A, B; 0, 1);
+impl_epd_displays!(A, B, C; 0, 1, 2);
+impl_epd_displays!(A, B, C, D; 0, 1, 2, 3);
@@ -0,0 +1,100 @@
+//! Raw images with separate black and red planes, e.g. exported by vendor tools
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    image::{GetPixel, ImageDrawable},
+    primitives::{PointsIter, Rectangle},
+};
+
+use crate::{packing, TriColor};
+
+/// Raw image consisting of a black and a red plane with 1 bit per pixel (set: black resp. red),
+/// row by row and MSB first. Each row starts at a byte boundary. A set bit in the black plane
+/// takes precedence. In contrast to `ImageRaw<TriColor>` the planes can be used as exported
+/// by vendor tools and drawn with `Image` without conversion.
+#[derive(Clone, Copy)]
+pub struct PlanarImage<'a> {
+    black: &'a [u8],
+    red: &'a [u8],
+    size: Size,
+}
+
+impl<'a> PlanarImage<'a> {
+    /// Creates an image with the given width from the black and the red plane.
+    /// The height is determined by the length of the planes; if they differ,
+    /// the shorter plane is used.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn new(black: &'a [u8], red: &'a [u8], width: u32) -> Self {
+        let row_size = width.div_ceil(8) as usize;
+        let len = if black.len() < red.len() {
+            black.len()
+        } else {
+            red.len()
+        };
+        let height = match len.checked_div(row_size) {
+            Some(height) => height,
+            None => 0,
+        };
+        Self {
+            black,
+            red,
+            size: Size::new(width, height as u32),
+        }
+    }
+
+    /// Creates an image with the given width from a whole frame, i.e. the black plane
+    /// followed by the red plane, e.g. an array exported by vendor tools (see
+    /// [`c_array`](crate::c_array) for their C sources). The frame is split in the middle.
+    #[must_use]
+    pub const fn from_frame(frame: &'a [u8], width: u32) -> Self {
+        let (black, red) = frame.split_at(frame.len() / 2);
+        Self::new(black, red, width)
+    }
+
+    /// Returns the color of the pixel at point `p`, which must be inside the image
+    #[allow(clippy::cast_sign_loss)]
+    fn color(&self, p: Point) -> TriColor {
+        let position = (p.x as usize, p.y as usize);
+        packing::get_pixel(self.black, self.red, self.size.width, position).unwrap_or_default()
+    }
+}
+
+impl OriginDimensions for PlanarImage<'_> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl ImageDrawable for PlanarImage<'_> {
+    type Color = TriColor;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let area = self.bounding_box();
+        target.fill_contiguous(&area, area.points().map(|p| self.color(p)))
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let area = area.intersection(&self.bounding_box());
+        target.fill_contiguous(
+            &Rectangle::new(Point::zero(), area.size),
+            area.points().map(|p| self.color(p)),
+        )
+    }
+}
+
+impl GetPixel for PlanarImage<'_> {
+    type Color = TriColor;
+
+    /// Returns the color of the pixel at point `p` or `None` if the point is outside the image
+    fn pixel(&self, p: Point) -> Option<Self::Color> {
+        self.bounding_box().contains(p).then(|| self.color(p))
+    }
+}
@@ -0,0 +1,138 @@
+//! Scheduling of maintenance refreshes, which the datasheets of the panels require periodically
+
+use embedded_hal::{delay::DelayNs, digital::InputPin, digital::OutputPin, spi::SpiDevice};
+
+use crate::{driver::EpdError, Active, DisplayBuffer, Epd};
+
+/// Tracks the refreshes of a panel and tells when a maintenance refresh is due, i.e. the
+/// frame has to be refreshed even if it did not change. The datasheets of Pervasive Displays
+/// recommend to refresh the panel at least once every 24 hours to avoid ghosting and damage
+/// of the film.
+///
+/// Timestamps are supplied by the application in seconds from an arbitrary epoch, e.g. of an
+/// RTC. A refresh is due after `max_updates` updates or `max_interval` seconds since the last
+/// maintenance refresh, whichever comes first:
+/// ```ignore
+/// let mut policy = RefreshPolicy::new().with_max_interval(24 * 60 * 60);
+/// // refreshes if the frame changed or a maintenance refresh is due
+/// policy.update(&mut epd, &display, rtc.now(), &mut spi, &mut delay)?;
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct RefreshPolicy {
+    max_updates: Option<u32>,
+    max_interval: Option<u64>,
+    /// number of updates since the last maintenance refresh
+    updates: u32,
+    /// time of the last maintenance refresh (`None`: not yet known)
+    last_maintenance: Option<u64>,
+}
+
+impl RefreshPolicy {
+    /// Creates a policy without limits, so a maintenance refresh is never due
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_updates: None,
+            max_interval: None,
+            updates: 0,
+            last_maintenance: None,
+        }
+    }
+
+    /// Requires a maintenance refresh after `max_updates` updates
+    #[must_use]
+    pub const fn with_max_updates(self, max_updates: u32) -> Self {
+        Self {
+            max_updates: Some(max_updates),
+            ..self
+        }
+    }
+
+    /// Requires a maintenance refresh `max_interval` seconds after the last one
+    #[must_use]
+    pub const fn with_max_interval(self, max_interval: u64) -> Self {
+        Self {
+            max_interval: Some(max_interval),
+            ..self
+        }
+    }
+
+    /// Returns the number of updates since the last maintenance refresh
+    #[must_use]
+    pub fn updates(&self) -> u32 {
+        self.updates
+    }
+
+    /// Returns true if a maintenance refresh is due at time `now`. The interval starts with
+    /// the first recorded refresh.
+    #[must_use]
+    pub fn is_due(&self, now: u64) -> bool {
+        let updates_due = self.max_updates.is_some_and(|max| self.updates >= max);
+        let interval_due = match (self.max_interval, self.last_maintenance) {
+            (Some(max), Some(last)) => now.saturating_sub(last) >= max,
+            _ => false,
+        };
+        updates_due || interval_due
+    }
+
+    /// Returns the seconds from `now` until the next maintenance refresh is due, 0 if it is
+    /// already due, e.g. to do it together with another wakeup. Returns `None` if no maximum
+    /// interval is set or no refresh was recorded yet and the maximum number of updates is
+    /// not reached.
+    #[must_use]
+    pub fn time_until_maintenance(&self, now: u64) -> Option<u64> {
+        if self.is_due(now) {
+            return Some(0);
+        }
+        let max = self.max_interval?;
+        let last = self.last_maintenance?;
+        Some(last.saturating_add(max).saturating_sub(now))
+    }
+
+    /// Records an update at time `now`, call this after each update of the panel
+    pub fn record_update(&mut self, now: u64) {
+        self.updates = self.updates.saturating_add(1);
+        self.last_maintenance.get_or_insert(now);
+    }
+
+    /// Records a maintenance refresh at time `now`, which restarts the counting
+    pub fn record_maintenance(&mut self, now: u64) {
+        self.updates = 0;
+        self.last_maintenance = Some(now);
+    }
+
+    /// Shows `display` on the e-paper if it differs from the frame shown last or if a
+    /// maintenance refresh is due at time `now` and records the refresh. Returns true if
+    /// the panel was refreshed. This function is blocking until the update process is complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is an error
+    /// with the GPIOs or the SPI device.
+    pub fn update<SPI, BUSY, DC, RST, DELAY>(
+        &mut self,
+        epd: &mut Epd<Active, SPI, BUSY, DC, RST, DELAY>,
+        display: &impl DisplayBuffer,
+        now: u64,
+        spi: &mut SPI,
+        delay: &mut DELAY,
+    ) -> Result<bool, EpdError<SPI, DC, RST>>
+    where
+        SPI: SpiDevice,
+        BUSY: InputPin,
+        DC: OutputPin,
+        RST: OutputPin,
+        DELAY: DelayNs,
+    {
+        if self.is_due(now) {
+            epd.update_hashed(display, spi, delay)?;
+            self.record_maintenance(now);
+            Ok(true)
+        } else if epd.update_if_changed(display, spi, delay)? {
+            self.record_update(now);
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+}
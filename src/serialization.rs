@@ -0,0 +1,160 @@
+//! `serde` support for display buffers (feature `serde`)
+//!
+//! A [`Display`] is serialized as a struct with the fields `black` and `red`
+//! containing the planes as bytes (see [`Display::black_plane`] for the layout).
+
+use core::fmt;
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::Display;
+
+const FIELDS: &[&str] = &["black", "red"];
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> Serialize
+    for Display<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Display", FIELDS.len())?;
+        state.serialize_field("black", &Bytes(self.black_plane()))?;
+        state.serialize_field("red", &Bytes(self.chromatic_plane()))?;
+        state.end()
+    }
+}
+
+impl<'de, const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> Deserialize<'de>
+    for Display<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("Display", FIELDS, DisplayVisitor)
+    }
+}
+
+/// Serializes a plane as bytes
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Deserializes a plane with exactly `IMAGE_SIZE` bytes
+struct Plane<const IMAGE_SIZE: usize>([u8; IMAGE_SIZE]);
+
+impl<'de, const IMAGE_SIZE: usize> Deserialize<'de> for Plane<IMAGE_SIZE> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(PlaneVisitor)
+    }
+}
+
+struct PlaneVisitor<const IMAGE_SIZE: usize>;
+
+impl<'de, const IMAGE_SIZE: usize> Visitor<'de> for PlaneVisitor<IMAGE_SIZE> {
+    type Value = Plane<IMAGE_SIZE>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a plane with {IMAGE_SIZE} bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        v.try_into()
+            .map(Plane)
+            .map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    // formats without native byte arrays (e.g. JSON) use sequences
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut plane = [0; IMAGE_SIZE];
+        for (len, byte) in plane.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(len, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(IMAGE_SIZE + 1, &self));
+        }
+        Ok(Plane(plane))
+    }
+}
+
+enum Field {
+    Black,
+    Red,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct FieldVisitor;
+
+impl Visitor<'_> for FieldVisitor {
+    type Value = Field;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("`black` or `red`")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        match v {
+            "black" => Ok(Field::Black),
+            "red" => Ok(Field::Red),
+            _ => Err(E::unknown_field(v, FIELDS)),
+        }
+    }
+}
+
+struct DisplayVisitor<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>;
+
+impl<'de, const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize> Visitor<'de>
+    for DisplayVisitor<SIZE_V, SIZE_H, IMAGE_SIZE>
+{
+    type Value = Display<SIZE_V, SIZE_H, IMAGE_SIZE>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("struct Display")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let black: Plane<IMAGE_SIZE> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let red: Plane<IMAGE_SIZE> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(to_display(&black, &red))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut black: Option<Plane<IMAGE_SIZE>> = None;
+        let mut red: Option<Plane<IMAGE_SIZE>> = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Black if black.is_some() => return Err(de::Error::duplicate_field("black")),
+                Field::Black => black = Some(map.next_value()?),
+                Field::Red if red.is_some() => return Err(de::Error::duplicate_field("red")),
+                Field::Red => red = Some(map.next_value()?),
+            }
+        }
+        let black = black.ok_or_else(|| de::Error::missing_field("black"))?;
+        let red = red.ok_or_else(|| de::Error::missing_field("red"))?;
+        Ok(to_display(&black, &red))
+    }
+}
+
+/// Creates a display from deserialized planes, the whole display is marked as changed
+fn to_display<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>(
+    black: &Plane<IMAGE_SIZE>,
+    red: &Plane<IMAGE_SIZE>,
+) -> Display<SIZE_V, SIZE_H, IMAGE_SIZE> {
+    let mut display = Display::default();
+    display.black_plane_mut().copy_from_slice(&black.0);
+    display.chromatic_plane_mut().copy_from_slice(&red.0);
+    display
+}
@@ -0,0 +1,76 @@
+//! Preview of display buffers in the window of `embedded-graphics-simulator` (feature `simulator`)
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions},
+    image::GetPixel,
+    primitives::PointsIter,
+    Pixel,
+};
+use embedded_graphics_simulator::{
+    OutputSettingsBuilder, SimulatorDisplay, SimulatorEvent, Window,
+};
+
+use crate::TriColor;
+
+/// Returns a simulator display with the content of `source`, e.g. a [`Display`](crate::Display).
+/// The size is the size of `source`, i.e. the exact panel resolution with the rotation applied.
+#[must_use]
+pub fn simulator_display<S>(source: &S) -> SimulatorDisplay<TriColor>
+where
+    S: OriginDimensions + GetPixel<Color = TriColor>,
+{
+    let mut display = SimulatorDisplay::new(source.size());
+    let area = source.bounding_box();
+    let pixels = area
+        .points()
+        .filter_map(|p| source.pixel(p).map(|color| Pixel(p, color)));
+    let Ok(()) = display.draw_iter(pixels);
+    display
+}
+
+/// Simulator window which shows the content of display buffers in white, black and red:
+/// ```ignore
+/// let mut window = SimulatorWindow::new("Preview", 2);
+/// window.show_static(&display);
+/// ```
+pub struct SimulatorWindow {
+    window: Window,
+}
+
+impl SimulatorWindow {
+    /// Creates a window with the given title. Each pixel of the display is shown as
+    /// `scale` x `scale` pixels. The window is opened by the first update.
+    #[must_use]
+    pub fn new(title: &str, scale: u32) -> Self {
+        let settings = OutputSettingsBuilder::new()
+            .scale(scale.max(1))
+            .pixel_spacing(0)
+            .build();
+        Self {
+            window: Window::new(title, &settings),
+        }
+    }
+
+    /// Shows the content of `source`. The size of the window is determined by the first update,
+    /// so the rotation should be set before.
+    pub fn update<S>(&mut self, source: &S)
+    where
+        S: OriginDimensions + GetPixel<Color = TriColor>,
+    {
+        self.window.update(&simulator_display(source));
+    }
+
+    /// Shows the content of `source` until the window is closed
+    pub fn show_static<S>(&mut self, source: &S)
+    where
+        S: OriginDimensions + GetPixel<Color = TriColor>,
+    {
+        self.window.show_static(&simulator_display(source));
+    }
+
+    /// Returns the events of the window since the last call, e.g. to redraw on key presses
+    pub fn events(&mut self) -> impl Iterator<Item = SimulatorEvent> + '_ {
+        self.window.events()
+    }
+}
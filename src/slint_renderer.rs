@@ -0,0 +1,87 @@
+//! Adapter for the line by line software renderer of [Slint](https://slint.dev) (feature `slint`)
+
+use core::{convert::Infallible, ops::Range};
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    pixelcolor::{raw::RawU16, Rgb565},
+    primitives::{PointsIter, Rectangle},
+    Pixel,
+};
+use slint::platform::software_renderer::{LineBufferProvider, Rgb565Pixel};
+
+use crate::{DitherMethod, DitherPalette, DitherTarget, TriColor};
+
+/// Line buffer for `SoftwareRenderer::render_by_line` of Slint which converts the rendered
+/// lines into [`TriColor`] and draws them to `target`, e.g. a [`Display`](crate::Display)
+/// which is sent to the e-paper afterwards. `WIDTH` is the width of a line in pixels and
+/// must be at least the width of the display (with rotation).
+///
+/// By default the colors are mapped to the nearest color of the palette. With dithering
+/// (see `set_method`) the lines are dithered independently of each other, as Slint only
+/// renders the lines which changed. So ordered dithering gives the most regular results.
+pub struct SlintLineBuffer<'a, D, const WIDTH: usize> {
+    target: DitherTarget<'a, D, Rgb565, WIDTH>,
+    dithering: bool,
+    line: [Rgb565Pixel; WIDTH],
+}
+
+impl<'a, D, const WIDTH: usize> SlintLineBuffer<'a, D, WIDTH>
+where
+    D: DrawTarget<Color = TriColor, Error = Infallible>,
+{
+    /// Creates a line buffer which draws to `target` with the colors of `palette`
+    pub fn new(target: &'a mut D, palette: DitherPalette) -> Self {
+        Self {
+            target: DitherTarget::new(target, palette),
+            dithering: false,
+            line: [Rgb565Pixel::default(); WIDTH],
+        }
+    }
+
+    /// Sets the dithering method, `None` maps the colors to the nearest color without dithering
+    pub fn set_method(&mut self, method: Option<DitherMethod>) {
+        // without dithering the nearest color is used for single pixels (`draw_iter`)
+        self.dithering = method.is_some();
+        self.target.set_method(method.unwrap_or_default());
+    }
+    #[must_use]
+    pub fn method(&self) -> Option<DitherMethod> {
+        self.dithering.then(|| self.target.method())
+    }
+}
+
+impl<D, const WIDTH: usize> LineBufferProvider for SlintLineBuffer<'_, D, WIDTH>
+where
+    D: DrawTarget<Color = TriColor, Error = Infallible>,
+{
+    type TargetPixel = Rgb565Pixel;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn process_line(
+        &mut self,
+        line: usize,
+        range: Range<usize>,
+        render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+    ) {
+        // lines wider than the buffer are dropped
+        let Some(pixels) = self.line.get_mut(range.clone()) else {
+            return;
+        };
+        render_fn(pixels);
+
+        let area = Rectangle::new(
+            Point::new(range.start as i32, line as i32),
+            Size::new(pixels.len() as u32, 1),
+        );
+        let colors = pixels
+            .iter()
+            .map(|pixel| Rgb565::from(RawU16::new(pixel.0)));
+        let Ok(()) = if self.dithering {
+            self.target.fill_contiguous(&area, colors)
+        } else {
+            let pixels = area.points().zip(colors).map(|(p, color)| Pixel(p, color));
+            self.target.draw_iter(pixels)
+        };
+    }
+}
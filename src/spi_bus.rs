@@ -0,0 +1,105 @@
+//! SPI device from an SPI bus and a chip select pin, for HALs without `embedded-hal-bus`
+
+use embedded_hal::{
+    digital::OutputPin,
+    spi::{self, ErrorKind, ErrorType, Operation, SpiBus, SpiDevice},
+};
+
+/// Errors of [`SpiBusDevice`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SpiBusError<BusError, CsError> {
+    /// Error of the SPI bus
+    Spi(BusError),
+    /// Error setting the chip select pin
+    Cs(CsError),
+    /// The transaction contains a delay, which is not supported
+    Delay,
+}
+
+impl<BusError, CsError> spi::Error for SpiBusError<BusError, CsError>
+where
+    BusError: spi::Error,
+    CsError: core::fmt::Debug,
+{
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Spi(error) => error.kind(),
+            Self::Cs(_) => ErrorKind::ChipSelectFault,
+            Self::Delay => ErrorKind::Other,
+        }
+    }
+}
+
+/// SPI device which owns an SPI bus and asserts the chip select pin (active low) around each
+/// transaction, so the driver can be used with an `SpiBus` and a chip select `OutputPin`:
+/// ```ignore
+/// let mut spi = SpiBusDevice::new(spi_bus, cs)?;
+/// let epd = Epd::new(&mut spi, busy, dc, rst, &mut delay, 0);
+/// ```
+/// The bus must not be shared with other devices, use `embedded-hal-bus` for this.
+/// Delays in transactions are not supported, the driver does not use them.
+pub struct SpiBusDevice<BUS, CS> {
+    bus: BUS,
+    cs: CS,
+}
+
+impl<BUS, CS> SpiBusDevice<BUS, CS>
+where
+    BUS: SpiBus,
+    CS: OutputPin,
+{
+    /// Creates a device from `bus` and `cs` and deasserts the chip select pin
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the chip select pin if it cannot be set high.
+    pub fn new(bus: BUS, mut cs: CS) -> Result<Self, CS::Error> {
+        cs.set_high()?;
+        Ok(Self { bus, cs })
+    }
+
+    /// Returns the SPI bus and the chip select pin
+    pub fn release(self) -> (BUS, CS) {
+        (self.bus, self.cs)
+    }
+
+    fn run(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), SpiBusError<BUS::Error, CS::Error>> {
+        for operation in operations {
+            match operation {
+                Operation::Read(words) => self.bus.read(words),
+                Operation::Write(words) => self.bus.write(words),
+                Operation::Transfer(read, write) => self.bus.transfer(read, write),
+                Operation::TransferInPlace(words) => self.bus.transfer_in_place(words),
+                Operation::DelayNs(_) => return Err(SpiBusError::Delay),
+            }
+            .map_err(SpiBusError::Spi)?;
+        }
+        Ok(())
+    }
+}
+
+impl<BUS, CS> ErrorType for SpiBusDevice<BUS, CS>
+where
+    BUS: SpiBus,
+    CS: OutputPin,
+{
+    type Error = SpiBusError<BUS::Error, CS::Error>;
+}
+
+impl<BUS, CS> SpiDevice for SpiBusDevice<BUS, CS>
+where
+    BUS: SpiBus,
+    CS: OutputPin,
+{
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        self.cs.set_low().map_err(SpiBusError::Cs)?;
+        let result = self.run(operations);
+        // the chip select pin is deasserted even if the transfer failed
+        let flushed = self.bus.flush().map_err(SpiBusError::Spi);
+        let deasserted = self.cs.set_high().map_err(SpiBusError::Cs);
+        result.and(flushed).and(deasserted)
+    }
+}
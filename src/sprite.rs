@@ -0,0 +1,108 @@
+//! Sprite sheets: many images of the same size in one raw image, e.g. a set of icons
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    image::{Image, ImageDrawable, ImageDrawableExt, SubImage},
+    primitives::Rectangle,
+    Drawable,
+};
+
+/// Raw image, e.g. an `ImageRaw<TriColor>` or a [`PlanarImage`](crate::PlanarImage), which
+/// is divided into sprites of the same size. The sprites are numbered row by row from the top
+/// left, incomplete sprites at the right and bottom edges are ignored. So a set of icons can
+/// be converted and included as one image and drawn by index:
+/// ```ignore
+/// let icons = SpriteSheet::new(include_epd_image!("weather.png"), Size::new(32, 32));
+/// icons.draw_sprite(SUNNY, Point::new(10, 10), &mut display)?;
+/// ```
+#[derive(Clone, Copy)]
+pub struct SpriteSheet<I> {
+    image: I,
+    sprite_size: Size,
+    columns: u32,
+    len: usize,
+}
+
+impl<I: ImageDrawable> SpriteSheet<I> {
+    /// Creates a sprite sheet from `image` with sprites of `sprite_size`
+    #[must_use]
+    pub fn new(image: I, sprite_size: Size) -> Self {
+        let size = image.size();
+        let columns = size.width.checked_div(sprite_size.width).unwrap_or(0);
+        let rows = size.height.checked_div(sprite_size.height).unwrap_or(0);
+        Self {
+            image,
+            sprite_size,
+            columns,
+            len: columns as usize * rows as usize,
+        }
+    }
+
+    /// Returns the number of sprites
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the image contains no complete sprite
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the size of the sprites
+    #[must_use]
+    pub fn sprite_size(&self) -> Size {
+        self.sprite_size
+    }
+
+    /// Returns the whole image
+    pub fn image(&self) -> &I {
+        &self.image
+    }
+
+    /// Returns the area of the sprite with `index` in the image, or `None` if the index is
+    /// outside the sheet
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub fn sprite_area(&self, index: usize) -> Option<Rectangle> {
+        if index >= self.len {
+            return None;
+        }
+        let index = index as u32;
+        let top_left = Point::new(
+            ((index % self.columns) * self.sprite_size.width) as i32,
+            ((index / self.columns) * self.sprite_size.height) as i32,
+        );
+        Some(Rectangle::new(top_left, self.sprite_size))
+    }
+
+    /// Returns the sprite with `index` as image, e.g. to draw it with `Image`, or `None`
+    /// if the index is outside the sheet
+    pub fn sprite(&self, index: usize) -> Option<SubImage<'_, I>> {
+        self.sprite_area(index)
+            .map(|area| self.image.sub_image(&area))
+    }
+
+    /// Draws the sprite with `index` with its top left corner at `top_left`.
+    /// Nothing is drawn if the index is outside the sheet.
+    ///
+    /// # Errors
+    ///
+    /// This function will return the error of the draw target.
+    pub fn draw_sprite<D>(
+        &self,
+        index: usize,
+        top_left: Point,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = I::Color>,
+    {
+        match self.sprite(index) {
+            Some(sprite) => Image::new(&sprite, top_left).draw(target),
+            None => Ok(()),
+        }
+    }
+}
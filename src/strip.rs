@@ -0,0 +1,178 @@
+//! Strip buffers for rendering a frame in parts on devices with little RAM
+
+use core::cmp::min;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    primitives::{PointsIter, Rectangle},
+    Pixel,
+};
+
+use crate::{packing, DisplayRotation, TriColor};
+
+/// Buffer for a horizontal strip of rows of the (unrotated) panel, used with `Epd::update_strips`
+/// to render a frame strip by strip instead of in a full display buffer.
+/// Drawing uses the coordinates of the whole display, pixels outside the strip are discarded.
+/// `STRIP_SIZE` is the size of each plane of the strip in bytes and must be a multiple of
+/// the bytes per row (`SIZE_H / 8`). E.g. `Strip<300, 400, 1000>` covers 20 rows of the 4.2" e-paper.
+pub struct Strip<const SIZE_V: u32, const SIZE_H: u32, const STRIP_SIZE: usize> {
+    buffer_black: [u8; STRIP_SIZE],
+    buffer_red: [u8; STRIP_SIZE],
+    rotation: DisplayRotation,
+    /// first buffer row covered by the strip
+    first_row: u32,
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const STRIP_SIZE: usize>
+    Strip<SIZE_V, SIZE_H, STRIP_SIZE>
+{
+    const ROW_SIZE: usize = SIZE_H as usize / 8;
+
+    /// Checks at compile time that `STRIP_SIZE` is a multiple of the row size, it is evaluated
+    /// when a strip is created. So e.g. `Strip<296, 152, 5000>` fails to compile:
+    /// ```compile_fail
+    /// let strip = epd_spectra::Strip::<296, 152, 5000>::new();
+    /// ```
+    pub(crate) const SIZE_CHECK: () = assert!(
+        SIZE_H.is_multiple_of(8)
+            && Self::ROW_SIZE > 0
+            && STRIP_SIZE > 0
+            && STRIP_SIZE.is_multiple_of(Self::ROW_SIZE),
+        "STRIP_SIZE must be a multiple of SIZE_H / 8"
+    );
+
+    /// Number of buffer rows covered by a strip
+    #[allow(clippy::cast_possible_truncation)]
+    pub const ROWS: u32 = {
+        let () = Self::SIZE_CHECK;
+        (STRIP_SIZE / Self::ROW_SIZE) as u32
+    };
+
+    /// Creates an empty (white) strip covering the first rows
+    #[must_use]
+    pub const fn new() -> Self {
+        let () = Self::SIZE_CHECK;
+        Self {
+            buffer_black: [0; STRIP_SIZE],
+            buffer_red: [0; STRIP_SIZE],
+            rotation: DisplayRotation::Rotate0,
+            first_row: 0,
+        }
+    }
+
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) {
+        self.rotation = rotation;
+    }
+    #[must_use]
+    pub fn rotation(&self) -> DisplayRotation {
+        self.rotation
+    }
+
+    /// Returns the area of the display (in display coordinates) which is covered by the
+    /// current strip. Drawing can be limited to content within this area.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn area(&self) -> Rectangle {
+        let strip = Rectangle::new(
+            Point::new(0, self.first_row as i32),
+            Size::new(SIZE_H, self.rows()),
+        );
+        self.rotation
+            .display_area(&strip, Size::new(SIZE_H, SIZE_V))
+    }
+
+    /// Returns the black plane of the current strip, see `Display::black_plane`
+    #[must_use]
+    pub fn black_plane(&self) -> &[u8] {
+        self.buffer_black
+            .get(..self.plane_size())
+            .unwrap_or_default()
+    }
+
+    /// Returns the chromatic (red) plane of the current strip, see `Display::chromatic_plane`
+    #[must_use]
+    pub fn chromatic_plane(&self) -> &[u8] {
+        self.buffer_red.get(..self.plane_size()).unwrap_or_default()
+    }
+
+    /// Returns the first buffer row of each strip of a frame
+    pub(crate) fn first_rows() -> impl Iterator<Item = u32> {
+        (0..SIZE_V).step_by(Self::ROWS as usize)
+    }
+
+    /// Moves the strip to the rows starting at `first_row` and clears it
+    pub(crate) fn start(&mut self, first_row: u32) {
+        self.first_row = first_row;
+        self.buffer_black.fill(0);
+        self.buffer_red.fill(0);
+    }
+
+    /// Returns the number of rows of the current strip, the last strip may be shorter
+    fn rows(&self) -> u32 {
+        min(Self::ROWS, SIZE_V.saturating_sub(self.first_row))
+    }
+
+    fn plane_size(&self) -> usize {
+        self.rows() as usize * Self::ROW_SIZE
+    }
+
+    /// Converts a point in display coordinates into the position (x, y) of the pixel in the
+    /// strip. Returns `None` if the point is outside the strip.
+    fn strip_position(&self, p: Point) -> Option<(usize, usize)> {
+        let (x, y) = self
+            .rotation
+            .buffer_position(p, Size::new(SIZE_H, SIZE_V))?;
+        let y = y.checked_sub(self.first_row as usize)?;
+        if y >= self.rows() as usize {
+            return None;
+        }
+        Some((x, y))
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const STRIP_SIZE: usize> Default
+    for Strip<SIZE_V, SIZE_H, STRIP_SIZE>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const STRIP_SIZE: usize> OriginDimensions
+    for Strip<SIZE_V, SIZE_H, STRIP_SIZE>
+{
+    fn size(&self) -> Size {
+        self.rotation.display_size(Size::new(SIZE_H, SIZE_V))
+    }
+}
+
+impl<const SIZE_V: u32, const SIZE_H: u32, const STRIP_SIZE: usize> DrawTarget
+    for Strip<SIZE_V, SIZE_H, STRIP_SIZE>
+{
+    type Color = TriColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(p, color) in pixels {
+            if let Some(position) = self.strip_position(p) {
+                packing::set_pixel(
+                    &mut self.buffer_black,
+                    &mut self.buffer_red,
+                    SIZE_H,
+                    position,
+                    color,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        // Only the part within the strip needs to be drawn
+        let area = area.intersection(&self.area());
+        self.draw_iter(area.points().map(|p| Pixel(p, color)))
+    }
+}
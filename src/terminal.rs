@@ -0,0 +1,130 @@
+//! Draw target which maps terminal colors to [`TriColor`], e.g. for terminal-style UIs
+//! rendered with ratatui and the `mousefood` backend
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::Dimensions,
+    pixelcolor::{Rgb888, RgbColor},
+    primitives::Rectangle,
+    Pixel,
+};
+
+use crate::TriColor;
+
+/// Draw target for `Rgb888` content with terminal colors, like the output of the embedded
+/// ratatui backend `mousefood`, which draws to a [`TriColor`] draw target, e.g. a display
+/// buffer. It owns the target, so it can be passed to backends which require a `'static`
+/// draw target. Red colors are drawn red, other saturated colors (blue, green, ...) black,
+/// so that they stay readable, and grays black or white.
+///
+/// Terminal UIs usually assume a dark background with light text. By default black and
+/// white are therefore swapped, so the UI is shown as dark text on the white e-paper.
+pub struct TerminalTarget<D> {
+    target: D,
+    dark_background: bool,
+}
+
+impl<D> TerminalTarget<D>
+where
+    D: DrawTarget<Color = TriColor>,
+{
+    /// Creates a terminal target which draws to `target`, swapping black and white
+    pub fn new(target: D) -> Self {
+        Self {
+            target,
+            dark_background: true,
+        }
+    }
+
+    /// Swaps black and white if the UI assumes a dark background (the default)
+    pub fn set_dark_background(&mut self, dark_background: bool) {
+        self.dark_background = dark_background;
+    }
+    #[must_use]
+    pub fn dark_background(&self) -> bool {
+        self.dark_background
+    }
+
+    /// Returns the target, e.g. to send the display buffer to the e-paper
+    pub fn target(&self) -> &D {
+        &self.target
+    }
+
+    /// Returns the target as mutable reference
+    pub fn target_mut(&mut self) -> &mut D {
+        &mut self.target
+    }
+
+    /// Returns the target
+    pub fn into_target(self) -> D {
+        self.target
+    }
+}
+
+/// Returns the color for the terminal color `color`
+fn terminal_color(color: Rgb888, dark_background: bool) -> TriColor {
+    let channels = [color.r(), color.g(), color.b()];
+    let max = channels.iter().copied().max().unwrap_or_default();
+    let min = channels.iter().copied().min().unwrap_or_default();
+    if max - min > u8::MAX / 4 {
+        if color.r() > color.g() && color.r() > color.b() {
+            TriColor::Red
+        } else {
+            TriColor::Black
+        }
+    } else if (max > u8::MAX / 2) == dark_background {
+        TriColor::Black
+    } else {
+        TriColor::White
+    }
+}
+
+impl<D> Dimensions for TerminalTarget<D>
+where
+    D: DrawTarget<Color = TriColor>,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D> DrawTarget for TerminalTarget<D>
+where
+    D: DrawTarget<Color = TriColor>,
+{
+    type Color = Rgb888;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let dark_background = self.dark_background;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(p, color)| Pixel(p, terminal_color(color, dark_background))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let dark_background = self.dark_background;
+        let colors = colors
+            .into_iter()
+            .map(|color| terminal_color(color, dark_background));
+        self.target.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let color = terminal_color(color, self.dark_background);
+        self.target.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let color = terminal_color(color, self.dark_background);
+        self.target.clear(color)
+    }
+}
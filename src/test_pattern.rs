@@ -0,0 +1,88 @@
+//! Test patterns, e.g. for board bring-up and checking panels
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    primitives::PointsIter,
+    Drawable,
+};
+
+use crate::TriColor;
+
+/// Test patterns which fill the whole draw target, e.g. a display buffer:
+/// `TestPattern::ColorBars.draw(&mut display)`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TestPattern {
+    /// Squares of `size` x `size` pixels alternating between both colors
+    Checkerboard { size: u32, colors: [TriColor; 2] },
+    /// Three columns of equal width: white, black and red
+    ColorBars,
+    /// Stripes of `width` rows alternating between both colors
+    HorizontalStripes { width: u32, colors: [TriColor; 2] },
+    /// Stripes of `width` columns alternating between both colors
+    VerticalStripes { width: u32, colors: [TriColor; 2] },
+    /// Single pixels of `color` every `spacing` pixels in both directions on `background`
+    Grid {
+        spacing: u32,
+        color: TriColor,
+        background: TriColor,
+    },
+}
+
+impl TestPattern {
+    /// Returns the color of the pattern at point `p` for a draw target with the given size
+    #[allow(clippy::cast_sign_loss)]
+    fn color(self, p: Point, size: Size) -> TriColor {
+        let (x, y) = (p.x as u32, p.y as u32);
+        match self {
+            TestPattern::Checkerboard { size, colors } => {
+                let size = size.max(1);
+                alternate(colors, x / size + y / size)
+            }
+            TestPattern::ColorBars => match x / size.width.div_ceil(3).max(1) {
+                0 => TriColor::White,
+                1 => TriColor::Black,
+                _ => TriColor::Red,
+            },
+            TestPattern::HorizontalStripes { width, colors } => alternate(colors, y / width.max(1)),
+            TestPattern::VerticalStripes { width, colors } => alternate(colors, x / width.max(1)),
+            TestPattern::Grid {
+                spacing,
+                color,
+                background,
+            } => {
+                let spacing = spacing.max(1);
+                if x % spacing == 0 && y % spacing == 0 {
+                    color
+                } else {
+                    background
+                }
+            }
+        }
+    }
+}
+
+/// Returns the first color for even `n` and the second one for odd `n`
+fn alternate([even, odd]: [TriColor; 2], n: u32) -> TriColor {
+    if n.is_multiple_of(2) {
+        even
+    } else {
+        odd
+    }
+}
+
+impl Drawable for TestPattern {
+    type Color = TriColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let area = target.bounding_box();
+        let colors = area
+            .points()
+            .map(|p| self.color(p - area.top_left, area.size));
+        target.fill_contiguous(&area, colors)
+    }
+}
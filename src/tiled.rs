@@ -0,0 +1,155 @@
+//! Draw target spanning several independent panels of the same type arranged in a grid
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    primitives::Rectangle,
+    Pixel,
+};
+
+use crate::{EpdDisplay, EpdDisplays, EpdPanel};
+
+/// Several displays of the same type arranged in a grid, row by row from the top left,
+/// which act as one large canvas, e.g. a wall of panels. Drawing is routed to the display
+/// buffers of the tiles and [`Self::update_all`] refreshes all panels at the same time.
+/// All tiles must have the same size (with rotation), which is taken from the first one,
+/// so the size of the canvas follows when the rotation of the tiles is changed.
+pub struct TiledDisplay<P: EpdPanel, const N: usize> {
+    tiles: [EpdDisplay<P>; N],
+    columns: u32,
+}
+
+impl<P: EpdPanel, const N: usize> TiledDisplay<P, N> {
+    /// Creates a canvas with the given number of columns from `tiles`, ordered row by row
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(tiles: [EpdDisplay<P>; N], columns: u32) -> Self {
+        Self {
+            tiles,
+            columns: columns.clamp(1, N.max(1) as u32),
+        }
+    }
+
+    /// Show the display buffers of all tiles, the panels refresh at the same time.
+    /// This function is blocking until all refreshes are complete.
+    ///
+    /// # Errors
+    ///
+    /// This function will return the first error of a panel.
+    pub fn update_all(&mut self, delay: &mut P::Delay) -> Result<(), P::Error> {
+        self.tiles.flush_all(delay)
+    }
+
+    /// Power off all panels
+    ///
+    /// # Errors
+    ///
+    /// This function will return the first error of a panel.
+    pub fn power_off_all(&mut self, delay: &mut P::Delay) -> Result<(), P::Error> {
+        self.tiles.power_off_all(delay)
+    }
+
+    /// Returns the tiles, e.g. to update a single one
+    pub fn tiles_mut(&mut self) -> &mut [EpdDisplay<P>; N] {
+        &mut self.tiles
+    }
+
+    /// Returns the tiles
+    pub fn tiles(&self) -> &[EpdDisplay<P>; N] {
+        &self.tiles
+    }
+
+    /// Returns the tiles
+    pub fn into_tiles(self) -> [EpdDisplay<P>; N] {
+        self.tiles
+    }
+
+    /// Returns the size of a tile in display coordinates, i.e. with its rotation
+    fn tile_size(&self) -> Size {
+        self.tiles
+            .first()
+            .map_or(Size::zero(), |tile| tile.bounding_box().size)
+    }
+
+    /// Returns the area of the tile with `index` on the canvas
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn tile_area(&self, index: usize) -> Rectangle {
+        let index = index as u32;
+        let column = index % self.columns;
+        let row = index / self.columns;
+        let tile_size = self.tile_size();
+        let top_left = Point::new(
+            (column * tile_size.width) as i32,
+            (row * tile_size.height) as i32,
+        );
+        Rectangle::new(top_left, tile_size)
+    }
+
+    /// Returns the index of the tile which contains `p` and the point in tile coordinates
+    #[allow(clippy::cast_sign_loss)]
+    fn locate(&self, p: Point) -> Option<(usize, Point)> {
+        let tile_size = self.tile_size();
+        if p.x < 0 || p.y < 0 || tile_size.width == 0 || tile_size.height == 0 {
+            return None;
+        }
+        let column = p.x as u32 / tile_size.width;
+        let row = p.y as u32 / tile_size.height;
+        if column >= self.columns {
+            return None;
+        }
+        let index = (row * self.columns + column) as usize;
+        (index < N).then(|| (index, p - self.tile_area(index).top_left))
+    }
+}
+
+impl<P: EpdPanel, const N: usize> OriginDimensions for TiledDisplay<P, N> {
+    #[allow(clippy::cast_possible_truncation)]
+    fn size(&self) -> Size {
+        let rows = (N as u32).div_ceil(self.columns);
+        let tile_size = self.tile_size();
+        Size::new(self.columns * tile_size.width, rows * tile_size.height)
+    }
+}
+
+impl<P: EpdPanel, const N: usize> DrawTarget for TiledDisplay<P, N> {
+    type Color = P::Color;
+    type Error = <P::Framebuffer as DrawTarget>::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(p, color) in pixels {
+            if let Some((tile, p)) = self
+                .locate(p)
+                .and_then(|(index, p)| self.tiles.get_mut(index).map(|tile| (tile, p)))
+            {
+                tile.draw_iter([Pixel(p, color)])?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        for index in 0..N {
+            let tile_area = self.tile_area(index);
+            let area = area.intersection(&tile_area);
+            if let Some(tile) = self
+                .tiles
+                .get_mut(index)
+                .filter(|_| area.size != Size::zero())
+            {
+                let area = Rectangle::new(area.top_left - tile_area.top_left, area.size);
+                tile.fill_solid(&area, color)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        for tile in &mut self.tiles {
+            tile.clear(color)?;
+        }
+        Ok(())
+    }
+}
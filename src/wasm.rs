@@ -0,0 +1,86 @@
+//! Preview of display buffers and images in the browser (feature `wasm`), e.g. for designers
+//! without hardware. Compiled to WebAssembly with
+//! `cargo rustc --lib --crate-type cdylib --target wasm32-unknown-unknown --release --features wasm`
+//! and bound with `wasm-bindgen --target web --out-dir pkg`, the rendering of the driver is
+//! shown in a canvas:
+//! ```js
+//! import init, { drawPlanes } from "./pkg/epd_spectra.js";
+//! await init();
+//! drawPlanes(document.getElementById("preview"), black, red, 152);
+//! ```
+
+use std::vec::Vec;
+
+use embedded_graphics::{
+    geometry::{Dimensions, OriginDimensions},
+    image::GetPixel,
+    pixelcolor::{Rgb888, RgbColor},
+    primitives::PointsIter,
+};
+use wasm_bindgen::{prelude::*, Clamped};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, ImageData};
+
+use crate::{PlanarImage, TriColor};
+
+/// Returns the pixels of `source`, e.g. a [`Display`](crate::Display), as RGBA bytes row by
+/// row, the layout of `ImageData` of a canvas
+#[must_use]
+pub fn rgba<S>(source: &S) -> Vec<u8>
+where
+    S: OriginDimensions + GetPixel<Color = TriColor>,
+{
+    source
+        .bounding_box()
+        .points()
+        .flat_map(|p| {
+            let color = Rgb888::from(source.pixel(p).unwrap_or(TriColor::White));
+            [color.r(), color.g(), color.b(), u8::MAX]
+        })
+        .collect()
+}
+
+/// Resizes `canvas` to the size of `source` and draws its content
+///
+/// # Errors
+///
+/// Returns an error if the canvas has no 2D context or the image cannot be drawn.
+pub fn draw_to_canvas<S>(source: &S, canvas: &HtmlCanvasElement) -> Result<(), JsValue>
+where
+    S: OriginDimensions + GetPixel<Color = TriColor>,
+{
+    let size = source.size();
+    canvas.set_width(size.width);
+    canvas.set_height(size.height);
+    let context = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from("canvas has no 2D context"))?
+        .dyn_into::<CanvasRenderingContext2d>()
+        .map_err(|_| JsValue::from("canvas has no 2D context"))?;
+    let data = rgba(source);
+    let image =
+        ImageData::new_with_u8_clamped_array_and_sh(Clamped(&data), size.width, size.height)?;
+    context.put_image_data(&image, 0.0, 0.0)
+}
+
+/// Draws an image with a black and a red plane with the given width, e.g. converted by
+/// `epd-convert` or exported by vendor tools (see [`PlanarImage`]), to `canvas`
+///
+/// # Errors
+///
+/// Returns an error if the canvas has no 2D context or the image cannot be drawn.
+#[wasm_bindgen(js_name = drawPlanes)]
+pub fn draw_planes(
+    canvas: &HtmlCanvasElement,
+    black: &[u8],
+    red: &[u8],
+    width: u32,
+) -> Result<(), JsValue> {
+    draw_to_canvas(&PlanarImage::new(black, red, width), canvas)
+}
+
+/// Returns an image with a black and a red plane with the given width as RGBA bytes
+#[must_use]
+#[wasm_bindgen(js_name = renderPlanes)]
+pub fn render_planes(black: &[u8], red: &[u8], width: u32) -> Vec<u8> {
+    rgba(&PlanarImage::new(black, red, width))
+}
@@ -0,0 +1,258 @@
+//! Simple widgets for menus on tri-color e-paper displays (feature `widgets`): header and
+//! footer bars and a scrollable list with the selection highlighted in red. The list scrolls
+//! page by page, so moving the selection changes only few frames, which suits the slow
+//! full refresh of e-paper displays.
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    mono_font::{MonoFont, MonoTextStyle},
+    primitives::Rectangle,
+    text::{Baseline, Text},
+    Drawable,
+};
+
+use crate::TriColor;
+
+/// Space between the text and the edges of a bar or list row
+const PADDING: u32 = 2;
+/// Width of the scroll bar of a list
+const SCROLL_BAR_WIDTH: u32 = 4;
+
+/// Position of a [`Bar`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BarPosition {
+    Top,
+    Bottom,
+}
+
+/// Bar over the whole width of the draw target with a line of text, e.g. a header with
+/// the title of a menu or a footer with the key assignment
+pub struct Bar<'a> {
+    text: &'a str,
+    font: &'a MonoFont<'a>,
+    position: BarPosition,
+    color: TriColor,
+}
+
+impl<'a> Bar<'a> {
+    /// Creates a black bar at the top with white text
+    #[must_use]
+    pub fn header(text: &'a str, font: &'a MonoFont<'a>) -> Self {
+        Self {
+            text,
+            font,
+            position: BarPosition::Top,
+            color: TriColor::Black,
+        }
+    }
+
+    /// Creates a black bar at the bottom with white text
+    #[must_use]
+    pub fn footer(text: &'a str, font: &'a MonoFont<'a>) -> Self {
+        Self {
+            position: BarPosition::Bottom,
+            ..Self::header(text, font)
+        }
+    }
+
+    /// Sets the color of the bar, the text is white on black and red bars and black otherwise
+    #[must_use]
+    pub fn with_color(self, color: TriColor) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Returns the height of the bar in pixels
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.font.character_size.height + 2 * PADDING
+    }
+
+    /// Returns the area of the bar within `bounds`, e.g. the bounding box of the display
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn area(&self, bounds: &Rectangle) -> Rectangle {
+        let height = self.height().min(bounds.size.height);
+        let y = match self.position {
+            BarPosition::Top => bounds.top_left.y,
+            BarPosition::Bottom => bounds.top_left.y + (bounds.size.height - height) as i32,
+        };
+        Rectangle::new(
+            Point::new(bounds.top_left.x, y),
+            Size::new(bounds.size.width, height),
+        )
+    }
+
+    /// Returns the part of `bounds` which is not covered by the bar, e.g. for a [`List`]
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn remaining(&self, bounds: &Rectangle) -> Rectangle {
+        let height = self.area(bounds).size.height;
+        let top_left = match self.position {
+            BarPosition::Top => bounds.top_left + Point::new(0, height as i32),
+            BarPosition::Bottom => bounds.top_left,
+        };
+        Rectangle::new(
+            top_left,
+            Size::new(bounds.size.width, bounds.size.height - height),
+        )
+    }
+}
+
+impl Drawable for Bar<'_> {
+    type Color = TriColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = TriColor>,
+    {
+        let area = self.area(&target.bounding_box());
+        target.fill_solid(&area, self.color)?;
+        let text_color = match self.color {
+            TriColor::White => TriColor::Black,
+            TriColor::Black | TriColor::Red => TriColor::White,
+        };
+        draw_text(target, self.text, self.font, &area, text_color)
+    }
+}
+
+/// List of text items of which one is selected, drawn within `area` with one item per row.
+/// The selected item is highlighted in red. If the items do not fit into `area`, the list
+/// shows the page with the selected item and a scroll bar on the right.
+pub struct List<'a> {
+    items: &'a [&'a str],
+    font: &'a MonoFont<'a>,
+    area: Rectangle,
+    selected: usize,
+}
+
+impl<'a> List<'a> {
+    /// Creates a list with the first item selected
+    #[must_use]
+    pub fn new(items: &'a [&'a str], font: &'a MonoFont<'a>, area: Rectangle) -> Self {
+        Self {
+            items,
+            font,
+            area,
+            selected: 0,
+        }
+    }
+
+    /// Returns the index of the selected item
+    #[must_use]
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Selects the item with `index`, limited to the last item
+    pub fn set_selected(&mut self, index: usize) {
+        self.selected = index.min(self.items.len().saturating_sub(1));
+    }
+
+    /// Selects the next item, after the last item the first one
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.items.len().max(1);
+    }
+
+    /// Selects the previous item, before the first item the last one
+    pub fn select_previous(&mut self) {
+        self.selected = self
+            .selected
+            .checked_sub(1)
+            .unwrap_or(self.items.len().saturating_sub(1));
+    }
+
+    /// Returns the number of items which fit into the area
+    #[must_use]
+    pub fn rows_per_page(&self) -> usize {
+        (self.area.size.height / self.row_height()).max(1) as usize
+    }
+
+    /// Returns the index of the first item of the page with the selected item
+    #[must_use]
+    pub fn first_visible(&self) -> usize {
+        let rows = self.rows_per_page();
+        self.selected / rows * rows
+    }
+
+    fn row_height(&self) -> u32 {
+        self.font.character_size.height + 2 * PADDING
+    }
+}
+
+impl Drawable for List<'_> {
+    type Color = TriColor;
+    type Output = ();
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = TriColor>,
+    {
+        target.fill_solid(&self.area, TriColor::White)?;
+        let rows = self.rows_per_page();
+        let first = self.first_visible();
+        let scrolling = self.items.len() > rows;
+        let width = if scrolling {
+            self.area.size.width.saturating_sub(SCROLL_BAR_WIDTH)
+        } else {
+            self.area.size.width
+        };
+
+        let row_height = self.row_height();
+        let visible = self.items.iter().enumerate().skip(first).take(rows);
+        for (row, (index, item)) in visible.enumerate() {
+            let row = Rectangle::new(
+                self.area.top_left + Point::new(0, (row as u32 * row_height) as i32),
+                Size::new(width, row_height),
+            );
+            let text_color = if index == self.selected {
+                target.fill_solid(&row, TriColor::Red)?;
+                TriColor::White
+            } else {
+                TriColor::Black
+            };
+            draw_text(target, item, self.font, &row, text_color)?;
+        }
+
+        if scrolling {
+            // the thumb covers the part of the track which corresponds to the visible page
+            let track = self.area.size.height as usize;
+            let count = self.items.len();
+            let top = track * first / count;
+            let bottom = (track * (first + rows) / count).min(track);
+            let thumb = Rectangle::new(
+                self.area.top_left + Point::new(width as i32, top as i32),
+                Size::new(SCROLL_BAR_WIDTH, (bottom - top) as u32),
+            );
+            target.fill_solid(&thumb, TriColor::Black)?;
+        }
+        Ok(())
+    }
+}
+
+/// Draws a line of `text` at the left of `area`, truncated to the width of the area
+#[allow(clippy::cast_possible_wrap)]
+fn draw_text<D>(
+    target: &mut D,
+    text: &str,
+    font: &MonoFont<'_>,
+    area: &Rectangle,
+    color: TriColor,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = TriColor>,
+{
+    let advance = font.character_size.width + font.character_spacing;
+    let max_chars = (area.size.width.saturating_sub(2 * PADDING) / advance.max(1)) as usize;
+    let text = text
+        .char_indices()
+        .nth(max_chars)
+        .and_then(|(end, _)| text.get(..end))
+        .unwrap_or(text);
+    let position = area.top_left + Point::new(PADDING as i32, PADDING as i32);
+    let style = MonoTextStyle::new(font, color);
+    Text::with_baseline(text, position, style, Baseline::Top).draw(target)?;
+    Ok(())
+}
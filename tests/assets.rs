@@ -0,0 +1,58 @@
+use std::{fs, path::PathBuf};
+
+use epd_spectra::convert::{generate_assets, AssetError, ConvertOptions};
+
+const FERRIS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/ferris.bmp");
+
+/// Creates an empty directory `name` in the temporary directory containing copies of
+/// `ferris.bmp` named `files`
+fn assets(name: &str, files: &[&str]) -> PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for file in files {
+        fs::copy(FERRIS, dir.join(file)).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn images_are_converted() {
+    let dir = assets("epd-assets", &["wifi-on.bmp", "battery.BMP", "notes.txt"]);
+    let out = dir.join("assets.rs");
+    let paths = generate_assets(&dir, &out, &ConvertOptions::default()).unwrap();
+    assert_eq!(paths, [dir.join("battery.BMP"), dir.join("wifi-on.bmp")]);
+
+    let module = fs::read_to_string(out).unwrap();
+    assert!(module.contains("pub const BATTERY: ::embedded_graphics::image::ImageRaw"));
+    assert!(module.contains("pub const WIFI_ON: ::embedded_graphics::image::ImageRaw"));
+}
+
+#[test]
+fn duplicate_names_are_rejected() {
+    let dir = assets("epd-assets-duplicate", &["wifi-on.bmp", "wifi_on.bmp"]);
+    let out = dir.join("assets.rs");
+    match generate_assets(&dir, &out, &ConvertOptions::default()) {
+        Err(AssetError::DuplicateName {
+            name,
+            first,
+            second,
+        }) => {
+            assert_eq!(name, "WIFI_ON");
+            assert_eq!(first, dir.join("wifi-on.bmp"));
+            assert_eq!(second, dir.join("wifi_on.bmp"));
+        }
+        result => panic!("{result:?}"),
+    }
+    assert!(!out.exists());
+}
+
+#[test]
+fn empty_names_are_rejected() {
+    let dir = assets("epd-assets-empty", &["--.bmp"]);
+    let out = dir.join("assets.rs");
+    assert!(matches!(
+        generate_assets(&dir, &out, &ConvertOptions::default()),
+        Err(AssetError::EmptyName(path)) if path == dir.join("--.bmp")
+    ));
+}
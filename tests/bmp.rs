@@ -0,0 +1,91 @@
+use embedded_graphics::prelude::*;
+use epd_spectra::{
+    mock::MockLog, BmpError, BmpSource, Display2in13, DitherMethod, DitherPalette, Epd, Error,
+    TriColor,
+};
+
+/// BMP file in memory
+struct Bmp(Vec<u8>);
+
+impl BmpSource for Bmp {
+    fn read_at(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), BmpError> {
+        let start = offset as usize;
+        let data = self
+            .0
+            .get(start..start + buffer.len())
+            .ok_or(BmpError::Format)?;
+        buffer.copy_from_slice(data);
+        Ok(())
+    }
+}
+
+/// Header of a BMP with 32 bits per pixel, followed by `pixels` (BGRA, bottom-up)
+fn bmp(width: i32, height: i32, pixels: &[[u8; 4]]) -> Bmp {
+    let mut file = Vec::new();
+    file.extend_from_slice(b"BM");
+    file.extend_from_slice(&[0; 8]);
+    file.extend_from_slice(&54u32.to_le_bytes());
+    file.extend_from_slice(&40u32.to_le_bytes());
+    file.extend_from_slice(&width.to_le_bytes());
+    file.extend_from_slice(&height.to_le_bytes());
+    file.extend_from_slice(&1u16.to_le_bytes());
+    file.extend_from_slice(&32u16.to_le_bytes());
+    file.extend_from_slice(&[0; 24]);
+    file.extend(pixels.iter().flatten());
+    Bmp(file)
+}
+
+fn show(mut file: Bmp) -> (MockLog, Result<(), ()>) {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    let result = epd.update_from_bmp::<104>(
+        &mut file,
+        Size::new(104, 212),
+        DitherPalette::TriColor,
+        DitherMethod::FloydSteinberg,
+        &mut spi,
+        &mut delay,
+    );
+    let result = match result {
+        Ok(()) => Ok(()),
+        Err(Error::Bmp(BmpError::Format)) => Err(()),
+        Err(error) => panic!("{error:?}"),
+    };
+    (log, result)
+}
+
+#[test]
+fn image_is_shown() {
+    let (black, red, white) = ([0, 0, 0, 0], [0, 0, 255, 0], [255; 4]);
+    // bottom row first
+    let (log, result) = show(bmp(2, 2, &[red, white, black, red]));
+    assert_eq!(result, Ok(()));
+
+    let frame: Display2in13 = log.frame().unwrap();
+    let mut expected = Display2in13::new();
+    for (x, y, color) in [
+        (0, 0, TriColor::Black),
+        (1, 0, TriColor::Red),
+        (0, 1, TriColor::Red),
+    ] {
+        Pixel(Point::new(x, y), color).draw(&mut expected).unwrap();
+    }
+    assert_eq!(frame.black_plane(), expected.black_plane());
+    assert_eq!(frame.chromatic_plane(), expected.chromatic_plane());
+}
+
+#[test]
+fn oversized_headers_are_rejected() {
+    for (width, height) in [
+        (i32::MAX, 1),
+        (1 << 29, 2),
+        (1 << 20, 1 << 12),
+        (1, i32::MIN + 1),
+    ] {
+        let (log, result) = show(bmp(width, height, &[]));
+        assert_eq!(result, Err(()), "{width}x{height}");
+        assert_eq!(log.refreshes(), 0);
+    }
+}
@@ -0,0 +1,88 @@
+use epd_spectra::{
+    c_array::{parse_c_arrays, vendor_planes, CArray, CArrayError},
+    Display2in13,
+};
+
+#[test]
+fn arrays_are_parsed_in_the_order_of_their_definition() {
+    let source = r"
+        // exported image
+        #include <stdint.h>
+        struct point origin = { 1, 2 };
+        const uint8_t image_BW[4] = { 0x0F, 017, 0b101, /* comment, 9 */ 255u };
+        static const unsigned char image_R[] PROGMEM = {
+            0X80, 0, 12UL, // trailing comma
+        };
+    ";
+    assert_eq!(
+        parse_c_arrays(source),
+        Ok(vec![
+            CArray {
+                name: "image_BW".into(),
+                data: vec![15, 15, 5, 255],
+            },
+            CArray {
+                name: "image_R".into(),
+                data: vec![0x80, 0, 12],
+            },
+        ])
+    );
+}
+
+#[test]
+fn invalid_arrays_are_rejected() {
+    let value = parse_c_arrays("uint8_t a[] = {1, 256};");
+    assert_eq!(
+        value,
+        Err(CArrayError::Value {
+            name: "a".into(),
+            value: "256".into(),
+        })
+    );
+    let unterminated = parse_c_arrays("uint8_t b[] = {1, 2");
+    assert_eq!(unterminated, Err(CArrayError::Initializer("b".into())));
+    let nested = parse_c_arrays("uint8_t c[2][1] = {{1}, {2}};");
+    assert_eq!(nested, Err(CArrayError::Initializer("c".into())));
+}
+
+#[test]
+fn planes_are_split_from_one_or_two_arrays() {
+    let one = "const uint8_t frame[] = {1, 2, 3, 4};";
+    assert_eq!(vendor_planes(one), Ok((vec![1, 2], vec![3, 4])));
+    let two = "const uint8_t bw[] = {1, 2}; const uint8_t r[] = {3, 4};";
+    assert_eq!(vendor_planes(two), Ok((vec![1, 2], vec![3, 4])));
+
+    let odd = "const uint8_t frame[] = {1, 2, 3};";
+    assert_eq!(vendor_planes(odd), Err(CArrayError::PlaneSize));
+    let different = "const uint8_t bw[] = {1, 2}; const uint8_t r[] = {3};";
+    assert_eq!(vendor_planes(different), Err(CArrayError::PlaneSize));
+    assert_eq!(vendor_planes(""), Err(CArrayError::ArrayCount(0)));
+    let three = "uint8_t a[] = {1}; uint8_t b[] = {2}; uint8_t c[] = {3};";
+    assert_eq!(vendor_planes(three), Err(CArrayError::ArrayCount(3)));
+}
+
+#[test]
+fn exported_frame_is_restored() {
+    let black: Vec<u8> = (0..=u8::MAX)
+        .cycle()
+        .take(Display2in13::PLANE_SIZE)
+        .collect();
+    let red: Vec<u8> = black.iter().map(|byte| byte.rotate_left(3)).collect();
+    let hex = |plane: &[u8]| {
+        plane
+            .iter()
+            .map(|byte| format!("0x{byte:02X}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let source = format!(
+        "const uint8_t image_BW[] = {{{}}};\nconst uint8_t image_R[] = {{{}}};\n",
+        hex(&black),
+        hex(&red)
+    );
+
+    let (black_plane, red_plane) = vendor_planes(&source).unwrap();
+    let display = Display2in13::from_raw_planes(&black_plane, &red_plane).unwrap();
+    assert_eq!(display.black_plane(), black);
+    assert_eq!(display.chromatic_plane(), red);
+}
@@ -0,0 +1,187 @@
+//! Reference model and proptest strategies shared by the tests which compare the planes of
+//! the display buffers pixel by pixel
+
+#![allow(dead_code)]
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use epd_spectra::{DisplayBuffer, DisplayRotation, TriColor};
+use proptest::{collection::vec, prelude::*, sample::select};
+
+pub const ROTATIONS: [DisplayRotation; 4] = [
+    DisplayRotation::Rotate0,
+    DisplayRotation::Rotate90,
+    DisplayRotation::Rotate180,
+    DisplayRotation::Rotate270,
+];
+
+/// One color per pixel of the rotated display
+#[derive(Clone, PartialEq, Debug)]
+pub struct Reference {
+    pub size: Size,
+    pub colors: Vec<TriColor>,
+}
+
+impl Reference {
+    pub fn new(size: Size) -> Self {
+        let len = usize::try_from(size.width * size.height).unwrap();
+        Self {
+            size,
+            colors: vec![TriColor::White; len],
+        }
+    }
+
+    /// Returns the reference with the colors of `pixel` for each point of the display
+    pub fn from_fn(size: Size, mut pixel: impl FnMut(Point) -> TriColor) -> Self {
+        let mut reference = Self::new(size);
+        for p in Rectangle::new(Point::zero(), size).points() {
+            reference.set(p, pixel(p));
+        }
+        reference
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        let (x, y) = (u32::try_from(p.x).ok()?, u32::try_from(p.y).ok()?);
+        if x < self.size.width && y < self.size.height {
+            Some((y * self.size.width + x) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the color of a pixel, points outside of the display are ignored
+    pub fn set(&mut self, p: Point, color: TriColor) {
+        if let Some(index) = self.index(p) {
+            self.colors[index] = color;
+        }
+    }
+
+    /// Returns the color of a pixel or `None` if the point is outside of the display
+    pub fn get(&self, p: Point) -> Option<TriColor> {
+        self.index(p).map(|index| self.colors[index])
+    }
+
+    /// Returns the planes of the unrotated panel with `width` pixels per row
+    pub fn planes(&self, rotation: DisplayRotation, width: usize, height: usize) -> [Vec<u8>; 2] {
+        let bytes_per_row = width.div_ceil(8);
+        let mut planes = [
+            vec![0; bytes_per_row * height],
+            vec![0; bytes_per_row * height],
+        ];
+        let logical_width = self.size.width as usize;
+        for (i, &color) in self.colors.iter().enumerate() {
+            let (x, y) = (i % logical_width, i / logical_width);
+            let (x, y) = match rotation {
+                DisplayRotation::Rotate0 => (x, y),
+                DisplayRotation::Rotate90 => (width - 1 - y, x),
+                DisplayRotation::Rotate180 => (width - 1 - x, height - 1 - y),
+                DisplayRotation::Rotate270 => (y, height - 1 - x),
+            };
+            let plane = match color {
+                TriColor::White => continue,
+                TriColor::Black => 0,
+                TriColor::Red => 1,
+            };
+            planes[plane][y * bytes_per_row + x / 8] |= 0x80 >> (x % 8);
+        }
+        planes
+    }
+}
+
+/// Drawing operation of a [`DrawTarget`]
+#[derive(Clone, Debug)]
+pub enum Operation {
+    DrawIter(Vec<Pixel<TriColor>>),
+    FillSolid(Rectangle, TriColor),
+    /// The colors are repeated to fill the area
+    FillContiguous(Rectangle, Vec<TriColor>),
+    Clear(TriColor),
+}
+
+impl Operation {
+    /// Applies the operation to `target` and to the reference via `set`, which gets the
+    /// points in the coordinates of `target`. `size` is the size of `target`.
+    pub fn apply<D>(&self, target: &mut D, size: Size, mut set: impl FnMut(Point, TriColor))
+    where
+        D: DrawTarget<Color = TriColor, Error = core::convert::Infallible>,
+    {
+        match self {
+            Operation::DrawIter(pixels) => {
+                for &Pixel(p, color) in pixels {
+                    set(p, color);
+                }
+                target.draw_iter(pixels.iter().copied()).unwrap();
+            }
+            Operation::FillSolid(area, color) => {
+                for p in area.points() {
+                    set(p, *color);
+                }
+                target.fill_solid(area, *color).unwrap();
+            }
+            Operation::FillContiguous(area, colors) => {
+                let colors = || colors.iter().copied().cycle();
+                for (p, color) in area.points().zip(colors()) {
+                    set(p, color);
+                }
+                target.fill_contiguous(area, colors()).unwrap();
+            }
+            Operation::Clear(color) => {
+                for p in Rectangle::new(Point::zero(), size).points() {
+                    set(p, *color);
+                }
+                target.clear(*color).unwrap();
+            }
+        }
+    }
+}
+
+pub fn color() -> impl Strategy<Value = TriColor> {
+    select(vec![TriColor::White, TriColor::Black, TriColor::Red])
+}
+
+pub fn rotation() -> impl Strategy<Value = DisplayRotation> {
+    select(ROTATIONS.to_vec())
+}
+
+/// Points within and up to 8 pixels around the largest display
+pub fn point() -> impl Strategy<Value = Point> {
+    (-8..488_i32, -8..488_i32).prop_map(|(x, y)| Point::new(x, y))
+}
+
+/// Rectangles which may be partially or completely outside of the display
+pub fn rectangle() -> impl Strategy<Value = Rectangle> {
+    (point(), 0..128_u32, 0..128_u32)
+        .prop_map(|(p, width, height)| Rectangle::new(p, Size::new(width, height)))
+}
+
+pub fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        3 => vec((point(), color()).prop_map(|(p, color)| Pixel(p, color)), 1..50)
+            .prop_map(Operation::DrawIter),
+        2 => (rectangle(), color()).prop_map(|(area, color)| Operation::FillSolid(area, color)),
+        2 => (rectangle(), vec(color(), 1..32))
+            .prop_map(|(area, colors)| Operation::FillContiguous(area, colors)),
+        1 => color().prop_map(Operation::Clear),
+    ]
+}
+
+pub fn operations() -> impl Strategy<Value = Vec<Operation>> {
+    vec(operation(), 1..16)
+}
+
+/// Returns the planes sent to the e-paper by `display`
+pub fn written_planes(display: &impl DisplayBuffer) -> [Vec<u8>; 2] {
+    let (mut black, mut red) = (Vec::new(), Vec::new());
+    display
+        .write_black(|data| {
+            black.extend_from_slice(data);
+            Ok::<(), core::convert::Infallible>(())
+        })
+        .unwrap();
+    display
+        .write_red(|data| {
+            red.extend_from_slice(data);
+            Ok::<(), core::convert::Infallible>(())
+        })
+        .unwrap();
+    [black, red]
+}
@@ -0,0 +1,129 @@
+//! Round trips of frames through the `PackBits` encoding of `CompressedFrame`
+
+mod common;
+
+use common::written_planes;
+use epd_spectra::{CompressedFrame, Display2in9};
+use proptest::prelude::*;
+
+const PLANE_SIZE: usize = Display2in9::PLANE_SIZE;
+
+/// Compresses the planes, checks that they are decompressed unchanged and returns the
+/// compressed planes
+fn round_trip(black: &[u8], red: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let display = Display2in9::from_raw_planes(black, red).unwrap();
+    let mut buffer = vec![0; CompressedFrame::max_size(PLANE_SIZE)];
+    let frame = CompressedFrame::compress(&display, &mut buffer).unwrap();
+    assert_eq!(frame.plane_size(), PLANE_SIZE);
+    let [sent_black, sent_red] = written_planes(&frame);
+    assert!(sent_black == black, "black plane differs");
+    assert!(sent_red == red, "red plane differs");
+
+    let (compressed_black, compressed_red) = (frame.black().to_vec(), frame.red().to_vec());
+    assert!(CompressedFrame::new(&compressed_black, &compressed_red).is_some());
+    (compressed_black, compressed_red)
+}
+
+/// Plane in which no byte equals the previous one
+fn distinct(len: usize) -> Vec<u8> {
+    (0..=u8::MAX).cycle().take(len).collect()
+}
+
+#[test]
+fn white_frame_consists_of_full_runs() {
+    let white = vec![0; PLANE_SIZE];
+    let (black, red) = round_trip(&white, &white);
+    let packets = PLANE_SIZE.div_ceil(128);
+    assert_eq!(black.len(), 2 * packets);
+    assert_eq!(&black[..2], [129, 0]);
+    assert_eq!(red, black);
+}
+
+#[test]
+fn frame_without_repeats_consists_of_full_literals() {
+    let plane = distinct(PLANE_SIZE);
+    let (black, _) = round_trip(&plane, &vec![0; PLANE_SIZE]);
+    assert_eq!(black.len(), CompressedFrame::max_size(PLANE_SIZE) / 2);
+    assert_eq!(black[0], 127);
+}
+
+#[test]
+fn run_of_128_bytes_is_one_packet() {
+    let mut plane = distinct(PLANE_SIZE);
+    plane[..128].fill(0xaa);
+    plane[128] = 0x55;
+    let (black, _) = round_trip(&plane, &vec![0; PLANE_SIZE]);
+    assert_eq!(&black[..4], [129, 0xaa, 127, 0x55]);
+}
+
+#[test]
+fn run_of_129_bytes_continues_as_literal() {
+    let mut plane = distinct(PLANE_SIZE);
+    plane[..129].fill(0xaa);
+    plane[129] = 0x55;
+    let (black, _) = round_trip(&plane, &vec![0; PLANE_SIZE]);
+    assert_eq!(&black[..5], [129, 0xaa, 127, 0xaa, 0x55]);
+}
+
+#[test]
+fn last_literal_is_closed_at_the_end_of_the_plane() {
+    let mut plane = vec![0; PLANE_SIZE];
+    for (byte, value) in plane[PLANE_SIZE - 130..].iter_mut().zip(1..) {
+        *byte = value;
+    }
+    let (black, _) = round_trip(&plane, &plane);
+    // a full literal of 128 bytes followed by one of 2 bytes
+    assert_eq!(
+        &black[black.len() - 3..],
+        [1, plane[PLANE_SIZE - 2], plane[PLANE_SIZE - 1]]
+    );
+    assert_eq!(black[black.len() - 3 - 129], 127);
+}
+
+#[test]
+fn malformed_planes_are_rejected() {
+    // literal of 6 bytes with only 2 bytes
+    assert!(CompressedFrame::new(&[5, 1, 2], &[5, 1, 2]).is_none());
+    // repeat without the byte
+    assert!(CompressedFrame::new(&[0, 7], &[0xff]).is_none());
+    // planes of different sizes
+    assert!(CompressedFrame::new(&[0xfe, 7], &[0, 7]).is_none());
+
+    // no-operation headers are skipped
+    let frame = CompressedFrame::new(&[128, 0, 7], &[0, 7, 128]).unwrap();
+    assert_eq!(frame.plane_size(), 1);
+    assert_eq!(written_planes(&frame), [vec![7], vec![7]]);
+}
+
+#[test]
+fn too_small_buffer_is_rejected() {
+    let display = Display2in9::from_raw_planes(&distinct(PLANE_SIZE), &distinct(PLANE_SIZE));
+    let mut buffer = vec![0; CompressedFrame::max_size(PLANE_SIZE) - 1];
+    assert!(CompressedFrame::compress(&display.unwrap(), &mut buffer).is_none());
+}
+
+/// Planes of runs of random bytes with random lengths, mostly short ones
+fn plane() -> impl Strategy<Value = Vec<u8>> {
+    proptest::collection::vec(
+        (any::<u8>(), prop_oneof![3 => 1..4_usize, 1 => 1..300_usize]),
+        1..200,
+    )
+    .prop_map(|runs| {
+        let mut plane: Vec<u8> = runs
+            .into_iter()
+            .flat_map(|(byte, len)| std::iter::repeat_n(byte, len))
+            .take(PLANE_SIZE)
+            .collect();
+        plane.resize(PLANE_SIZE, 0);
+        plane
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn planes_are_restored(black in plane(), red in plane()) {
+        round_trip(&black, &red);
+    }
+}
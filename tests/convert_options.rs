@@ -0,0 +1,37 @@
+use epd_spectra::{
+    convert::{constant_name, parse_dithering, RawFormat},
+    DitherMethod, DitherPalette, ThresholdMatrix,
+};
+
+#[test]
+fn options_are_parsed() {
+    assert_eq!(parse_dithering("none"), Ok(None));
+    assert_eq!(
+        parse_dithering("floyd_steinberg"),
+        Ok(Some(DitherMethod::FloydSteinberg))
+    );
+    assert_eq!(
+        "blue_noise".parse(),
+        Ok(DitherMethod::Ordered(ThresholdMatrix::BlueNoise16))
+    );
+    assert_eq!("monochrome".parse(), Ok(DitherPalette::Monochrome));
+    assert_eq!("planar".parse(), Ok(RawFormat::Planar));
+}
+
+#[test]
+fn invalid_options_name_the_alternatives() {
+    let error = parse_dithering("bayer3").unwrap_err().to_string();
+    assert!(
+        error.contains("`bayer3`") && error.contains("bayer4"),
+        "{error}"
+    );
+    assert!("none".parse::<DitherMethod>().is_err());
+    assert!("red".parse::<DitherPalette>().is_err());
+    assert!("".parse::<RawFormat>().is_err());
+}
+
+#[test]
+fn constant_names_are_identifiers() {
+    assert_eq!(constant_name("wifi-on"), "WIFI_ON");
+    assert_eq!(constant_name("1st icon.v2"), "_1ST_ICON_V2");
+}
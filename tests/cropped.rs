@@ -0,0 +1,61 @@
+//! Draws into a cropped region of a display and compares the planes and the pixels with the
+//! reference of `tests/common`
+
+mod common;
+
+use common::{operations, rectangle, rotation, Reference};
+use embedded_graphics::{image::GetPixel, prelude::*, primitives::Rectangle};
+use epd_spectra::{Display2in9, TriColor};
+use proptest::prelude::*;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn drawing_is_translated_and_clipped_to_the_region(
+        rotation in rotation(),
+        area in rectangle(),
+        operations in operations(),
+    ) {
+        let mut display = Display2in9::new();
+        display.set_rotation(rotation);
+        display.clear(TriColor::Red).unwrap();
+        let mut reference = Reference::new(display.size());
+        reference.colors.fill(TriColor::Red);
+
+        let mut cropped = display.cropped(&area);
+        let local = Rectangle::new(Point::zero(), area.size);
+        for operation in &operations {
+            operation.apply(&mut cropped, area.size, |p, color| {
+                if local.contains(p) {
+                    reference.set(p + area.top_left, color);
+                }
+            });
+        }
+        for p in Rectangle::new(Point::new(-4, -4), area.size + Size::new(8, 8)).points() {
+            let expected = if local.contains(p) {
+                reference.get(p + area.top_left)
+            } else {
+                None
+            };
+            prop_assert_eq!(cropped.pixel(p), expected, "{:?}", p);
+        }
+
+        let [black, red] = reference.planes(
+            rotation,
+            Display2in9::WIDTH as usize,
+            Display2in9::HEIGHT as usize,
+        );
+        prop_assert!(display.black_plane() == black, "black plane differs");
+        prop_assert!(display.chromatic_plane() == red, "chromatic plane differs");
+    }
+}
+
+#[test]
+fn size_is_the_size_of_the_region() {
+    let mut display = Display2in9::new();
+    let area = Rectangle::new(Point::new(10, 20), Size::new(30, 40));
+    let cropped = display.cropped(&area);
+    assert_eq!(cropped.size(), area.size);
+    assert_eq!(cropped.area(), area);
+}
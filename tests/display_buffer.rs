@@ -0,0 +1,104 @@
+#![allow(deprecated)]
+
+use core::convert::Infallible;
+
+use embedded_graphics::prelude::*;
+use epd_spectra::{
+    Display2in13, DisplayBuffer, InterleavedDisplay2in13, MonoDisplay2in13, TriColor,
+};
+
+/// Implementation of the trait before the `write_*` methods were added
+struct Legacy {
+    black: [u8; 4],
+    red: [u8; 4],
+}
+
+impl DisplayBuffer for Legacy {
+    fn get_buffer_black(&self) -> &[u8] {
+        &self.black
+    }
+    fn get_buffer_red(&self) -> &[u8] {
+        &self.red
+    }
+}
+
+fn written(write: impl FnOnce(&mut dyn FnMut(&[u8]) -> Result<(), Infallible>)) -> Vec<u8> {
+    let mut data = Vec::new();
+    write(&mut |chunk| {
+        data.extend_from_slice(chunk);
+        Ok(())
+    });
+    data
+}
+
+#[test]
+fn slices_are_written_by_default() {
+    let legacy = Legacy {
+        black: [1, 2, 3, 4],
+        red: [5, 6, 7, 8],
+    };
+    let black = written(|write| legacy.write_black(write).unwrap());
+    let red = written(|write| legacy.write_red(write).unwrap());
+    assert_eq!(black, [1, 2, 3, 4]);
+    assert_eq!(red, [5, 6, 7, 8]);
+}
+
+#[test]
+fn display_provides_its_planes() {
+    let mut display = Display2in13::new();
+    Pixel(Point::new(0, 0), TriColor::Black)
+        .draw(&mut display)
+        .unwrap();
+    Pixel(Point::new(9, 0), TriColor::Red)
+        .draw(&mut display)
+        .unwrap();
+    assert_eq!(display.get_buffer_black(), display.black_plane());
+    assert_eq!(display.get_buffer_red(), display.chromatic_plane());
+    assert_eq!(
+        written(|write| display.write_black(write).unwrap()),
+        display.black_plane()
+    );
+}
+
+#[test]
+fn getters_of_buffers_converting_on_the_fly_are_empty() {
+    let interleaved = InterleavedDisplay2in13::new();
+    assert!(interleaved.get_buffer_black().is_empty());
+    assert!(interleaved.get_buffer_red().is_empty());
+    let mono = MonoDisplay2in13::new();
+    assert_eq!(mono.get_buffer_black().len(), Display2in13::PLANE_SIZE);
+    assert!(mono.get_buffer_red().is_empty());
+
+    // the getters of a display return the stored planes without its conversion
+    let mut display = Display2in13::new();
+    display.set_inverted(true);
+    assert_eq!(display.get_buffer_black(), display.black_plane());
+    assert_eq!(
+        written(|write| display.write_black(write).unwrap()),
+        vec![0xff; Display2in13::PLANE_SIZE]
+    );
+}
+
+#[test]
+fn interleaved_new_is_white() {
+    let display = InterleavedDisplay2in13::new();
+    assert!(display.buffer().iter().all(|&byte| byte == 0));
+    assert_eq!(
+        display.buffer(),
+        InterleavedDisplay2in13::default().buffer()
+    );
+}
+
+#[test]
+fn converted_planes_have_the_plane_size() {
+    let interleaved = InterleavedDisplay2in13::new();
+    let mono = MonoDisplay2in13::new();
+    for plane in [
+        written(|write| interleaved.write_black(write).unwrap()),
+        written(|write| interleaved.write_red(write).unwrap()),
+        written(|write| mono.write_black(write).unwrap()),
+        written(|write| mono.write_red(write).unwrap()),
+    ] {
+        assert_eq!(plane.len(), Display2in13::PLANE_SIZE);
+    }
+}
@@ -0,0 +1,178 @@
+use embedded_graphics::{
+    image::GetPixel,
+    pixelcolor::{Gray8, Rgb888},
+    prelude::*,
+    primitives::Rectangle,
+};
+use epd_spectra::{
+    Display2in66, DitherMethod, DitherPalette, DitherTarget, ThresholdMatrix, TriColor,
+};
+
+const WIDTH: u32 = 32;
+const HEIGHT: u32 = 16;
+
+/// Gradient over all channels, so every palette color and error occurs
+#[allow(clippy::cast_possible_truncation)]
+fn gradient(x: u32, y: u32) -> Rgb888 {
+    Rgb888::new((x * 8) as u8, (y * 16) as u8, ((x + y) * 5) as u8)
+}
+
+fn channels(color: Rgb888) -> [i16; 3] {
+    [color.r(), color.g(), color.b()].map(i16::from)
+}
+
+/// Adds `weight`/16 of `error` to `errors`
+fn add_error(errors: &mut [i16; 3], error: [i16; 3], weight: i16) {
+    for (value, error) in errors.iter_mut().zip(error) {
+        *value += error * weight / 16;
+    }
+}
+
+/// Floyd–Steinberg dithering of the whole frame at once with an error buffer per pixel,
+/// returns the colors row by row
+fn dither_frame(palette: &[TriColor]) -> Vec<TriColor> {
+    let width = WIDTH as usize;
+    let mut errors = vec![[[0; 3]; WIDTH as usize + 1]; HEIGHT as usize + 1];
+    let mut frame = Vec::new();
+    for row in 0..HEIGHT {
+        for column in 0..WIDTH {
+            let (x, y) = (column as usize, row as usize);
+            let mut rgb = channels(gradient(column, row));
+            for (value, error) in rgb.iter_mut().zip(errors[y][x]) {
+                *value = (*value + error).clamp(0, 255);
+            }
+            let distance = |color: &TriColor| {
+                channels(Rgb888::from(*color))
+                    .iter()
+                    .zip(rgb)
+                    .map(|(a, b)| (i32::from(*a) - i32::from(b)).pow(2))
+                    .sum::<i32>()
+            };
+            let color = palette.iter().copied().min_by_key(distance).unwrap();
+            frame.push(color);
+            let quantized = channels(Rgb888::from(color));
+            let error = [0, 1, 2].map(|i| rgb[i] - quantized[i]);
+            // errors of the column right of the frame are dropped
+            if x + 1 < width {
+                add_error(&mut errors[y][x + 1], error, 7);
+                add_error(&mut errors[y + 1][x + 1], error, 1);
+            }
+            if x > 0 {
+                add_error(&mut errors[y + 1][x - 1], error, 3);
+            }
+            add_error(&mut errors[y + 1][x], error, 5);
+        }
+    }
+    frame
+}
+
+#[test]
+#[allow(clippy::cast_sign_loss)]
+fn row_by_row_dithering_matches_the_whole_frame() {
+    let mut display = Display2in66::new();
+    let mut target =
+        DitherTarget::<_, Rgb888, { WIDTH as usize }>::new(&mut display, DitherPalette::TriColor);
+    let area = Rectangle::new(Point::zero(), Size::new(WIDTH, HEIGHT));
+    let colors = area.points().map(|p| gradient(p.x as u32, p.y as u32));
+    target.fill_contiguous(&area, colors).unwrap();
+
+    let frame = dither_frame(&[TriColor::White, TriColor::Black, TriColor::Red]);
+    for (p, expected) in area.points().zip(frame) {
+        assert_eq!(display.pixel(p), Some(expected), "{p:?}");
+    }
+}
+
+#[test]
+fn half_gray_gives_half_black_pixels() {
+    let mut display = Display2in66::new();
+    let mut target = DitherTarget::<_, Gray8, 64>::new(&mut display, DitherPalette::Monochrome);
+    let area = Rectangle::new(Point::zero(), Size::new(64, 64));
+    target.fill_solid(&area, Gray8::new(128)).unwrap();
+
+    let black = area
+        .points()
+        .filter(|&p| display.pixel(p) == Some(TriColor::Black))
+        .count();
+    // 127/255 of the 4096 pixels are black on average
+    assert!((1946..=2150).contains(&black), "{black}");
+    assert!(area
+        .points()
+        .all(|p| display.pixel(p) != Some(TriColor::Red)));
+}
+
+const MATRICES: [(ThresholdMatrix, i32); 4] = [
+    (ThresholdMatrix::Bayer2, 2),
+    (ThresholdMatrix::Bayer4, 4),
+    (ThresholdMatrix::Bayer8, 8),
+    (ThresholdMatrix::BlueNoise16, 16),
+];
+
+/// Dithers an area of 32 x 32 pixels of `gray` with `matrix`
+fn ordered(matrix: ThresholdMatrix, gray: u8) -> Display2in66 {
+    let mut display = Display2in66::new();
+    let mut target = DitherTarget::<_, Gray8, 0>::new(&mut display, DitherPalette::Monochrome);
+    target.set_method(DitherMethod::Ordered(matrix));
+    let area = Rectangle::new(Point::zero(), Size::new(32, 32));
+    target.fill_solid(&area, Gray8::new(gray)).unwrap();
+    display
+}
+
+/// Returns true if the area of 32 x 32 pixels repeats every `period` pixels in both directions
+fn is_periodic(display: &Display2in66, period: i32) -> bool {
+    let area = Rectangle::new(Point::zero(), Size::new(32, 32));
+    area.points().all(|p| {
+        let tile = Point::new(p.x % period, p.y % period);
+        display.pixel(p) == display.pixel(tile)
+    })
+}
+
+#[test]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn threshold_maps_contain_each_level_once() {
+    // a gray of k / n² shows k white pixels per tile of n x n pixels, so the thresholds of
+    // a tile are spread evenly over the gray levels
+    for (matrix, size) in MATRICES {
+        let levels = size * size;
+        for k in 0..levels {
+            let display = ordered(matrix, (256 * k / levels) as u8);
+            let tile = Rectangle::new(Point::zero(), Size::new(size as u32, size as u32));
+            let white = tile
+                .points()
+                .filter(|&p| display.pixel(p) == Some(TriColor::White))
+                .count();
+            assert_eq!(white, k as usize, "{matrix:?} {k}");
+        }
+    }
+
+    // half gray is a checkerboard with the 2 x 2 Bayer matrix
+    let display = ordered(ThresholdMatrix::Bayer2, 128);
+    for p in Rectangle::new(Point::zero(), Size::new(32, 32)).points() {
+        let expected = if (p.x + p.y) % 2 == 0 {
+            TriColor::Black
+        } else {
+            TriColor::White
+        };
+        assert_eq!(display.pixel(p), Some(expected), "{p:?}");
+    }
+}
+
+#[test]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn threshold_maps_are_tiled_with_their_size() {
+    for (matrix, size) in MATRICES {
+        let levels = size * size;
+        let displays: Vec<Display2in66> = (1..levels)
+            .map(|k| ordered(matrix, (256 * k / levels) as u8))
+            .collect();
+        assert!(
+            displays.iter().all(|display| is_periodic(display, size)),
+            "{matrix:?}"
+        );
+        assert!(
+            !displays
+                .iter()
+                .all(|display| is_periodic(display, size / 2)),
+            "{matrix:?}"
+        );
+    }
+}
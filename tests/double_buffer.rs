@@ -0,0 +1,77 @@
+mod common;
+
+use common::{operations, rotation, Reference};
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use epd_spectra::{Display2in13, Display2in9, DoubleBuffered, TriColor};
+use proptest::prelude::*;
+
+#[test]
+fn drawing_changes_the_frame() {
+    let mut frame = DoubleBuffered::new(Display2in13::new());
+    assert!(!frame.is_changed());
+    Pixel(Point::new(10, 20), TriColor::Red)
+        .draw(&mut *frame)
+        .unwrap();
+    assert!(frame.is_changed());
+    assert_eq!(
+        frame.changed_area(),
+        Some(Rectangle::new(Point::new(10, 20), Size::new(1, 1)))
+    );
+    frame.mark_transmitted();
+    assert!(!frame.is_changed());
+    assert_eq!(frame.changed_area(), None);
+}
+
+#[test]
+fn flags_change_the_frame() {
+    let mut frame = DoubleBuffered::new(Display2in13::new());
+    frame.set_inverted(true);
+    assert!(frame.is_changed());
+    assert_eq!(frame.changed_area(), Some(frame.bounding_box()));
+    frame.mark_transmitted();
+    assert!(!frame.is_changed());
+
+    frame.set_chromatic_as_black(true);
+    assert!(frame.is_changed());
+    frame.mark_transmitted();
+    assert!(!frame.is_changed());
+    assert!(frame.previous().inverted() && frame.previous().chromatic_as_black());
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn changed_area_is_the_bounding_box_of_the_changed_pixels(
+        rotation in rotation(),
+        transmitted in operations(),
+        drawn in operations(),
+    ) {
+        let mut display = Display2in9::new();
+        display.set_rotation(rotation);
+        let size = display.size();
+        let mut frame = DoubleBuffered::new(display);
+        let mut reference = Reference::new(size);
+        for operation in &transmitted {
+            operation.apply(&mut *frame, size, |p, color| reference.set(p, color));
+        }
+        frame.mark_transmitted();
+        let previous = reference.clone();
+        for operation in &drawn {
+            operation.apply(&mut *frame, size, |p, color| reference.set(p, color));
+        }
+
+        let changed: Vec<Point> = Rectangle::new(Point::zero(), size)
+            .points()
+            .filter(|&p| reference.get(p) != previous.get(p))
+            .collect();
+        let expected = changed.first().map(|&first| {
+            let (top_left, bottom_right) = changed.iter().fold((first, first), |(min, max), &p| {
+                (min.component_min(p), max.component_max(p))
+            });
+            Rectangle::with_corners(top_left, bottom_right)
+        });
+        prop_assert_eq!(frame.is_changed(), expected.is_some());
+        prop_assert_eq!(frame.changed_area(), expected);
+    }
+}
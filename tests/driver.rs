@@ -0,0 +1,456 @@
+use embedded_graphics::{image::GetPixel, prelude::*, primitives::Rectangle};
+use epd_spectra::{
+    mock::{MockEvent, MockLog, MockSpiError},
+    Display2in66, Epd, Error, MonoDisplay2in66, TriColor, OPERATING_TEMPERATURE,
+};
+
+#[test]
+fn failed_power_off_returns_the_driver() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    epd.update(&Display2in66::new(), &mut spi, &mut delay)
+        .unwrap();
+
+    log.set_busy(true);
+    let Err((epd, error)) = epd.try_power_off(&mut spi, &mut delay) else {
+        panic!("power off succeeded while busy");
+    };
+    assert!(matches!(error, Error::Timeout), "{error:?}");
+
+    log.set_busy(false);
+    assert!(epd.power_off(&mut spi, &mut delay).is_ok());
+}
+
+#[test]
+fn temperature_is_checked_before_the_transfer() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let mut epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    // the operating range of the datasheet by default
+    epd.set_temperature(45);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    log.clear();
+    let result = epd.update(&Display2in66::new(), &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::Temperature(45))), "{result:?}");
+    epd.set_temperature(*OPERATING_TEMPERATURE.start() - 1);
+    let result = epd.update(&Display2in66::new(), &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::Temperature(-1))), "{result:?}");
+    assert!(log.commands().is_empty());
+
+    epd.set_temperature(*OPERATING_TEMPERATURE.end());
+    epd.update(&Display2in66::new(), &mut spi, &mut delay)
+        .unwrap();
+    assert_eq!(log.refreshes(), 1);
+
+    // no range at all
+    epd.set_temperature(45);
+    epd.set_temperature_range(None);
+    epd.update(&Display2in66::new(), &mut spi, &mut delay)
+        .unwrap();
+    assert_eq!(log.refreshes(), 2);
+}
+
+#[test]
+fn with_active_powers_off_after_errors() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    log.set_busy(true);
+    let (_epd, result) = epd.with_active(&mut spi, &mut delay, |epd, spi, delay| {
+        epd.update(&Display2in66::new(), spi, delay)
+    });
+    assert!(
+        matches!(result, Err(Error::PanelNotResponding)),
+        "{result:?}"
+    );
+    // the power off is sent although the panel stays busy
+    assert_eq!(log.commands().last().map(|c| c.command), Some(0x02));
+    assert_eq!(log.refreshes(), 0);
+}
+
+#[test]
+fn planes_of_the_wrong_size_are_rejected() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    log.clear();
+
+    let size = Size::new(152, 296);
+    let plane = [0; 5624];
+    let result = epd.update_raw(&plane, &plane[1..], size, &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::FrameSize)), "{result:?}");
+    assert!(log.commands().is_empty());
+
+    // the size hints of arrays are checked before anything is sent
+    let result = epd.update_from_iters(plane, [0; 5625], size, &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::FrameSize)), "{result:?}");
+    let result = epd.update_from_iters([0; 5623], plane, size, &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::FrameSize)), "{result:?}");
+    assert!(log.commands().is_empty());
+
+    // without a size hint the length is only known after sending
+    let short = plane.into_iter().filter(|_| true).skip(1);
+    let result = epd.update_from_iters(plane, short, size, &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::FrameSize)), "{result:?}");
+    assert_eq!(log.refreshes(), 0);
+
+    epd.update_raw(&plane, &plane, size, &mut spi, &mut delay)
+        .unwrap();
+    assert_eq!(log.refreshes(), 1);
+    // a size passed with a frame is not taken as the size of the panel
+    assert_eq!(epd.dimensions(), None);
+}
+
+#[test]
+fn frames_of_another_panel_size_are_rejected() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let mut epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    epd.set_dimensions(Size::new(152, 296));
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    log.clear();
+
+    let size = Size::new(104, 212);
+    let plane = [0; 2756];
+    let result = epd.update_raw(&plane, &plane, size, &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::FrameSize)), "{result:?}");
+    let result = epd.update_from_iters(plane, plane, size, &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::FrameSize)), "{result:?}");
+    let result = epd.update_from_fn(size, |_, _| TriColor::Red, &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::FrameSize)), "{result:?}");
+    assert!(log.commands().is_empty());
+    assert_eq!(epd.dimensions(), Some(Size::new(152, 296)));
+
+    let plane = [0; 5624];
+    epd.update_raw(&plane, &plane, Size::new(152, 296), &mut spi, &mut delay)
+        .unwrap();
+    assert_eq!(log.refreshes(), 1);
+}
+
+#[test]
+fn init_fails_while_the_panel_stays_busy() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    log.set_busy(true);
+    let result = epd.init(&mut spi, &mut delay);
+    assert!(
+        matches!(result, Err(Error::PanelNotResponding)),
+        "{:?}",
+        result.err()
+    );
+
+    // a panel which needs some time after the reset is fine
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    log.set_busy_polls(20);
+    assert!(epd.init(&mut spi, &mut delay).is_ok());
+}
+
+#[test]
+fn init_detects_a_missing_panel_if_enabled() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let soft_reset = MockEvent::Data(vec![0x0e]);
+
+    // by default only the not-busy signal is awaited, like by the vendor driver
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    assert!(epd.init(&mut spi, &mut delay).is_ok());
+    let events = log.events();
+    let after_reset = events
+        .iter()
+        .position(|event| *event == soft_reset)
+        .unwrap()
+        + 1;
+    assert!(!matches!(events[after_reset], MockEvent::Delay(_)));
+    log.clear();
+
+    let mut epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    epd.set_detect_missing_panel(true);
+    let result = epd.init(&mut spi, &mut delay);
+    assert!(
+        matches!(result, Err(Error::PanelNotResponding)),
+        "{:?}",
+        result.err()
+    );
+    // the busy signal is polled for 10 ms after the soft reset
+    let events = log.events();
+    let after_reset = events
+        .iter()
+        .position(|event| *event == soft_reset)
+        .unwrap()
+        + 1;
+    assert_eq!(
+        &events[after_reset..],
+        vec![MockEvent::Delay(1_000_000); 10]
+    );
+
+    let mut epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    epd.set_detect_missing_panel(true);
+    log.set_busy_after_reset(3);
+    assert!(epd.init(&mut spi, &mut delay).is_ok());
+}
+
+#[test]
+fn updates_fail_until_the_started_refresh_is_complete() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    epd.start_update(&Display2in66::new(), &mut spi, &mut delay)
+        .unwrap();
+    log.set_busy(true);
+    log.clear();
+
+    let result = epd.update(&Display2in66::new(), &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::Busy)), "{result:?}");
+    let result = epd.start_update(&Display2in66::new(), &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::Busy)), "{result:?}");
+    assert!(log.commands().is_empty());
+
+    log.set_busy_polls(3);
+    let mut polls = 0;
+    epd.wait_while_busy(|_| polls += 1).unwrap();
+    assert_eq!(polls, 3);
+    epd.update(&Display2in66::new(), &mut spi, &mut delay)
+        .unwrap();
+    assert_eq!(log.refreshes(), 1);
+}
+
+#[test]
+fn refreshes_within_the_timeout_succeed() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    log.set_busy_polls(500);
+    epd.update(&Display2in66::new(), &mut spi, &mut delay)
+        .unwrap();
+    log.set_busy_polls(500);
+    assert!(epd.power_off(&mut spi, &mut delay).is_ok());
+}
+
+#[test]
+fn mono_display_sends_red_as_black_and_a_white_chromatic_plane() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+
+    let black = Rectangle::new(Point::new(2, 3), Size::new(10, 4));
+    let red = Rectangle::new(Point::new(40, 100), Size::new(5, 9));
+    let mut mono = MonoDisplay2in66::new();
+    mono.fill_solid(&black, TriColor::Black).unwrap();
+    mono.fill_solid(&red, TriColor::Red).unwrap();
+    epd.update(&mono, &mut spi, &mut delay).unwrap();
+
+    let mut expected = Display2in66::new();
+    expected.fill_solid(&black, TriColor::Black).unwrap();
+    expected.fill_solid(&red, TriColor::Black).unwrap();
+    let sent: Display2in66 = log.frame().unwrap();
+    assert_eq!(sent.black_plane(), expected.black_plane());
+    assert!(sent.chromatic_plane().iter().all(|&byte| byte == 0));
+}
+
+/// Returns the colors of a black, a red and a white pixel of `display` as sent to the e-paper
+fn sent_colors(display: &mut Display2in66) -> [TriColor; 3] {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    let points = [Point::new(0, 0), Point::new(1, 0), Point::new(2, 0)];
+    Pixel(points[0], TriColor::Black).draw(display).unwrap();
+    Pixel(points[1], TriColor::Red).draw(display).unwrap();
+    epd.update(display, &mut spi, &mut delay).unwrap();
+    let sent: Display2in66 = log.frame().unwrap();
+    points.map(|p| sent.pixel(p).unwrap())
+}
+
+#[test]
+fn inverted_displays_swap_black_and_white_when_sent() {
+    use TriColor::{Black, Red, White};
+    let mut display = Display2in66::new();
+    display.set_inverted(true);
+    assert_eq!(sent_colors(&mut display), [White, Red, Black]);
+    // the buffer itself is not changed
+    assert_eq!(display.pixel(Point::new(0, 0)), Some(Black));
+
+    let mut display = Display2in66::new();
+    display.set_chromatic_as_black(true);
+    assert_eq!(sent_colors(&mut display), [Black, Black, White]);
+
+    // red is shown as black first and then inverted
+    display.set_inverted(true);
+    assert_eq!(sent_colors(&mut display), [White, White, Black]);
+}
+
+#[test]
+fn red_pixels_are_sent_in_the_black_plane() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    let mut display = Display2in66::new();
+    display.set_chromatic_as_black(true);
+    // the first byte of the planes holds the top left 8 pixels
+    Pixel(Point::new(0, 0), TriColor::Red)
+        .draw(&mut display)
+        .unwrap();
+    Pixel(Point::new(7, 0), TriColor::Black)
+        .draw(&mut display)
+        .unwrap();
+    log.clear();
+    epd.update(&display, &mut spi, &mut delay).unwrap();
+
+    let commands = log.commands();
+    let plane = |command| {
+        commands
+            .iter()
+            .find(|c| c.command == command)
+            .map(|c| c.data.clone())
+            .unwrap()
+    };
+    let black = plane(0x10);
+    assert_eq!(black.len(), Display2in66::PLANE_SIZE);
+    assert_eq!(black[0], 0b1000_0001);
+    assert!(black[1..].iter().all(|&byte| byte == 0));
+    let red = plane(0x13);
+    assert_eq!(red.len(), Display2in66::PLANE_SIZE);
+    assert!(red.iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn only_update_if_changed_skips_the_frame_shown_last() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    let display = Display2in66::new();
+
+    assert!(epd
+        .update_if_changed(&display, &mut spi, &mut delay)
+        .unwrap());
+    assert!(!epd
+        .update_if_changed(&display, &mut spi, &mut delay)
+        .unwrap());
+    assert_eq!(log.refreshes(), 1);
+
+    // other updates send new frame data without hashing it
+    epd.update(&display, &mut spi, &mut delay).unwrap();
+    assert!(epd
+        .update_if_changed(&display, &mut spi, &mut delay)
+        .unwrap());
+    assert_eq!(log.refreshes(), 3);
+
+    // a rejected frame does not reach the controller, so the hash stays valid
+    epd.set_temperature(45);
+    let result = epd.update(&display, &mut spi, &mut delay);
+    assert!(matches!(result, Err(Error::Temperature(45))), "{result:?}");
+    epd.set_temperature(20);
+    assert!(!epd
+        .update_if_changed(&display, &mut spi, &mut delay)
+        .unwrap());
+    assert_eq!(log.refreshes(), 3);
+}
+
+/// Returns the number of SPI transactions of an update of `display`
+fn update_transactions(
+    display: &Display2in66,
+    spi_chunk_size: usize,
+    writes_per_transaction: usize,
+) -> usize {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let mut epd = Epd::new(
+        &mut spi,
+        log.busy(),
+        log.dc(),
+        log.rst(),
+        &mut delay,
+        spi_chunk_size,
+    );
+    epd.set_writes_per_transaction(writes_per_transaction);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    log.clear();
+    epd.update(display, &mut spi, &mut delay).unwrap();
+    log.transactions()
+}
+
+#[test]
+fn planes_are_written_in_batched_transactions() {
+    // the commands and register data take 6 transactions
+    assert_eq!(Display2in66::PLANE_SIZE, 5624);
+    let mut display = Display2in66::new();
+    // 88 chunks of 64 bytes per plane, 8 chunks per transaction by default
+    assert_eq!(update_transactions(&display, 64, 8), 2 * 11 + 6);
+    assert_eq!(update_transactions(&display, 64, 1), 2 * 88 + 6);
+    // the converted chunks of 64 bytes are collected in batches of 512 bytes
+    display.set_inverted(true);
+    assert_eq!(update_transactions(&display, 0, 8), 2 * 11 + 6);
+    assert_eq!(update_transactions(&display, 64, 8), 2 * 11 + 6);
+}
+
+#[test]
+fn spi_errors_of_the_transfer_are_retried() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let mut epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    epd.set_spi_retries(2);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    let mut display = Display2in66::new();
+    let area = Rectangle::new(Point::new(10, 20), Size::new(30, 40));
+    display.fill_solid(&area, TriColor::Red).unwrap();
+    let resets = || {
+        log.events()
+            .iter()
+            .filter(|event| **event == MockEvent::Reset(false))
+            .count()
+    };
+
+    // each failure of the black plane is followed by a reset and a new transfer
+    log.clear();
+    log.fail_data_of(0x10, 2);
+    epd.update(&display, &mut spi, &mut delay).unwrap();
+    assert_eq!(resets(), 2);
+    assert_eq!(log.refreshes(), 1);
+    let sent: Display2in66 = log.frame().unwrap();
+    assert_eq!(sent.chromatic_plane(), display.chromatic_plane());
+
+    // the error is returned after all retries failed, without powering on the e-paper
+    log.clear();
+    log.fail_data_of(0x10, 3);
+    let result = epd.update(&display, &mut spi, &mut delay);
+    assert!(
+        matches!(result, Err(Error::Spi(MockSpiError))),
+        "{result:?}"
+    );
+    assert_eq!(resets(), 2);
+    assert_eq!(log.refreshes(), 0);
+    assert!(log.commands().iter().all(|command| command.command != 0x04));
+}
+
+/// Returns the data of the PSR sent by init with the given dimensions
+fn psr_data(dimensions: Option<Size>) -> Vec<u8> {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let mut epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    if let Some(size) = dimensions {
+        epd.set_dimensions(size);
+    }
+    let _epd = epd.init(&mut spi, &mut delay).unwrap();
+    // the soft reset is sent with the same command before
+    log.commands()
+        .into_iter()
+        .rfind(|command| command.command == 0x00)
+        .unwrap()
+        .data
+}
+
+#[test]
+fn only_the_4in17_panel_gets_the_psr_of_the_4in2_size() {
+    assert_eq!(psr_data(Some(Size::new(400, 300))), [0x0f, 0x89]);
+    assert_eq!(psr_data(Some(Size::new(152, 296))), [0xcf, 0x8d]);
+    assert_eq!(psr_data(None), [0xcf, 0x8d]);
+}
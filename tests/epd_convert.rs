@@ -0,0 +1,45 @@
+use std::process::Command;
+
+fn epd_convert(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_epd-convert"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn help_succeeds() {
+    for flag in ["-h", "--help"] {
+        let output = epd_convert(&[flag]);
+        assert!(output.status.success());
+        assert!(String::from_utf8_lossy(&output.stdout).starts_with("Usage: epd-convert"));
+    }
+}
+
+#[test]
+fn invalid_option_fails() {
+    let output = epd_convert(&["--palette", "red", "image.png"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid palette `red`"));
+}
+
+#[test]
+fn names_starting_with_a_digit_are_valid() {
+    let dir = std::env::temp_dir().join("epd-convert-test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let image = dir.join("1st-ferris.bmp");
+    std::fs::copy(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/examples/ferris.bmp"),
+        &image,
+    )
+    .unwrap();
+
+    let output = epd_convert(&[image.to_str().unwrap()]);
+    assert!(output.status.success());
+    let code = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        code.starts_with("const _1ST_FERRIS_WIDTH: u32 = 150;"),
+        "{code}"
+    );
+    assert!(code.contains("const _1ST_FERRIS_IMG: &[u8] = &["));
+}
@@ -0,0 +1,72 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use epd_spectra::{Display2in13, DisplayRotation, TriColor};
+
+const COLORS: [TriColor; 3] = [TriColor::White, TriColor::Black, TriColor::Red];
+
+fn pattern(len: usize) -> Vec<TriColor> {
+    (0..len).map(|i| COLORS[(i * 7 + i / 5) % 3]).collect()
+}
+
+/// Fills `area` once with `fill_contiguous` and once pixel by pixel, the planes must match
+fn assert_fill_matches(rotation: DisplayRotation, area: Rectangle, len: usize) {
+    let colors = pattern(len);
+    let mut filled = Display2in13::new();
+    let mut drawn = Display2in13::new();
+    for display in [&mut filled, &mut drawn] {
+        display.set_rotation(rotation);
+        display.clear(TriColor::Red).unwrap();
+    }
+
+    filled.fill_contiguous(&area, colors.clone()).unwrap();
+    drawn
+        .draw_iter(area.points().zip(colors).map(|(p, color)| Pixel(p, color)))
+        .unwrap();
+    assert_eq!(filled.black_plane(), drawn.black_plane(), "{area:?}");
+    assert_eq!(
+        filled.chromatic_plane(),
+        drawn.chromatic_plane(),
+        "{area:?}"
+    );
+}
+
+#[test]
+fn aligned_fill_matches_pixels() {
+    for area in [
+        Rectangle::new(Point::new(0, 0), Size::new(104, 212)),
+        Rectangle::new(Point::new(8, 3), Size::new(16, 10)),
+        Rectangle::new(Point::new(96, 200), Size::new(8, 12)),
+    ] {
+        let len = area.points().count();
+        assert_fill_matches(DisplayRotation::Rotate0, area, len);
+        // the colors end within a byte, the rest keeps the background
+        assert_fill_matches(DisplayRotation::Rotate0, area, len / 2 + 3);
+    }
+}
+
+#[test]
+fn unaligned_fill_matches_pixels() {
+    for rotation in [
+        DisplayRotation::Rotate0,
+        DisplayRotation::Rotate90,
+        DisplayRotation::Rotate180,
+        DisplayRotation::Rotate270,
+    ] {
+        for area in [
+            Rectangle::new(Point::new(3, 5), Size::new(17, 9)),
+            Rectangle::new(Point::new(-4, -2), Size::new(16, 8)),
+            Rectangle::new(Point::new(96, 200), Size::new(16, 20)),
+        ] {
+            assert_fill_matches(rotation, area, area.points().count());
+        }
+    }
+}
+
+#[test]
+fn aligned_fill_marks_dirty_region() {
+    let mut display = Display2in13::new();
+    let area = Rectangle::new(Point::new(16, 4), Size::new(24, 6));
+    display
+        .fill_contiguous(&area, pattern(area.points().count()))
+        .unwrap();
+    assert_eq!(display.take_dirty_region(), Some(area));
+}
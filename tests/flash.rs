@@ -0,0 +1,116 @@
+use core::convert::Infallible;
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use embedded_storage::nor_flash::{ErrorType, NorFlashErrorKind, ReadNorFlash};
+use epd_spectra::{
+    mock::{MockLog, MockSpiError},
+    Display2in66, Epd, Error, TriColor,
+};
+
+/// Offset of the red plane in the flash
+const RED_OFFSET: u32 = 8192;
+
+/// Unrotated size of the 2.66" panel
+const SIZE: Size = Size::new(152, 296);
+
+/// Flash in memory, reads have to be aligned to `READ_SIZE` like with a real flash
+struct Flash<const READ_SIZE: usize>(Vec<u8>);
+
+impl<const READ_SIZE: usize> ErrorType for Flash<READ_SIZE> {
+    type Error = NorFlashErrorKind;
+}
+
+impl<const READ_SIZE: usize> ReadNorFlash for Flash<READ_SIZE> {
+    const READ_SIZE: usize = READ_SIZE;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        let start = offset as usize;
+        if !start.is_multiple_of(READ_SIZE) || !bytes.len().is_multiple_of(READ_SIZE) {
+            return Err(NorFlashErrorKind::NotAligned);
+        }
+        let data = self
+            .0
+            .get(start..start + bytes.len())
+            .ok_or(NorFlashErrorKind::OutOfBounds)?;
+        bytes.copy_from_slice(data);
+        Ok(())
+    }
+
+    fn capacity(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Returns a display with some black and red content
+fn display() -> Display2in66 {
+    let mut display = Display2in66::new();
+    let area = Rectangle::new(Point::new(5, 7), Size::new(50, 60));
+    display.fill_solid(&area, TriColor::Black).unwrap();
+    let area = Rectangle::new(Point::new(40, 100), Size::new(70, 30));
+    display.fill_solid(&area, TriColor::Red).unwrap();
+    display
+}
+
+/// Returns a flash with the planes of `display` at offset 0 and `RED_OFFSET`
+fn flash<const READ_SIZE: usize>(display: &Display2in66) -> Flash<READ_SIZE> {
+    let mut data = vec![0xff; 2 * RED_OFFSET as usize];
+    let red = RED_OFFSET as usize;
+    data[..Display2in66::PLANE_SIZE].copy_from_slice(display.black_plane());
+    data[red..red + Display2in66::PLANE_SIZE].copy_from_slice(display.chromatic_plane());
+    Flash(data)
+}
+
+/// Result of `update_from_flash` with the mocks
+type FlashResult = Result<(), Error<MockSpiError, Infallible, Infallible>>;
+
+/// Shows the frame of `flash` with `size` on the 2.66" panel and returns the log of the mocks
+/// after the initialization
+fn show<const READ_SIZE: usize>(
+    flash: &mut Flash<READ_SIZE>,
+    size: Size,
+) -> (MockLog, FlashResult) {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let mut epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    epd.set_dimensions(SIZE);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    log.clear();
+    let result = epd.update_from_flash(flash, 0, RED_OFFSET, size, &mut spi, &mut delay);
+    (log, result)
+}
+
+#[test]
+fn frame_is_read_from_the_flash() {
+    let display = display();
+    let (log, result) = show(&mut flash::<4>(&display), SIZE);
+    result.unwrap();
+    let sent: Display2in66 = log.frame().unwrap();
+    assert_eq!(sent.black_plane(), display.black_plane());
+    assert_eq!(sent.chromatic_plane(), display.chromatic_plane());
+}
+
+#[test]
+fn read_size_which_does_not_divide_the_chunks_is_rejected() {
+    // chunks of 64 bytes would be read with a length of 48, the following offsets would not
+    // be aligned anymore
+    let (log, result) = show(&mut flash::<48>(&display()), SIZE);
+    assert!(
+        matches!(result, Err(Error::Flash(NorFlashErrorKind::NotAligned))),
+        "{result:?}"
+    );
+    assert!(log.events().is_empty());
+
+    let (_, result) = show(&mut flash::<128>(&display()), SIZE);
+    assert!(
+        matches!(result, Err(Error::Flash(NorFlashErrorKind::NotAligned))),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn frame_of_another_panel_size_is_rejected() {
+    // the planes of the 2.13" panel are smaller, the frame would be incomplete
+    let (log, result) = show(&mut flash::<4>(&display()), Size::new(104, 212));
+    assert!(matches!(result, Err(Error::FrameSize)), "{result:?}");
+    assert!(log.events().is_empty());
+}
@@ -0,0 +1,144 @@
+//! Compares the sequence sent for init, update and power off of each panel size with the
+//! golden files in `tests/golden`. The files are written by hand from the sequence of the
+//! vendor driver, so they must not be regenerated from the output of this driver.
+
+use std::{fmt::Write, fs, path::Path};
+
+use embedded_graphics::prelude::*;
+use epd_spectra::{
+    mock::{MockEvent, MockLog},
+    Display, Display1in54, Display2in13, Display2in66, Display2in71, Display2in87, Display2in9,
+    Display3in70, Display4in17, Display4in37, Epd, TriColor,
+};
+
+/// Appends the bytes in hex, runs of at least 4 equal bytes as `byte*count`
+fn write_bytes(line: &mut String, bytes: &[u8]) {
+    for run in bytes.chunk_by(|a, b| a == b) {
+        if run.len() >= 4 {
+            write!(line, " {:02x}*{}", run[0], run.len()).unwrap();
+        } else {
+            for byte in run {
+                write!(line, " {byte:02x}").unwrap();
+            }
+        }
+    }
+}
+
+/// Appends the pending data and delay as lines
+fn flush(text: &mut String, data: &mut Vec<u8>, delay_ns: &mut u32) {
+    if !data.is_empty() {
+        text.push_str("data");
+        write_bytes(text, data);
+        text.push('\n');
+        data.clear();
+    }
+    if *delay_ns % 1_000_000 == 0 && *delay_ns > 0 {
+        writeln!(text, "delay {} ms", *delay_ns / 1_000_000).unwrap();
+    } else if *delay_ns > 0 {
+        writeln!(text, "delay {delay_ns} ns").unwrap();
+    }
+    *delay_ns = 0;
+}
+
+/// Returns the recorded events as text, one line per event. The chunks of a plane and the
+/// slices of a delay are merged into one line.
+fn format_events(events: &[MockEvent]) -> String {
+    let mut text = String::new();
+    let mut data = Vec::new();
+    let mut delay_ns = 0;
+    for event in events {
+        match event {
+            MockEvent::Data(bytes) => {
+                if delay_ns > 0 {
+                    flush(&mut text, &mut data, &mut delay_ns);
+                }
+                data.extend_from_slice(bytes);
+                continue;
+            }
+            MockEvent::Delay(ns) => {
+                if !data.is_empty() {
+                    flush(&mut text, &mut data, &mut delay_ns);
+                }
+                delay_ns += ns;
+                continue;
+            }
+            _ => flush(&mut text, &mut data, &mut delay_ns),
+        }
+        match event {
+            MockEvent::Command(bytes) => {
+                text.push_str("command");
+                write_bytes(&mut text, bytes);
+            }
+            MockEvent::Reset(high) => text.push_str(if *high { "reset high" } else { "reset low" }),
+            MockEvent::Data(_) | MockEvent::Delay(_) => {}
+        }
+        text.push('\n');
+    }
+    flush(&mut text, &mut data, &mut delay_ns);
+    text
+}
+
+/// Records init, an update and the power off of a frame with a black pixel in the top left
+/// and a red pixel in the bottom right corner
+fn record<const SIZE_V: u32, const SIZE_H: u32, const IMAGE_SIZE: usize>(
+    mut display: Display<SIZE_V, SIZE_H, IMAGE_SIZE>,
+) -> String {
+    let bottom_right = display.bounding_box().bottom_right().unwrap();
+    Pixel(Point::zero(), TriColor::Black)
+        .draw(&mut display)
+        .unwrap();
+    Pixel(bottom_right, TriColor::Red)
+        .draw(&mut display)
+        .unwrap();
+
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let mut epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    epd.set_dimensions(Size::new(SIZE_H, SIZE_V));
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    epd.update(&display, &mut spi, &mut delay).unwrap();
+    epd.power_off(&mut spi, &mut delay).unwrap();
+    format_events(&log.events())
+}
+
+/// Compares the sequence with the golden file without its comment lines starting with `#`
+fn check(name: &str, sequence: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.txt"));
+    let golden =
+        fs::read_to_string(&path).unwrap_or_else(|error| panic!("{}: {error}", path.display()));
+    let golden: String = golden
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .flat_map(|line| [line, "\n"])
+        .collect();
+    assert!(
+        sequence == golden,
+        "the sequence of {name} differs from the vendor sequence in {}:\n{sequence}",
+        path.display()
+    );
+}
+
+macro_rules! golden {
+    ($($name:ident: $display:ty,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check(stringify!($name), &record(<$display>::new()));
+            }
+        )*
+    };
+}
+
+golden! {
+    display1in54: Display1in54,
+    display2in13: Display2in13,
+    display2in66: Display2in66,
+    display2in71: Display2in71,
+    display2in87: Display2in87,
+    display2in9: Display2in9,
+    display3in70: Display3in70,
+    display4in17: Display4in17,
+    display4in37: Display4in37,
+}
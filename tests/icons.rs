@@ -0,0 +1,172 @@
+use core::convert::Infallible;
+use std::collections::BTreeMap;
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use epd_spectra::{
+    icons::{Alert, Battery, Wifi},
+    TriColor,
+};
+
+/// Records the last color drawn to each pixel
+#[derive(Default)]
+struct Canvas(BTreeMap<(i32, i32), TriColor>);
+
+impl Canvas {
+    /// Draws `icon` on an empty canvas
+    fn draw(icon: &impl Drawable<Color = TriColor>) -> Self {
+        let mut canvas = Self::default();
+        icon.draw(&mut canvas).unwrap();
+        canvas
+    }
+
+    fn at(&self, x: i32, y: i32) -> Option<TriColor> {
+        self.0.get(&(x, y)).copied()
+    }
+
+    /// Returns the area which was drawn to, the icons are opaque so it is filled completely
+    fn drawn_area(&self) -> Rectangle {
+        let area = Rectangle::with_corners(
+            self.0.keys().min().map(|&(x, y)| Point::new(x, y)).unwrap(),
+            self.0.keys().max().map(|&(x, y)| Point::new(x, y)).unwrap(),
+        );
+        assert!(area.points().all(|p| self.0.contains_key(&(p.x, p.y))));
+        area
+    }
+}
+
+impl OriginDimensions for Canvas {
+    fn size(&self) -> Size {
+        Size::new(64, 64)
+    }
+}
+
+impl DrawTarget for Canvas {
+    type Color = TriColor;
+    type Error = Infallible;
+
+    fn draw_iter<I: IntoIterator<Item = Pixel<TriColor>>>(
+        &mut self,
+        pixels: I,
+    ) -> Result<(), Infallible> {
+        for Pixel(p, color) in pixels {
+            self.0.insert((p.x, p.y), color);
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn battery_fill_follows_the_level() {
+    // outline stroke 2, terminal 4 wide, fill from x = 9 to 36 with 28 pixels at 100 %
+    let area = Rectangle::new(Point::new(5, 7), Size::new(40, 16));
+    for (level, fill, last_fill_x) in [
+        (0, None, None),
+        (10, Some(TriColor::Red), Some(10)),
+        (20, Some(TriColor::Red), Some(13)),
+        (50, Some(TriColor::Black), Some(22)),
+        (100, Some(TriColor::Black), Some(36)),
+        (250, Some(TriColor::Black), Some(36)),
+    ] {
+        let battery = Battery::new(area, level);
+        assert_eq!(battery.bounding_box(), area);
+        let canvas = Canvas::draw(&battery);
+        assert_eq!(canvas.drawn_area(), area, "{level}");
+
+        // outline and terminal
+        assert_eq!(canvas.at(5, 7), Some(TriColor::Black), "{level}");
+        assert_eq!(canvas.at(40, 22), Some(TriColor::Black), "{level}");
+        assert_eq!(canvas.at(42, 15), Some(TriColor::Black), "{level}");
+        assert_eq!(canvas.at(42, 7), Some(TriColor::White), "{level}");
+        // gap between outline and fill
+        assert_eq!(canvas.at(8, 11), Some(TriColor::White), "{level}");
+
+        assert_eq!(
+            canvas.at(9, 11),
+            Some(fill.unwrap_or(TriColor::White)),
+            "{level}"
+        );
+        let last_fill_x = last_fill_x.unwrap_or(8);
+        assert_eq!(canvas.at(last_fill_x, 18), fill.or(Some(TriColor::White)));
+        assert_eq!(canvas.at(last_fill_x + 1, 18), Some(TriColor::White));
+    }
+}
+
+#[test]
+fn battery_accent_and_low_level_can_be_set() {
+    let area = Rectangle::new(Point::zero(), Size::new(40, 16));
+    let black_accent = Canvas::draw(&Battery::new(area, 10).with_accent(TriColor::Black));
+    assert_eq!(black_accent.at(4, 4), Some(TriColor::Black));
+    let not_low = Canvas::draw(&Battery::new(area, 10).with_low_level(5));
+    assert_eq!(not_low.at(4, 4), Some(TriColor::Black));
+    let red = Canvas::draw(&Battery::new(area, 50).with_color(TriColor::Red));
+    assert_eq!(red.at(0, 0), Some(TriColor::Red));
+    assert_eq!(red.at(4, 4), Some(TriColor::Red));
+}
+
+#[test]
+fn wifi_arcs_follow_the_strength() {
+    // 12 pixels high, the dot is at the bottom center (11, 11), the arcs cross the center
+    // column at y = 7, 4 and 0
+    let bounding_box = Rectangle::new(Point::new(3, 2), Size::new(23, 12));
+    for strength in 0..=4 {
+        let wifi = Wifi::new(Point::new(3, 2), 12, strength);
+        assert_eq!(wifi.bounding_box(), bounding_box);
+        let canvas = Canvas::draw(&wifi);
+        assert_eq!(canvas.drawn_area(), bounding_box, "{strength}");
+
+        let lit = |shown: bool| match (shown, strength) {
+            (false, _) => Some(TriColor::White),
+            (true, 0) => Some(TriColor::Red),
+            (true, _) => Some(TriColor::Black),
+        };
+        let at = |x, y| canvas.at(3 + x, 2 + y);
+        assert_eq!(at(11, 11), lit(true), "{strength}");
+        assert_eq!(at(11, 8), lit(false), "{strength}");
+        assert_eq!(at(11, 7), lit(true), "{strength}");
+        assert_eq!(at(11, 4), lit(strength != 1), "{strength}");
+        assert_eq!(at(11, 0), lit(strength == 0 || strength >= 3), "{strength}");
+        // outside of the quarter circle
+        assert_eq!(at(0, 11), Some(TriColor::White), "{strength}");
+        assert_eq!(at(0, 0), Some(TriColor::White), "{strength}");
+    }
+}
+
+#[test]
+fn wifi_colors_can_be_set() {
+    let weak = Canvas::draw(&Wifi::new(Point::zero(), 12, 1).with_color(TriColor::Red));
+    assert_eq!(weak.at(11, 11), Some(TriColor::Red));
+    let off = Canvas::draw(&Wifi::new(Point::zero(), 12, 0).with_accent(TriColor::Black));
+    assert_eq!(off.at(11, 0), Some(TriColor::Black));
+}
+
+#[test]
+fn alert_is_a_triangle_with_an_exclamation_mark() {
+    // the dot of the exclamation mark starts at 3/4 of the height
+    for (height, width, dot) in [(15, 15, 11), (16, 17, 12)] {
+        let alert = Alert::new(Point::new(1, 2), height);
+        let bounding_box = Rectangle::new(Point::new(1, 2), Size::new(width, height));
+        assert_eq!(alert.bounding_box(), bounding_box);
+        let canvas = Canvas::draw(&alert);
+        assert_eq!(canvas.drawn_area(), bounding_box, "{height}");
+
+        let Point {
+            x: right,
+            y: bottom,
+        } = bounding_box.bottom_right().unwrap() - Point::new(1, 2);
+        let center = right / 2;
+        let at = |x, y| canvas.at(1 + x, 2 + y);
+        assert_eq!(at(center, 0), Some(TriColor::Red), "{height}");
+        assert_eq!(at(0, 0), Some(TriColor::White), "{height}");
+        assert_eq!(at(right, 0), Some(TriColor::White), "{height}");
+        assert_eq!(at(0, bottom), Some(TriColor::Red), "{height}");
+        assert_eq!(at(right, bottom), Some(TriColor::Red), "{height}");
+        // bar and dot of the exclamation mark
+        assert_eq!(at(center, 4), Some(TriColor::Red), "{height}");
+        assert_eq!(at(center, 6), Some(TriColor::White), "{height}");
+        assert_eq!(at(center, 10), Some(TriColor::Red), "{height}");
+        assert_eq!(at(center, dot), Some(TriColor::White), "{height}");
+    }
+
+    let black = Canvas::draw(&Alert::new(Point::zero(), 15).with_accent(TriColor::Black));
+    assert_eq!(black.at(7, 0), Some(TriColor::Black));
+}
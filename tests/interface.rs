@@ -0,0 +1,103 @@
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::{
+    digital::OutputPin,
+    spi::{Operation, SpiDevice},
+};
+use epd_spectra::{Interface, InterfaceError};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Sent {
+    Commands(Vec<u8>),
+    Data(Vec<u8>),
+}
+
+/// Display interface which records what is sent and fails if `fail` is set
+#[derive(Default)]
+struct MockInterface {
+    sent: Vec<Sent>,
+    fail: bool,
+}
+
+fn bytes(format: DataFormat<'_>) -> Vec<u8> {
+    match format {
+        DataFormat::U8(bytes) => bytes.to_vec(),
+        _ => panic!("unexpected data format"),
+    }
+}
+
+impl WriteOnlyDataCommand for MockInterface {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        if self.fail {
+            return Err(DisplayError::BusWriteError);
+        }
+        self.sent.push(Sent::Commands(bytes(cmd)));
+        Ok(())
+    }
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        if self.fail {
+            return Err(DisplayError::BusWriteError);
+        }
+        self.sent.push(Sent::Data(bytes(buf)));
+        Ok(())
+    }
+}
+
+#[test]
+fn dc_pin_selects_commands_or_data() {
+    let interface = Interface::new(MockInterface::default());
+    let (mut spi, mut dc) = interface.split();
+    dc.set_low().unwrap();
+    spi.write(&[0x00]).unwrap();
+    dc.set_high().unwrap();
+    spi.transaction(&mut [Operation::Write(&[1, 2]), Operation::Write(&[3])])
+        .unwrap();
+    dc.set_low().unwrap();
+    spi.write(&[0x12]).unwrap();
+    assert_eq!(
+        interface.into_inner().sent,
+        [
+            Sent::Commands(vec![0x00]),
+            Sent::Data(vec![1, 2]),
+            Sent::Data(vec![3]),
+            Sent::Commands(vec![0x12]),
+        ]
+    );
+}
+
+#[test]
+fn only_writes_are_supported() {
+    let interface = Interface::new(MockInterface::default());
+    let (mut spi, _dc) = interface.split();
+    let (mut read, mut transfer, mut in_place) = ([0; 1], [0; 1], [0; 1]);
+    let operations = [
+        Operation::Read(&mut read),
+        Operation::Transfer(&mut transfer, &[1]),
+        Operation::TransferInPlace(&mut in_place),
+        Operation::DelayNs(1_000),
+    ];
+    for operation in operations {
+        let result = spi.transaction(&mut [operation]);
+        assert!(
+            matches!(result, Err(InterfaceError::Unsupported)),
+            "{result:?}"
+        );
+    }
+    assert!(interface.into_inner().sent.is_empty());
+}
+
+#[test]
+fn errors_of_the_interface_are_returned() {
+    let interface = Interface::new(MockInterface {
+        fail: true,
+        ..MockInterface::default()
+    });
+    let (mut spi, _dc) = interface.split();
+    let result = spi.write(&[0x00]);
+    assert!(
+        matches!(
+            result,
+            Err(InterfaceError::Interface(DisplayError::BusWriteError))
+        ),
+        "{result:?}"
+    );
+}
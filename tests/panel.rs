@@ -0,0 +1,67 @@
+use std::cell::RefCell;
+
+use epd_spectra::{mock::MockLog, Display2in66, Epd, EpdPanel, Panel, UpdatePhase};
+
+const INPUT_TEMPERATURE: u8 = 0xe5;
+const POWER_OFF: u8 = 0x02;
+
+thread_local! {
+    static PHASES: RefCell<Vec<UpdatePhase>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(phase: UpdatePhase) {
+    PHASES.with_borrow_mut(|phases| phases.push(phase));
+}
+
+fn count(log: &MockLog, command: u8) -> usize {
+    log.commands()
+        .iter()
+        .filter(|c| c.command == command)
+        .count()
+}
+
+#[test]
+fn panel_tracks_the_power_state() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    let mut panel: Panel<296, 152, 5624, _, _, _, _, _> = Panel::new(epd, spi);
+    let display = Display2in66::new();
+
+    // nothing to do while powered off
+    panel.power_off(&mut delay).unwrap();
+    panel.wait_until_idle(&mut delay).unwrap();
+    assert!(log.commands().is_empty());
+
+    panel.update(&display, &mut delay).unwrap();
+    panel.update(&display, &mut delay).unwrap();
+    assert_eq!(count(&log, INPUT_TEMPERATURE), 1);
+    panel.power_off(&mut delay).unwrap();
+    panel.power_off(&mut delay).unwrap();
+    assert_eq!(count(&log, POWER_OFF), 1);
+
+    // initialized again after the power off
+    panel.update(&display, &mut delay).unwrap();
+    assert_eq!(count(&log, INPUT_TEMPERATURE), 2);
+    assert_eq!(log.refreshes(), 3);
+
+    let (epd, _spi) = panel.release(&mut delay).unwrap();
+    assert_eq!(count(&log, POWER_OFF), 2);
+    assert_eq!(epd.refresh_count(), 3);
+}
+
+#[test]
+fn wait_until_idle_enters_the_idle_phase() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let mut epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    epd.set_phase_callback(Some(record));
+    let mut panel: Panel<296, 152, 5624, _, _, _, _, _> = Panel::new(epd, spi);
+
+    panel
+        .start_update(&Display2in66::new(), &mut delay)
+        .unwrap();
+    PHASES.with_borrow_mut(Vec::clear);
+    panel.wait_until_idle(&mut delay).unwrap();
+    assert_eq!(PHASES.take(), [UpdatePhase::Idle]);
+}
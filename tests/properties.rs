@@ -0,0 +1,87 @@
+//! Applies random drawing operations to the display buffers of each panel size at each
+//! rotation and compares the planes with a trivial reference, one color per pixel.
+//! Failures are shrunk by proptest to a minimal sequence of operations.
+
+mod common;
+
+use common::{operations, rotation, written_planes, Operation, Reference};
+use embedded_graphics::prelude::*;
+use epd_spectra::{
+    Display1in54, Display2in13, Display2in66, Display2in71, Display2in87, Display2in9,
+    Display3in70, Display4in17, Display4in37, DisplayBuffer, DisplayRotation,
+    InterleavedDisplay1in54, InterleavedDisplay2in13, InterleavedDisplay2in66,
+    InterleavedDisplay2in71, InterleavedDisplay2in87, InterleavedDisplay2in9,
+    InterleavedDisplay3in70, InterleavedDisplay4in17, InterleavedDisplay4in37, MonoDisplay1in54,
+    MonoDisplay2in13, MonoDisplay2in66, MonoDisplay2in71, MonoDisplay2in87, MonoDisplay2in9,
+    MonoDisplay3in70, MonoDisplay4in17, MonoDisplay4in37, TriColor,
+};
+use proptest::prelude::*;
+
+/// Applies `operations` to `display`, which has the given rotation and the unrotated size
+/// `width` x `height`, and compares the planes it sends with the reference. A buffer
+/// without chromatic plane (`mono`) has to send red as black.
+fn check<D>(
+    mut display: D,
+    (width, height): (u32, u32),
+    rotation: DisplayRotation,
+    operations: &[Operation],
+    mono: bool,
+) -> Result<(), TestCaseError>
+where
+    D: DrawTarget<Color = TriColor, Error = core::convert::Infallible> + DisplayBuffer,
+{
+    let size = display.bounding_box().size;
+    let mut reference = Reference::new(size);
+    for operation in operations {
+        operation.apply(&mut display, size, |p, color| reference.set(p, color));
+    }
+    let [mut black, mut red] = reference.planes(rotation, width as usize, height as usize);
+    if mono {
+        for (black, red) in black.iter_mut().zip(&mut red) {
+            *black |= *red;
+            *red = 0;
+        }
+    }
+    let [sent_black, sent_red] = written_planes(&display);
+    prop_assert!(sent_black == black, "black plane differs");
+    prop_assert!(sent_red == red, "chromatic plane differs");
+    Ok(())
+}
+
+macro_rules! conformance {
+    ($($name:ident: $display:ty, $interleaved:ty, $mono:ty;)*) => {
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(16))]
+            $(
+                #[test]
+                fn $name(rotation in rotation(), operations in operations()) {
+                    let size = (<$display>::WIDTH, <$display>::HEIGHT);
+
+                    let mut display = <$display>::new();
+                    display.set_rotation(rotation);
+                    check(display, size, rotation, &operations, false)?;
+
+                    let mut display = <$interleaved>::new();
+                    display.set_rotation(rotation);
+                    check(display, size, rotation, &operations, false)?;
+
+                    let mut display = <$mono>::new();
+                    display.set_rotation(rotation);
+                    check(display, size, rotation, &operations, true)?;
+                }
+            )*
+        }
+    };
+}
+
+conformance! {
+    display1in54: Display1in54, InterleavedDisplay1in54, MonoDisplay1in54;
+    display2in13: Display2in13, InterleavedDisplay2in13, MonoDisplay2in13;
+    display2in66: Display2in66, InterleavedDisplay2in66, MonoDisplay2in66;
+    display2in71: Display2in71, InterleavedDisplay2in71, MonoDisplay2in71;
+    display2in87: Display2in87, InterleavedDisplay2in87, MonoDisplay2in87;
+    display2in9: Display2in9, InterleavedDisplay2in9, MonoDisplay2in9;
+    display3in70: Display3in70, InterleavedDisplay3in70, MonoDisplay3in70;
+    display4in17: Display4in17, InterleavedDisplay4in17, MonoDisplay4in17;
+    display4in37: Display4in37, InterleavedDisplay4in37, MonoDisplay4in37;
+}
@@ -0,0 +1,87 @@
+use epd_spectra::{mock::MockLog, Display2in66, Epd, RefreshPolicy};
+
+#[test]
+fn maintenance_is_due_after_the_maximum_number_of_updates() {
+    let mut policy = RefreshPolicy::new().with_max_updates(3);
+    for now in 0..3 {
+        assert!(!policy.is_due(now));
+        policy.record_update(now);
+    }
+    assert_eq!(policy.updates(), 3);
+    assert!(policy.is_due(3));
+    assert_eq!(policy.time_until_maintenance(3), Some(0));
+
+    policy.record_maintenance(3);
+    assert_eq!(policy.updates(), 0);
+    assert!(!policy.is_due(4));
+}
+
+#[test]
+fn maintenance_is_due_after_the_maximum_interval() {
+    let mut policy = RefreshPolicy::new().with_max_interval(100);
+    policy.record_update(1_000);
+    assert!(!policy.is_due(1_099));
+    assert_eq!(policy.time_until_maintenance(1_040), Some(60));
+    assert!(policy.is_due(1_100));
+
+    // later updates do not move the start of the interval
+    policy.record_update(1_050);
+    assert!(policy.is_due(1_100));
+    policy.record_maintenance(1_100);
+    assert!(!policy.is_due(1_150));
+    assert_eq!(policy.time_until_maintenance(1_150), Some(50));
+}
+
+#[test]
+fn interval_starts_with_the_first_refresh() {
+    let policy = RefreshPolicy::new().with_max_interval(100);
+    assert!(!policy.is_due(u64::MAX));
+    assert_eq!(policy.time_until_maintenance(0), None);
+    assert_eq!(RefreshPolicy::new().time_until_maintenance(0), None);
+    assert!(!RefreshPolicy::new().is_due(u64::MAX));
+}
+
+#[test]
+fn update_refreshes_changed_frames_and_due_maintenance() {
+    let log = MockLog::default();
+    let (mut spi, mut delay) = (log.spi(), log.delay());
+    let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+    let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+    let display = Display2in66::new();
+    let mut policy = RefreshPolicy::new().with_max_updates(2);
+
+    // the first frame is unknown, so it is shown
+    assert!(policy
+        .update(&mut epd, &display, 0, &mut spi, &mut delay)
+        .unwrap());
+    assert!(!policy
+        .update(&mut epd, &display, 1, &mut spi, &mut delay)
+        .unwrap());
+    assert_eq!(log.refreshes(), 1);
+    assert_eq!(policy.updates(), 1);
+
+    let mut changed = Display2in66::new();
+    changed.set_inverted(true);
+    assert!(policy
+        .update(&mut epd, &changed, 2, &mut spi, &mut delay)
+        .unwrap());
+    assert_eq!(policy.updates(), 2);
+
+    // the unchanged frame is refreshed as maintenance
+    assert!(policy
+        .update(&mut epd, &changed, 3, &mut spi, &mut delay)
+        .unwrap());
+    assert_eq!(log.refreshes(), 3);
+    assert_eq!(policy.updates(), 0);
+    assert!(!policy
+        .update(&mut epd, &changed, 4, &mut spi, &mut delay)
+        .unwrap());
+}
+
+#[test]
+fn time_until_maintenance_does_not_overflow() {
+    let mut policy = RefreshPolicy::new().with_max_interval(u64::MAX);
+    policy.record_update(10);
+    assert!(!policy.is_due(20));
+    assert_eq!(policy.time_until_maintenance(20), Some(u64::MAX - 20));
+}
@@ -0,0 +1,255 @@
+use core::{convert::Infallible, marker::PhantomData};
+
+use embedded_graphics::{
+    image::GetPixel,
+    mono_font::{ascii::FONT_6X10, MonoTextStyle},
+    prelude::*,
+    primitives::Rectangle,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use embedded_hal::delay::DelayNs;
+use epd_spectra::{
+    Display2in66, Display2in66Landscape, DisplayBuffer, DisplayRotation, EpdDisplay, EpdPanel,
+    MonoDisplay2in66, TiledDisplay, TriColor,
+};
+
+/// Unrotated size of the 2.66" panel
+const WIDTH: usize = 152;
+const HEIGHT: usize = 296;
+
+/// Size of the 2.66" display at each rotation
+const ROTATED_SIZES: [(DisplayRotation, Size); 4] = [
+    (DisplayRotation::Rotate0, Size::new(152, 296)),
+    (DisplayRotation::Rotate90, Size::new(296, 152)),
+    (DisplayRotation::Rotate180, Size::new(152, 296)),
+    (DisplayRotation::Rotate270, Size::new(296, 152)),
+];
+
+/// Records the black pixels within `size`, the reference for the rotated display
+struct Ink {
+    size: Size,
+    points: Vec<Point>,
+}
+
+impl OriginDimensions for Ink {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl DrawTarget for Ink {
+    type Color = TriColor;
+    type Error = Infallible;
+
+    fn draw_iter<I: IntoIterator<Item = Pixel<TriColor>>>(
+        &mut self,
+        pixels: I,
+    ) -> Result<(), Infallible> {
+        let area = self.bounding_box();
+        self.points.extend(
+            pixels
+                .into_iter()
+                .filter(|Pixel(p, color)| *color == TriColor::Black && area.contains(*p))
+                .map(|Pixel(p, _)| p),
+        );
+        Ok(())
+    }
+}
+
+/// Draws a text at the top left and one at the bottom right corner of `target`
+fn draw_texts<D: DrawTarget<Color = TriColor>>(target: &mut D) -> Rectangle {
+    let character = MonoTextStyle::new(&FONT_6X10, TriColor::Black);
+    let area = target.bounding_box();
+    let top_left = Text::with_baseline("top left", Point::zero(), character, Baseline::Top);
+    let _ = top_left.draw(target);
+    let bottom_right = Text::with_text_style(
+        "bottom right",
+        area.bottom_right().unwrap(),
+        character,
+        TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Bottom)
+            .build(),
+    );
+    let _ = bottom_right.draw(target);
+    bottom_right.bounding_box()
+}
+
+/// Returns the pixel of the unrotated panel which shows the point `p` of the rotated display
+fn panel_pixel(rotation: DisplayRotation, p: Point) -> (usize, usize) {
+    let (x, y) = (usize::try_from(p.x).unwrap(), usize::try_from(p.y).unwrap());
+    match rotation {
+        DisplayRotation::Rotate0 => (x, y),
+        DisplayRotation::Rotate90 => (WIDTH - 1 - y, x),
+        DisplayRotation::Rotate180 => (WIDTH - 1 - x, HEIGHT - 1 - y),
+        DisplayRotation::Rotate270 => (y, HEIGHT - 1 - x),
+    }
+}
+
+/// Returns whether the pixel (x, y) of the unrotated panel is set, with 19 bytes per row
+fn is_black(plane: &[u8], (x, y): (usize, usize)) -> bool {
+    plane[y * 19 + x / 8] & (0x80 >> (x % 8)) != 0
+}
+
+#[test]
+fn text_is_laid_out_in_the_rotated_size() {
+    for (rotation, size) in ROTATED_SIZES {
+        let mut display = Display2in66::new();
+        display.set_rotation(rotation);
+        assert_eq!(display.size(), size, "{rotation:?}");
+        assert_eq!(
+            display.bounding_box(),
+            Rectangle::new(Point::zero(), size),
+            "{rotation:?}"
+        );
+
+        let text_area = draw_texts(&mut display);
+        // the right aligned text ends in the bottom right corner of the rotated display
+        assert_eq!(
+            text_area.bottom_right(),
+            display.bounding_box().bottom_right(),
+            "{rotation:?}"
+        );
+
+        let mut ink = Ink {
+            size,
+            points: Vec::new(),
+        };
+        draw_texts(&mut ink);
+        assert!(!ink.points.is_empty());
+        for &p in &ink.points {
+            assert!(
+                is_black(display.black_plane(), panel_pixel(rotation, p)),
+                "{rotation:?} {p:?}"
+            );
+            assert_eq!(
+                display.pixel(p),
+                Some(TriColor::Black),
+                "{rotation:?} {p:?}"
+            );
+        }
+        // nothing else is drawn, no text is clipped or wrapped around
+        let set_bits: u32 = display.black_plane().iter().map(|b| b.count_ones()).sum();
+        assert_eq!(set_bits as usize, ink.points.len(), "{rotation:?}");
+        assert!(display.chromatic_plane().iter().all(|&b| b == 0));
+    }
+}
+
+#[test]
+fn origin_is_in_the_rotated_corner_of_the_panel() {
+    for (rotation, corner) in [
+        (DisplayRotation::Rotate0, (0, 0)),
+        (DisplayRotation::Rotate90, (WIDTH - 1, 0)),
+        (DisplayRotation::Rotate180, (WIDTH - 1, HEIGHT - 1)),
+        (DisplayRotation::Rotate270, (0, HEIGHT - 1)),
+    ] {
+        let mut display = Display2in66::new();
+        display.set_rotation(rotation);
+        Pixel(Point::zero(), TriColor::Black)
+            .draw(&mut display)
+            .unwrap();
+        assert!(is_black(display.black_plane(), corner), "{rotation:?}");
+        assert_eq!(panel_pixel(rotation, Point::zero()), corner);
+    }
+}
+
+/// Panel without hardware, the tiles only depend on its display buffer
+struct NoPanel<F>(PhantomData<F>);
+
+struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+impl<F: DisplayBuffer + DrawTarget<Color = TriColor> + Default> EpdPanel for NoPanel<F> {
+    type Color = TriColor;
+    type Framebuffer = F;
+    type Delay = NoDelay;
+    type Error = Infallible;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn size(&self) -> Size {
+        Size::new(WIDTH as u32, HEIGHT as u32)
+    }
+    fn update(&mut self, _framebuffer: &F, _delay: &mut NoDelay) -> Result<(), Infallible> {
+        Ok(())
+    }
+    fn start_update(&mut self, _framebuffer: &F, _delay: &mut NoDelay) -> Result<(), Infallible> {
+        Ok(())
+    }
+    fn wait_until_idle(&mut self, _delay: &mut NoDelay) -> Result<(), Infallible> {
+        Ok(())
+    }
+    fn power_off(&mut self, _delay: &mut NoDelay) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// Draws the texts on two tiles side by side, which are rotated by `rotate` and have the
+/// size `tile_size` afterwards, and compares each tile with the reference
+fn check_tiles<F>(tile_size: Size, rotate: impl Fn(&mut F))
+where
+    F: DisplayBuffer
+        + DrawTarget<Color = TriColor, Error = Infallible>
+        + GetPixel<Color = TriColor>
+        + Default,
+{
+    let tiles = [
+        EpdDisplay::new(NoPanel::<F>(PhantomData)),
+        EpdDisplay::new(NoPanel::<F>(PhantomData)),
+    ];
+    let mut tiled = TiledDisplay::new(tiles, 2);
+    for tile in tiled.tiles_mut() {
+        rotate(tile.framebuffer_mut());
+    }
+    let size = Size::new(2 * tile_size.width, tile_size.height);
+    assert_eq!(tiled.size(), size);
+    assert_eq!(tiled.bounding_box(), Rectangle::new(Point::zero(), size));
+
+    let text_area = draw_texts(&mut tiled);
+    assert_eq!(
+        text_area.bottom_right(),
+        tiled.bounding_box().bottom_right()
+    );
+
+    let mut ink = Ink {
+        size,
+        points: Vec::new(),
+    };
+    draw_texts(&mut ink);
+    assert!(!ink.points.is_empty());
+    let width = i32::try_from(tile_size.width).unwrap();
+    for &p in &ink.points {
+        let (index, local) = if p.x < width {
+            (0, p)
+        } else {
+            (1, p - Point::new(width, 0))
+        };
+        let tile = tiled.tiles()[index].framebuffer();
+        assert_eq!(tile.pixel(local), Some(TriColor::Black), "{p:?}");
+    }
+    // nothing else is drawn, no text is clipped or wrapped around
+    let black: usize = tiled
+        .tiles()
+        .iter()
+        .map(|tile| {
+            let tile = tile.framebuffer();
+            tile.bounding_box()
+                .points()
+                .filter(|&p| tile.pixel(p) == Some(TriColor::Black))
+                .count()
+        })
+        .sum();
+    assert_eq!(black, ink.points.len());
+}
+
+#[test]
+fn tiles_are_laid_out_in_their_rotated_size() {
+    for (rotation, size) in ROTATED_SIZES {
+        check_tiles::<Display2in66>(size, |tile| tile.set_rotation(rotation));
+        check_tiles::<MonoDisplay2in66>(size, |tile| tile.set_rotation(rotation));
+    }
+    // the rotation of an oriented display is fixed by its type
+    check_tiles::<Display2in66Landscape>(Size::new(296, 152), |_| {});
+}
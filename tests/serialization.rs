@@ -0,0 +1,47 @@
+//! Round trips of display buffers through a serde format
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use epd_spectra::{Display2in9, TriColor};
+
+fn drawn() -> Display2in9 {
+    let mut display = Display2in9::new();
+    display
+        .fill_solid(
+            &Rectangle::new(Point::new(3, 5), Size::new(20, 7)),
+            TriColor::Black,
+        )
+        .unwrap();
+    Pixel(Point::new(100, 200), TriColor::Red)
+        .draw(&mut display)
+        .unwrap();
+    display
+}
+
+#[test]
+fn planes_are_restored() {
+    let display = drawn();
+    let json = serde_json::to_string(&display).unwrap();
+    let restored: Display2in9 = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.black_plane(), display.black_plane());
+    assert_eq!(restored.chromatic_plane(), display.chromatic_plane());
+}
+
+#[test]
+fn planes_of_the_wrong_size_are_rejected() {
+    let plane = vec![0_u8; Display2in9::PLANE_SIZE];
+    let short = vec![0_u8; Display2in9::PLANE_SIZE - 1];
+    let long = vec![0_u8; Display2in9::PLANE_SIZE + 1];
+    let json = |black: &[u8], red: &[u8]| format!(r#"{{"black":{black:?},"red":{red:?}}}"#);
+    assert!(serde_json::from_str::<Display2in9>(&json(&plane, &plane)).is_ok());
+    assert!(serde_json::from_str::<Display2in9>(&json(&short, &plane)).is_err());
+    assert!(serde_json::from_str::<Display2in9>(&json(&plane, &long)).is_err());
+}
+
+#[test]
+fn missing_and_duplicate_planes_are_rejected() {
+    let plane = vec![0_u8; Display2in9::PLANE_SIZE];
+    let missing = format!(r#"{{"black":{plane:?}}}"#);
+    let duplicate = format!(r#"{{"black":{plane:?},"black":{plane:?},"red":{plane:?}}}"#);
+    assert!(serde_json::from_str::<Display2in9>(&missing).is_err());
+    assert!(serde_json::from_str::<Display2in9>(&duplicate).is_err());
+}
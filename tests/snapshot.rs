@@ -0,0 +1,35 @@
+use embedded_graphics::prelude::*;
+use epd_spectra::{Display2in13, SizeError, TriColor};
+
+#[test]
+fn snapshot_restores_the_buffer() {
+    let mut display = Display2in13::new();
+    Pixel(Point::new(3, 7), TriColor::Black)
+        .draw(&mut display)
+        .unwrap();
+    Pixel(Point::new(50, 100), TriColor::Red)
+        .draw(&mut display)
+        .unwrap();
+    let snapshot: Vec<u8> = display.bytes().collect();
+    assert_eq!(snapshot.len(), Display2in13::SNAPSHOT_SIZE);
+
+    let mut restored = Display2in13::new();
+    assert_eq!(restored.load_bytes(&snapshot), Ok(()));
+    assert_eq!(restored.black_plane(), display.black_plane());
+    assert_eq!(restored.chromatic_plane(), display.chromatic_plane());
+    assert_eq!(restored.take_dirty_region(), Some(restored.bounding_box()));
+}
+
+#[test]
+fn snapshot_of_wrong_size_is_rejected() {
+    let mut display = Display2in13::new();
+    assert_eq!(
+        display.load_bytes(&[0xff; 10]),
+        Err(SizeError {
+            expected: Display2in13::SNAPSHOT_SIZE,
+            actual: 10
+        })
+    );
+    assert!(display.black_plane().iter().all(|&byte| byte == 0));
+    assert_eq!(display.take_dirty_region(), None);
+}
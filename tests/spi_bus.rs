@@ -0,0 +1,143 @@
+use std::{cell::RefCell, convert::Infallible, rc::Rc};
+
+use embedded_hal::{
+    digital::{self, OutputPin},
+    spi::{self, Error as _, ErrorKind, Operation, SpiBus, SpiDevice},
+};
+use epd_spectra::{SpiBusDevice, SpiBusError};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Event {
+    Cs(bool),
+    Write(Vec<u8>),
+    Read(usize),
+    Flush,
+}
+
+type Log = Rc<RefCell<Vec<Event>>>;
+
+#[derive(Debug, PartialEq, Eq)]
+struct BusError;
+
+impl spi::Error for BusError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Overrun
+    }
+}
+
+/// Bus which records its operations and fails writes if `fail` is set
+struct MockBus {
+    log: Log,
+    fail: bool,
+}
+
+impl spi::ErrorType for MockBus {
+    type Error = BusError;
+}
+
+impl SpiBus for MockBus {
+    fn read(&mut self, words: &mut [u8]) -> Result<(), BusError> {
+        words.fill(0xa5);
+        self.log.borrow_mut().push(Event::Read(words.len()));
+        Ok(())
+    }
+    fn write(&mut self, words: &[u8]) -> Result<(), BusError> {
+        if self.fail {
+            return Err(BusError);
+        }
+        self.log.borrow_mut().push(Event::Write(words.to_vec()));
+        Ok(())
+    }
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), BusError> {
+        self.write(write)?;
+        self.read(read)
+    }
+    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), BusError> {
+        self.write(words)?;
+        self.read(words)
+    }
+    fn flush(&mut self) -> Result<(), BusError> {
+        self.log.borrow_mut().push(Event::Flush);
+        Ok(())
+    }
+}
+
+struct MockCs(Log);
+
+impl digital::ErrorType for MockCs {
+    type Error = Infallible;
+}
+
+impl OutputPin for MockCs {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        self.0.borrow_mut().push(Event::Cs(false));
+        Ok(())
+    }
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        self.0.borrow_mut().push(Event::Cs(true));
+        Ok(())
+    }
+}
+
+fn device(fail: bool) -> (SpiBusDevice<MockBus, MockCs>, Log) {
+    let log = Log::default();
+    let bus = MockBus {
+        log: log.clone(),
+        fail,
+    };
+    let device = SpiBusDevice::new(bus, MockCs(log.clone())).unwrap();
+    (device, log)
+}
+
+#[test]
+fn chip_select_is_asserted_around_each_transaction() {
+    let (mut device, log) = device(false);
+    assert_eq!(log.take(), [Event::Cs(true)]);
+
+    device.write(&[1, 2]).unwrap();
+    let mut read = [0; 3];
+    device
+        .transaction(&mut [Operation::Write(&[3]), Operation::Read(&mut read)])
+        .unwrap();
+    assert_eq!(read, [0xa5; 3]);
+    assert_eq!(
+        log.take(),
+        [
+            Event::Cs(false),
+            Event::Write(vec![1, 2]),
+            Event::Flush,
+            Event::Cs(true),
+            Event::Cs(false),
+            Event::Write(vec![3]),
+            Event::Read(3),
+            Event::Flush,
+            Event::Cs(true),
+        ]
+    );
+}
+
+#[test]
+fn chip_select_is_released_after_errors() {
+    let (mut device, log) = device(true);
+    log.take();
+    let result = device.transaction(&mut [Operation::Write(&[1]), Operation::Read(&mut [0])]);
+    assert_eq!(result, Err(SpiBusError::Spi(BusError)));
+    // the operations after the failed one are skipped
+    assert_eq!(
+        log.take(),
+        [Event::Cs(false), Event::Flush, Event::Cs(true)]
+    );
+}
+
+#[test]
+fn delays_are_rejected() {
+    let (mut device, log) = device(false);
+    log.take();
+    let result = device.transaction(&mut [Operation::DelayNs(1_000), Operation::Write(&[1])]);
+    assert_eq!(result, Err(SpiBusError::Delay));
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::Other);
+    assert_eq!(
+        log.take(),
+        [Event::Cs(false), Event::Flush, Event::Cs(true)]
+    );
+}
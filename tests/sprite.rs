@@ -0,0 +1,86 @@
+use embedded_graphics::{image::GetPixel, prelude::*, primitives::Rectangle};
+use epd_spectra::{Display2in9, PlanarImage, SpriteSheet, TriColor};
+
+/// Width and height of the sheet, with an incomplete column and row of sprites
+const SHEET: Size = Size::new(26, 9);
+const SPRITE: Size = Size::new(8, 4);
+
+fn color(p: Point) -> TriColor {
+    match (p.x * 7 + p.y * 3) % 3 {
+        0 => TriColor::White,
+        1 => TriColor::Black,
+        _ => TriColor::Red,
+    }
+}
+
+/// Returns the black and the red plane of the sheet
+#[allow(clippy::cast_sign_loss)]
+fn planes() -> (Vec<u8>, Vec<u8>) {
+    let row_size = SHEET.width.div_ceil(8) as usize;
+    let len = row_size * SHEET.height as usize;
+    let (mut black, mut red) = (vec![0; len], vec![0; len]);
+    for p in Rectangle::new(Point::zero(), SHEET).points() {
+        let (x, y) = (p.x as usize, p.y as usize);
+        let plane = match color(p) {
+            TriColor::White => continue,
+            TriColor::Black => &mut black,
+            TriColor::Red => &mut red,
+        };
+        plane[y * row_size + x / 8] |= 0x80 >> (x % 8);
+    }
+    (black, red)
+}
+
+#[test]
+fn sprites_are_numbered_row_by_row() {
+    let (black, red) = planes();
+    let sheet = SpriteSheet::new(PlanarImage::new(&black, &red, SHEET.width), SPRITE);
+    assert_eq!(sheet.len(), 6);
+    assert!(!sheet.is_empty());
+    assert_eq!(
+        sheet.sprite_area(0),
+        Some(Rectangle::new(Point::zero(), SPRITE))
+    );
+    assert_eq!(
+        sheet.sprite_area(4),
+        Some(Rectangle::new(Point::new(8, 4), SPRITE))
+    );
+    assert_eq!(sheet.sprite_area(6), None);
+
+    let empty = SpriteSheet::new(PlanarImage::new(&black, &red, SHEET.width), Size::zero());
+    assert!(empty.is_empty());
+    assert_eq!(empty.sprite_area(0), None);
+}
+
+#[test]
+fn draw_sprite_copies_the_pixels_of_the_sprite() {
+    let (black, red) = planes();
+    let sheet = SpriteSheet::new(PlanarImage::new(&black, &red, SHEET.width), SPRITE);
+    for index in 0..sheet.len() {
+        let mut display = Display2in9::new();
+        let top_left = Point::new(30, 50);
+        sheet.draw_sprite(index, top_left, &mut display).unwrap();
+
+        let source = sheet.sprite_area(index).unwrap().top_left;
+        let target = Rectangle::new(top_left, SPRITE);
+        for p in display.bounding_box().points() {
+            let expected = if target.contains(p) {
+                color(p - top_left + source)
+            } else {
+                TriColor::White
+            };
+            assert_eq!(display.pixel(p), Some(expected), "sprite {index} at {p:?}");
+        }
+    }
+}
+
+#[test]
+fn sprites_outside_the_sheet_are_not_drawn() {
+    let (black, red) = planes();
+    let sheet = SpriteSheet::new(PlanarImage::new(&black, &red, SHEET.width), SPRITE);
+    let mut display = Display2in9::new();
+    sheet.draw_sprite(6, Point::zero(), &mut display).unwrap();
+    assert!(sheet.sprite(6).is_none());
+    assert!(display.black_plane().iter().all(|&byte| byte == 0));
+    assert!(display.chromatic_plane().iter().all(|&byte| byte == 0));
+}
@@ -0,0 +1,54 @@
+use embedded_graphics::{
+    mono_font::{ascii::FONT_6X10, MonoTextStyle},
+    prelude::*,
+    primitives::{Circle, PrimitiveStyle, Rectangle},
+    text::Text,
+};
+use epd_spectra::{mock::MockLog, Display2in66, DisplayRotation, Epd, Strip, TriColor};
+
+fn draw<D: DrawTarget<Color = TriColor>>(target: &mut D) {
+    let _ = Rectangle::new(Point::new(3, 5), Size::new(40, 120))
+        .into_styled(PrimitiveStyle::with_fill(TriColor::Red))
+        .draw(target);
+    let _ = Circle::new(Point::new(20, 60), 90)
+        .into_styled(PrimitiveStyle::with_stroke(TriColor::Black, 3))
+        .draw(target);
+    let _ = Text::new(
+        "strip",
+        Point::new(30, 40),
+        MonoTextStyle::new(&FONT_6X10, TriColor::Black),
+    )
+    .draw(target);
+}
+
+#[test]
+fn strips_match_display() {
+    for rotation in [
+        DisplayRotation::Rotate0,
+        DisplayRotation::Rotate90,
+        DisplayRotation::Rotate180,
+        DisplayRotation::Rotate270,
+    ] {
+        let mut display = Display2in66::new();
+        display.set_rotation(rotation);
+        draw(&mut display);
+
+        let log = MockLog::default();
+        let (mut spi, mut delay) = (log.spi(), log.delay());
+        let epd = Epd::new(&mut spi, log.busy(), log.dc(), log.rst(), &mut delay, 0);
+        let mut epd = epd.init(&mut spi, &mut delay).unwrap();
+        // 19 bytes per row, the last strip is shorter
+        let mut strip: Strip<296, 152, { 19 * 21 }> = Strip::new();
+        strip.set_rotation(rotation);
+        epd.update_strips(&mut strip, draw, &mut spi, &mut delay)
+            .unwrap();
+
+        let frame: Display2in66 = log.frame().unwrap();
+        assert_eq!(frame.black_plane(), display.black_plane(), "{rotation:?}");
+        assert_eq!(
+            frame.chromatic_plane(),
+            display.chromatic_plane(),
+            "{rotation:?}"
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use embedded_graphics::{image::GetPixel, pixelcolor::Rgb888, prelude::*};
+use epd_spectra::{Display2in66, TerminalTarget, TriColor};
+
+use TriColor::{Black, Red, White};
+
+/// ANSI colors of the xterm palette with the colors shown on a dark and on a light background
+const COLORS: [(&str, Rgb888, TriColor, TriColor); 11] = [
+    ("black", Rgb888::new(0, 0, 0), White, Black),
+    ("red", Rgb888::new(205, 0, 0), Red, Red),
+    ("green", Rgb888::new(0, 205, 0), Black, Black),
+    ("yellow", Rgb888::new(205, 205, 0), Black, Black),
+    ("blue", Rgb888::new(0, 0, 238), Black, Black),
+    ("magenta", Rgb888::new(205, 0, 205), Black, Black),
+    ("cyan", Rgb888::new(0, 205, 205), Black, Black),
+    ("white", Rgb888::new(229, 229, 229), Black, White),
+    ("bright black", Rgb888::new(127, 127, 127), White, Black),
+    ("bright red", Rgb888::new(255, 0, 0), Red, Red),
+    ("bright white", Rgb888::new(255, 255, 255), Black, White),
+];
+
+/// Returns the colors drawn for the terminal colors
+fn drawn(dark_background: bool) -> Vec<TriColor> {
+    let mut target = TerminalTarget::new(Display2in66::new());
+    target.set_dark_background(dark_background);
+    let points = (0..).map(|x| Point::new(x, 0));
+    for ((_, color, _, _), p) in COLORS.iter().zip(points.clone()) {
+        Pixel(p, *color).draw(&mut target).unwrap();
+    }
+    points
+        .take(COLORS.len())
+        .map(|p| target.target().pixel(p).unwrap())
+        .collect()
+}
+
+#[test]
+fn terminal_colors_are_mapped_for_a_dark_background() {
+    for ((name, _, expected, _), color) in COLORS.iter().zip(drawn(true)) {
+        assert_eq!(color, *expected, "{name}");
+    }
+}
+
+#[test]
+fn terminal_colors_are_mapped_for_a_light_background() {
+    for ((name, _, _, expected), color) in COLORS.iter().zip(drawn(false)) {
+        assert_eq!(color, *expected, "{name}");
+    }
+}
+
+#[test]
+fn solid_fills_are_mapped_like_pixels() {
+    let mut target = TerminalTarget::new(Display2in66::new());
+    target.clear(Rgb888::BLACK).unwrap();
+    assert_eq!(target.target().pixel(Point::new(10, 10)), Some(White));
+    let area = target.bounding_box();
+    target.fill_solid(&area, Rgb888::new(205, 0, 0)).unwrap();
+    assert_eq!(target.target().pixel(Point::new(10, 10)), Some(Red));
+}
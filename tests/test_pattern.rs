@@ -0,0 +1,85 @@
+use embedded_graphics::{image::GetPixel, prelude::*};
+use epd_spectra::{Display2in66, TestPattern, TriColor};
+
+use TriColor::{Black, Red, White};
+
+fn draw(pattern: TestPattern) -> Display2in66 {
+    let mut display = Display2in66::new();
+    pattern.draw(&mut display).unwrap();
+    display
+}
+
+fn at(display: &Display2in66, x: i32, y: i32) -> TriColor {
+    display.pixel(Point::new(x, y)).unwrap()
+}
+
+#[test]
+fn color_bars_have_equal_widths() {
+    // 152 pixels: bars of 51, 51 and 50 columns
+    let display = draw(TestPattern::ColorBars);
+    for y in [0, 295] {
+        assert_eq!(at(&display, 0, y), White);
+        assert_eq!(at(&display, 50, y), White);
+        assert_eq!(at(&display, 51, y), Black);
+        assert_eq!(at(&display, 101, y), Black);
+        assert_eq!(at(&display, 102, y), Red);
+        assert_eq!(at(&display, 151, y), Red);
+    }
+}
+
+#[test]
+fn checkerboard_alternates_squares() {
+    let display = draw(TestPattern::Checkerboard {
+        size: 4,
+        colors: [Black, Red],
+    });
+    assert_eq!(at(&display, 3, 3), Black);
+    assert_eq!(at(&display, 4, 3), Red);
+    assert_eq!(at(&display, 3, 4), Red);
+    assert_eq!(at(&display, 4, 4), Black);
+
+    // a size of 0 is handled like 1
+    let display = draw(TestPattern::Checkerboard {
+        size: 0,
+        colors: [Black, Red],
+    });
+    assert_eq!(at(&display, 0, 0), Black);
+    assert_eq!(at(&display, 1, 0), Red);
+    assert_eq!(at(&display, 0, 1), Red);
+    assert_eq!(at(&display, 1, 1), Black);
+}
+
+#[test]
+fn grid_draws_pixels_at_the_spacing() {
+    let display = draw(TestPattern::Grid {
+        spacing: 10,
+        color: Red,
+        background: White,
+    });
+    assert_eq!(at(&display, 0, 0), Red);
+    assert_eq!(at(&display, 10, 20), Red);
+    assert_eq!(at(&display, 10, 21), White);
+    assert_eq!(at(&display, 5, 0), White);
+
+    // a spacing of 0 is handled like 1, so every pixel is drawn
+    let display = draw(TestPattern::Grid {
+        spacing: 0,
+        color: Black,
+        background: White,
+    });
+    assert!(display
+        .bounding_box()
+        .points()
+        .all(|p| display.pixel(p) == Some(Black)));
+}
+
+#[test]
+fn stripes_alternate_rows_and_columns() {
+    let colors = [White, Black];
+    let display = draw(TestPattern::HorizontalStripes { width: 3, colors });
+    assert_eq!(at(&display, 100, 2), White);
+    assert_eq!(at(&display, 100, 3), Black);
+    let display = draw(TestPattern::VerticalStripes { width: 3, colors });
+    assert_eq!(at(&display, 2, 100), White);
+    assert_eq!(at(&display, 3, 100), Black);
+}
@@ -0,0 +1,174 @@
+//! Checks the operations which create or transform the content of a display buffer as a
+//! whole against the reference of `tests/common`, pixel by pixel
+
+mod common;
+
+use common::{color, operations, rotation, Operation, Reference};
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use epd_spectra::{Display2in9, DisplayRotation};
+use proptest::prelude::*;
+
+/// Returns a display with `rotation` and the reference after applying `operations` to both.
+/// The 2.9" display has an odd number of bytes per row.
+fn drawn(rotation: DisplayRotation, operations: &[Operation]) -> (Display2in9, Reference) {
+    let mut display = Display2in9::new();
+    display.set_rotation(rotation);
+    let size = display.size();
+    let mut reference = Reference::new(size);
+    for operation in operations {
+        operation.apply(&mut display, size, |p, color| reference.set(p, color));
+    }
+    (display, reference)
+}
+
+fn planes(reference: &Reference, rotation: DisplayRotation) -> [Vec<u8>; 2] {
+    let (width, height) = (Display2in9::WIDTH as usize, Display2in9::HEIGHT as usize);
+    reference.planes(rotation, width, height)
+}
+
+fn assert_matches(display: &Display2in9, reference: &Reference) -> Result<(), TestCaseError> {
+    let [black, red] = planes(reference, display.rotation());
+    prop_assert!(display.black_plane() == black, "black plane differs");
+    prop_assert!(display.chromatic_plane() == red, "chromatic plane differs");
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn raw_planes_are_restored(rotation in rotation(), operations in operations()) {
+        let (_, reference) = drawn(rotation, &operations);
+        let [black, red] = planes(&reference, rotation);
+        let mut display = Display2in9::from_raw_planes(&black, &red).unwrap();
+        display.set_rotation(rotation);
+        assert_matches(&display, &reference)?;
+        for Pixel(p, color) in display.pixels() {
+            prop_assert_eq!(Some(color), reference.get(p), "{:?}", p);
+        }
+    }
+}
+
+/// Returns the reference transformed by `source`, which returns the point of each pixel
+/// before the transformation
+fn transformed(reference: &Reference, source: impl Fn(Point, Size) -> Point) -> Reference {
+    Reference::from_fn(reference.size, |p| {
+        reference.get(source(p, reference.size)).unwrap()
+    })
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn mirrored_x(p: Point, size: Size) -> Point {
+    Point::new(size.width as i32 - 1 - p.x, p.y)
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn mirrored_y(p: Point, size: Size) -> Point {
+    Point::new(p.x, size.height as i32 - 1 - p.y)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn rotate_180_turns_the_content(rotation in rotation(), operations in operations()) {
+        let (mut display, reference) = drawn(rotation, &operations);
+        display.rotate_180();
+        let reference = transformed(&reference, |p, size| mirrored_x(mirrored_y(p, size), size));
+        assert_matches(&display, &reference)?;
+    }
+
+    #[test]
+    fn flip_horizontal_mirrors_the_columns(rotation in rotation(), operations in operations()) {
+        let (mut display, reference) = drawn(rotation, &operations);
+        display.flip_horizontal();
+        assert_matches(&display, &transformed(&reference, mirrored_x))?;
+    }
+
+    #[test]
+    fn flip_vertical_mirrors_the_rows(rotation in rotation(), operations in operations()) {
+        let (mut display, reference) = drawn(rotation, &operations);
+        display.flip_vertical();
+        assert_matches(&display, &transformed(&reference, mirrored_y))?;
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn scroll_up_moves_the_rows_up(
+        rotation in rotation(),
+        operations in operations(),
+        rows in 0..500_u32,
+        fill in color(),
+    ) {
+        let (mut display, reference) = drawn(rotation, &operations);
+        let band = display.scroll_up(rows, fill);
+        let size = reference.size;
+        let rows = rows.min(size.height);
+        let expected_band = Rectangle::new(
+            Point::new(0, i32::try_from(size.height - rows).unwrap()),
+            Size::new(size.width, rows),
+        );
+        prop_assert_eq!(band, expected_band);
+        let reference = Reference::from_fn(size, |p| {
+            reference.get(p + Point::new(0, i32::try_from(rows).unwrap())).unwrap_or(fill)
+        });
+        assert_matches(&display, &reference)?;
+    }
+
+    #[test]
+    fn scroll_down_moves_the_rows_down(
+        rotation in rotation(),
+        operations in operations(),
+        rows in 0..500_u32,
+        fill in color(),
+    ) {
+        let (mut display, reference) = drawn(rotation, &operations);
+        let band = display.scroll_down(rows, fill);
+        let size = reference.size;
+        let rows = rows.min(size.height);
+        prop_assert_eq!(band, Rectangle::new(Point::zero(), Size::new(size.width, rows)));
+        let reference = Reference::from_fn(size, |p| {
+            reference.get(p - Point::new(0, i32::try_from(rows).unwrap())).unwrap_or(fill)
+        });
+        assert_matches(&display, &reference)?;
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn draw_bitmap_sets_the_pixels_of_set_bits(
+        rotation in rotation(),
+        operations in operations(),
+        bitmap in proptest::collection::vec(any::<u8>(), 0..256),
+        width in 1..80_u32,
+        top_left in (-40..200_i32, -40..400_i32).prop_map(|(x, y)| Point::new(x, y)),
+        color in color(),
+    ) {
+        let (mut display, mut reference) = drawn(rotation, &operations);
+        display.draw_bitmap(&bitmap, width, top_left, color);
+
+        let row_bytes = width.div_ceil(8) as usize;
+        for (y, row) in bitmap.chunks_exact(row_bytes).enumerate() {
+            for x in 0..width as usize {
+                if row[x / 8] & (0x80 >> (x % 8)) != 0 {
+                    let offset = Point::new(i32::try_from(x).unwrap(), i32::try_from(y).unwrap());
+                    reference.set(top_left + offset, color);
+                }
+            }
+        }
+        assert_matches(&display, &reference)?;
+    }
+}
+
+#[test]
+fn raw_planes_of_the_wrong_size_are_rejected() {
+    let plane = [0; Display2in9::PLANE_SIZE];
+    assert!(Display2in9::from_raw_planes(&plane, &plane).is_some());
+    assert!(Display2in9::from_raw_planes(&plane[1..], &plane).is_none());
+    assert!(Display2in9::from_raw_planes(&plane, &[0; Display2in9::PLANE_SIZE + 1]).is_none());
+}
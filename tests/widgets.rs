@@ -0,0 +1,117 @@
+use embedded_graphics::{
+    image::GetPixel, mono_font::ascii::FONT_6X10, prelude::*, primitives::Rectangle,
+};
+use epd_spectra::{
+    widgets::{Bar, List},
+    Display2in66, TriColor,
+};
+
+const ITEMS: [&str; 12] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+    "twelve",
+];
+
+/// Area of 5 rows of 14 pixels with FONT_6X10
+fn area() -> Rectangle {
+    Rectangle::new(Point::zero(), Size::new(100, 70))
+}
+
+#[test]
+fn list_shows_the_page_of_the_selected_item() {
+    let mut list = List::new(&ITEMS, &FONT_6X10, area());
+    assert_eq!(list.rows_per_page(), 5);
+    assert_eq!(list.first_visible(), 0);
+    list.set_selected(4);
+    assert_eq!(list.first_visible(), 0);
+    list.set_selected(5);
+    assert_eq!(list.first_visible(), 5);
+    list.set_selected(100);
+    assert_eq!(list.selected(), 11);
+    assert_eq!(list.first_visible(), 10);
+}
+
+#[test]
+fn selection_wraps_around() {
+    let mut list = List::new(&ITEMS, &FONT_6X10, area());
+    list.select_previous();
+    assert_eq!(list.selected(), 11);
+    list.select_next();
+    assert_eq!(list.selected(), 0);
+    list.select_next();
+    assert_eq!(list.selected(), 1);
+
+    let mut empty = List::new(&[], &FONT_6X10, area());
+    empty.select_next();
+    empty.select_previous();
+    assert_eq!(empty.selected(), 0);
+}
+
+/// Returns the colors of the scroll bar column from the top to the bottom of the area
+fn scroll_bar(list: &List<'_>) -> Vec<TriColor> {
+    let mut display = Display2in66::new();
+    list.draw(&mut display).unwrap();
+    (0..70)
+        .map(|y| display.pixel(Point::new(97, y)).unwrap())
+        .collect()
+}
+
+#[test]
+fn scroll_thumb_covers_the_visible_page() {
+    let mut list = List::new(&ITEMS, &FONT_6X10, area());
+    // 5 of 12 items on the first page: 70 * 5 / 12 = 29 pixels
+    let column = scroll_bar(&list);
+    assert!(column[..29].iter().all(|&c| c == TriColor::Black));
+    assert!(column[29..].iter().all(|&c| c == TriColor::White));
+
+    // the last page starts at 70 * 10 / 12 = 58 and is cut at the end of the track
+    list.set_selected(11);
+    let column = scroll_bar(&list);
+    assert!(column[..58].iter().all(|&c| c == TriColor::White));
+    assert!(column[58..].iter().all(|&c| c == TriColor::Black));
+
+    // the selected second row of the page is red up to the scroll bar
+    let mut display = Display2in66::new();
+    list.draw(&mut display).unwrap();
+    assert_eq!(display.pixel(Point::new(0, 14)), Some(TriColor::Red));
+    assert_eq!(display.pixel(Point::new(95, 27)), Some(TriColor::Red));
+    assert_eq!(display.pixel(Point::new(0, 0)), Some(TriColor::White));
+}
+
+#[test]
+fn lists_which_fit_have_no_scroll_bar() {
+    let list = List::new(&ITEMS[..3], &FONT_6X10, area());
+    let column = scroll_bar(&list);
+    // the selected first row is red over the whole width
+    assert!(column[..14].iter().all(|&c| c == TriColor::Red));
+    assert!(column[14..].iter().all(|&c| c == TriColor::White));
+}
+
+#[test]
+fn bars_split_the_bounds() {
+    let bounds = Rectangle::new(Point::new(5, 20), Size::new(50, 100));
+    let header = Bar::header("Menu", &FONT_6X10);
+    assert_eq!(header.height(), 14);
+    assert_eq!(
+        header.area(&bounds),
+        Rectangle::new(Point::new(5, 20), Size::new(50, 14))
+    );
+    assert_eq!(
+        header.remaining(&bounds),
+        Rectangle::new(Point::new(5, 34), Size::new(50, 86))
+    );
+
+    let footer = Bar::footer("OK", &FONT_6X10);
+    assert_eq!(
+        footer.area(&bounds),
+        Rectangle::new(Point::new(5, 106), Size::new(50, 14))
+    );
+    assert_eq!(
+        footer.remaining(&bounds),
+        Rectangle::new(Point::new(5, 20), Size::new(50, 86))
+    );
+
+    // the bar is limited to small bounds
+    let small = Rectangle::new(Point::zero(), Size::new(50, 10));
+    assert_eq!(footer.area(&small), small);
+    assert_eq!(footer.remaining(&small).size, Size::new(50, 0));
+}